@@ -1,24 +1,161 @@
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
 
+use flate2::{Compression, write::DeflateEncoder};
+use levenshtein_automata::{Distance, LevenshteinAutomatonBuilder};
+use paltoquet::tokenizers::FingerprintTokenizer;
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
-use crate::CliResult;
-use crate::collections::{HashMap, hash_map::Entry};
+use crate::collections::{HashMap, IndexMap, hash_map::Entry, new_index_map};
 use crate::config::{Config, Delimiter};
 use crate::moonblade::Program;
 use crate::select::SelectedColumns;
 use crate::util;
+use crate::{CliError, CliResult};
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(try_from = "String")]
+enum Method {
+    KeyCollision,
+    Fingerprint,
+    NgramFingerprint,
+    Levenshtein,
+    JaroWinkler,
+    Ppm,
+}
+
+impl Method {
+    fn default_radius(&self) -> Option<f64> {
+        match self {
+            Self::Levenshtein => Some(1.0),
+            Self::JaroWinkler => Some(0.1),
+            Self::Ppm => Some(2.0),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<String> for Method {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(match value.as_str() {
+            "key-collision" => Self::KeyCollision,
+            "fingerprint" => Self::Fingerprint,
+            "ngram-fingerprint" => Self::NgramFingerprint,
+            "levenshtein" => Self::Levenshtein,
+            "jaro-winkler" => Self::JaroWinkler,
+            "ppm" => Self::Ppm,
+            _ => return Err(format!("unsupported --method \"{}\"", &value)),
+        })
+    }
+}
+
+fn ngram_fingerprint(string: &str, n: usize) -> String {
+    let normalized = unidecode::unidecode(string)
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect::<Vec<_>>();
+
+    if normalized.len() <= n {
+        return normalized.into_iter().collect();
+    }
+
+    let mut ngrams = normalized
+        .windows(n)
+        .map(|window| window.iter().collect::<String>())
+        .collect::<Vec<_>>();
+
+    ngrams.sort();
+    ngrams.dedup();
+
+    ngrams.concat()
+}
 
 static USAGE: &str = "
-TODO...
+Find clusters of similar values in a column of CSV data, in order to
+normalize them, in the spirit of OpenRefine's clustering feature.
+
+Supported methods (-m/--method):
+
+Key collision methods, finding values sharing a same key:
+    key-collision      - cells are only transformed by the -k/--key
+                         expression, if given.
+    fingerprint        - lowercased, deaccented, punctuation-stripped,
+                         sorted & deduplicated tokens.
+    ngram-fingerprint  - sorted & deduplicated character n-grams of the
+                         lowercased & deaccented alphanumeric characters.
+                         Size of n-grams can be set using --ngrams.
+
+Nearest neighbour methods, finding values whose distance is lower or equal
+to -r/--radius:
+    levenshtein   - Levenshtein edit distance. Radius must be an integer
+                    between 0 & 3 and defaults to 1.
+    jaro-winkler  - Jaro-Winkler distance, i.e. 1 - similarity. Radius
+                    defaults to 0.1.
+    ppm           - normalized compression distance, in the same scale as
+                    OpenRefine's PPM method, but relying on deflate
+                    compression. Radius defaults to 2.
+
+Clusters of nearest neighbour methods are the connected components of the
+graph of values within radius of each other. To avoid comparing all pairs of
+distinct values, those methods rely on blocking, i.e. values are only compared
+when they share a character n-gram. The size of those n-grams is capped by
+the -b/--block-size flag and derived from the radius & the length of the values
+so that values within Levenshtein radius of each other are guaranteed to share
+one. Since jaro-winkler & ppm distances cannot be expressed as a number of
+edits, their blocking is only a heuristic assuming a number of edits
+proportional to the radius & the length of the values. Use -b 0 to compare all
+pairs of distinct values.
+
+Note that the -k/--key expression, if given, is evaluated before applying any
+method, e.g. to lowercase values before computing Levenshtein distances.
+
+Clusters are written in TOML format, sorted by decreasing number of distinct
+values, and can be edited by hand to decide which ones to harmonize, using
+their `harmonize` & `replace_with` fields.
+
+Edited clusters can then be applied back to the data using -A/--apply, which
+will rewrite the clustered column of every row whose value belongs to a cluster
+marked with `harmonize = true`, using its `replace_with` value. The number of
+changed cells is reported on stderr.
+
+    $ xan cluster -m fingerprint name data.csv > clusters.toml
+    $ # edit clusters.toml...
+    $ xan cluster name --apply clusters.toml data.csv > harmonized.csv
+
+If the file given to -A/--apply does not end with \".toml\", it will be read
+as CSV instead, e.g. as produced by:
+
+    $ xan from -f toml --root cluster clusters.toml > clusters.csv
+
+Such a CSV file must have a \"harmonize\" column containing \"true\" or \"false\",
+a \"replace_with\" column and a \"values\" column containing the JSON list of
+clustered values.
 
 Usage:
     xan cluster <column> [options] [<input>]
     xan cluster --help
 
 cluster options:
-    -k, --key <expr>  An expression to evaluate to generate a key
-                      for each row by transforming the selected cell.
+    -m, --method <method>   Clustering method to use. Must be one of
+                            \"key-collision\", \"fingerprint\",
+                            \"ngram-fingerprint\", \"levenshtein\",
+                            \"jaro-winkler\" or \"ppm\".
+                            [default: key-collision]
+    -k, --key <expr>        An expression to evaluate to generate a key
+                            for each row by transforming the selected cell.
+    --ngrams <n>            Size of n-grams used by the ngram-fingerprint method.
+                            [default: 2]
+    -r, --radius <r>        Maximum distance for nearest neighbour methods.
+    -b, --block-size <n>    Maximum size of character n-grams used for blocking
+                            by nearest neighbour methods. [default: 6]
+    -A, --apply <clusters>  Apply clusters marked with `harmonize = true` in
+                            given file to the clustered column, instead of
+                            finding clusters.
+    --into <name>           When using -A/--apply, write harmonized values in
+                            a new column with given name instead of
+                            replacing the clustered column.
 
 Common options:
     -h, --help               Display this message
@@ -33,7 +170,13 @@ Common options:
 struct Args {
     arg_column: SelectedColumns,
     arg_input: Option<String>,
+    flag_method: Method,
     flag_key: Option<String>,
+    flag_ngrams: usize,
+    flag_radius: Option<f64>,
+    flag_block_size: usize,
+    flag_apply: Option<String>,
+    flag_into: Option<String>,
     flag_no_headers: bool,
     flag_output: Option<String>,
     flag_delimiter: Option<Delimiter>,
@@ -52,20 +195,128 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
     let sel_index = rconf.single_selection(headers)?;
 
+    if let Some(path) = &args.flag_apply {
+        let replacements = read_replacements(path)?;
+        let mut writer = Config::new(&args.flag_output).simd_writer()?;
+
+        if !rconf.no_headers {
+            let mut output_headers = headers.clone();
+
+            if let Some(name) = &args.flag_into {
+                output_headers.push_field(name.as_bytes());
+            }
+
+            writer.write_byte_record(&output_headers)?;
+        }
+
+        let mut record = simd_csv::ByteRecord::new();
+        let mut output_record = simd_csv::ByteRecord::new();
+        let mut changed: usize = 0;
+
+        while rdr.read_byte_record(&mut record)? {
+            let cell = &record[sel_index];
+            let replacement = match replacements.get(cell) {
+                Some(replacement) => {
+                    if replacement.as_slice() != cell {
+                        changed += 1;
+                    }
+
+                    replacement.as_slice()
+                }
+                None => cell,
+            };
+
+            output_record.clear();
+
+            if args.flag_into.is_some() {
+                output_record.extend(&record);
+                output_record.push_field(replacement);
+            } else {
+                for (i, field) in record.iter().enumerate() {
+                    output_record.push_field(if i == sel_index { replacement } else { field });
+                }
+            }
+
+            writer.write_byte_record(&output_record)?;
+        }
+
+        writer.flush()?;
+
+        eprintln!(
+            "{} cell{} changed",
+            changed,
+            if changed == 1 { "" } else { "s" }
+        );
+
+        return Ok(());
+    }
+
+    if args.flag_into.is_some() {
+        Err("--into only works with -A/--apply!")?;
+    }
+
     let key_expr = match &args.flag_key {
         Some(expr) => format!("col({}) | {}", sel_index, expr),
         None => format!("col({})", sel_index),
     };
 
     let program = Program::parse(&key_expr, headers, rconf.no_headers)?;
-    let mut clustering: Box<dyn ClusteringAlgorithm> = Box::<KeyCollision>::default();
+
+    let method = args.flag_method;
+
+    if args.flag_ngrams == 0 {
+        Err("--ngrams cannot be 0!")?;
+    }
+
+    let radius = match (method.default_radius(), args.flag_radius) {
+        (None, Some(_)) => Err("-r/--radius only works with nearest neighbour methods!")?,
+        (Some(default), radius_opt) => radius_opt.unwrap_or(default),
+        _ => 0.0,
+    };
+
+    let mut clustering: Box<dyn ClusteringAlgorithm> = match method {
+        Method::KeyCollision | Method::Fingerprint | Method::NgramFingerprint => {
+            Box::<KeyCollision>::default()
+        }
+        Method::Levenshtein => {
+            // NOTE: building Levenshtein automata is exponential in the radius
+            if radius.fract() != 0.0 || !(0.0..=3.0).contains(&radius) {
+                Err("-r/--radius must be an integer between 0 and 3 with the levenshtein method!")?;
+            }
+
+            Box::new(NearestNeighbours::new(
+                Metric::Levenshtein(radius as u8),
+                args.flag_block_size,
+            ))
+        }
+        Method::JaroWinkler => Box::new(NearestNeighbours::new(
+            Metric::JaroWinkler(radius),
+            args.flag_block_size,
+        )),
+        Method::Ppm => Box::new(NearestNeighbours::new(
+            Metric::Ppm(radius),
+            args.flag_block_size,
+        )),
+    };
+
+    let fingerprint_tokenizer = FingerprintTokenizer::default();
 
     let mut record = simd_csv::ByteRecord::new();
     let mut index: usize = 0;
 
     while rdr.read_byte_record(&mut record)? {
         let value = String::from_utf8(record[sel_index].to_vec()).unwrap();
-        let key = program.generate_key(index, &record)?;
+        let mut key = program.generate_key(index, &record)?;
+
+        match method {
+            Method::Fingerprint => {
+                key = fingerprint_tokenizer.key(&key);
+            }
+            Method::NgramFingerprint => {
+                key = ngram_fingerprint(&key, args.flag_ngrams);
+            }
+            _ => (),
+        };
 
         clustering.process(index, key, value);
 
@@ -91,6 +342,95 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     Ok(())
 }
 
+#[derive(Deserialize)]
+struct ClusteredValue {
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct ClusterDecision {
+    replace_with: String,
+    values: Vec<ClusteredValue>,
+    #[serde(default)]
+    harmonize: bool,
+}
+
+#[derive(Deserialize)]
+struct ClusterDecisions {
+    #[serde(default)]
+    cluster: Vec<ClusterDecision>,
+}
+
+fn read_cluster_decisions(path: &str) -> CliResult<Vec<ClusterDecision>> {
+    let conf = Config::new(&Some(path.to_string()));
+
+    if path.ends_with(".toml") {
+        let mut buffer = Vec::new();
+        conf.io_reader()?.read_to_end(&mut buffer)?;
+
+        let decisions: ClusterDecisions = toml::from_slice(&buffer)
+            .map_err(|err| CliError::Other(format!("could not parse {}: {}", path, err)))?;
+
+        return Ok(decisions.cluster);
+    }
+
+    let mut rdr = conf.simd_reader()?;
+    let headers = rdr.byte_headers()?;
+
+    let find_column = |name: &str| -> CliResult<usize> {
+        headers
+            .iter()
+            .position(|h| h == name.as_bytes())
+            .ok_or_else(|| CliError::Other(format!("{} has no \"{}\" column!", path, name)))
+    };
+
+    let harmonize_index = find_column("harmonize")?;
+    let replace_with_index = find_column("replace_with")?;
+    let values_index = find_column("values")?;
+
+    let mut decisions = Vec::new();
+    let mut record = simd_csv::ByteRecord::new();
+
+    while rdr.read_byte_record(&mut record)? {
+        let values: Vec<ClusteredValue> =
+            serde_json::from_slice(&record[values_index]).map_err(|_| {
+                CliError::Other(format!(
+                    "could not parse \"values\" cell {:?} as a JSON list of clustered values!",
+                    String::from_utf8_lossy(&record[values_index])
+                ))
+            })?;
+
+        decisions.push(ClusterDecision {
+            replace_with: String::from_utf8_lossy(&record[replace_with_index]).into_owned(),
+            values,
+            harmonize: record[harmonize_index]
+                .trim_ascii()
+                .eq_ignore_ascii_case(b"true"),
+        });
+    }
+
+    Ok(decisions)
+}
+
+fn read_replacements(path: &str) -> CliResult<HashMap<Vec<u8>, Vec<u8>>> {
+    let mut replacements = HashMap::new();
+
+    for decision in read_cluster_decisions(path)? {
+        if !decision.harmonize {
+            continue;
+        }
+
+        for clustered_value in decision.values {
+            replacements.insert(
+                clustered_value.value.into_bytes(),
+                decision.replace_with.clone().into_bytes(),
+            );
+        }
+    }
+
+    Ok(replacements)
+}
+
 #[derive(Debug)]
 struct Cluster {
     id: usize,
@@ -99,11 +439,15 @@ struct Cluster {
     values: Vec<(String, usize)>,
 }
 
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
 impl Cluster {
     fn write_toml<W: Write>(&self, mut writer: W) -> io::Result<()> {
         writeln!(&mut writer, "[[cluster]]")?;
         writeln!(&mut writer, "id = {}", self.id)?;
-        writeln!(&mut writer, "key = \"{}\"", self.key)?;
+        writeln!(&mut writer, "key = {}", toml_string(&self.key))?;
         writeln!(&mut writer, "nb_values = {}", self.values.len())?;
         writeln!(&mut writer, "nb_rows = {}", self.rows.len())?;
         writeln!(
@@ -115,14 +459,15 @@ impl Cluster {
                 .collect::<Vec<_>>()
                 .join(",")
         )?;
-        writeln!(&mut writer, "replace_with = {:?}", self.best())?;
+        writeln!(&mut writer, "replace_with = {}", toml_string(self.best()))?;
         writeln!(&mut writer, "values = [")?;
 
         for (value, count) in self.values.iter() {
             writeln!(
                 &mut writer,
-                "  {{ value = {:?}, count = {} }},",
-                value, count
+                "  {{ value = {}, count = {} }},",
+                toml_string(value),
+                count
             )?;
         }
 
@@ -220,3 +565,227 @@ impl ClusteringAlgorithm for KeyCollision {
             .collect()
     }
 }
+
+fn deflate_len(parts: &[&str]) -> usize {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+
+    for part in parts {
+        encoder.write_all(part.as_bytes()).unwrap();
+    }
+
+    encoder.finish().unwrap().len()
+}
+
+#[derive(Clone, Copy)]
+enum Metric {
+    Levenshtein(u8),
+    JaroWinkler(f64),
+    Ppm(f64),
+}
+
+struct UnionFind {
+    parents: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(n: usize) -> Self {
+        Self {
+            parents: (0..n).collect(),
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parents[i] != i {
+            self.parents[i] = self.parents[self.parents[i]];
+            i = self.parents[i];
+        }
+
+        i
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+
+        if a != b {
+            self.parents[a.max(b)] = a.min(b);
+        }
+    }
+}
+
+struct NearestNeighbours {
+    metric: Metric,
+    block_size: usize,
+    keys: IndexMap<String, Vec<(usize, String)>>,
+}
+
+impl NearestNeighbours {
+    fn new(metric: Metric, block_size: usize) -> Self {
+        Self {
+            metric,
+            block_size,
+            keys: new_index_map(),
+        }
+    }
+
+    // NOTE: jaro-winkler & ppm distances cannot be translated into a number
+    // of edits, so we approximate one proportional to the length of the value.
+    fn edit_radius(&self, len: usize) -> usize {
+        match self.metric {
+            Metric::Levenshtein(radius) => radius as usize,
+            Metric::JaroWinkler(radius) => (radius * len as f64).ceil() as usize,
+            Metric::Ppm(radius) => (radius / 10.0 * len as f64).ceil() as usize,
+        }
+    }
+
+    // NOTE: by the pigeonhole principle, two values within r edits of each
+    // other always share an n-gram of size q as long as q <= len / (r + 1),
+    // len being the length of the longest value.
+    fn ngram_size(&self, len: usize) -> usize {
+        (len / (self.edit_radius(len) + 1)).clamp(1, self.block_size)
+    }
+
+    fn blocks(&self) -> Vec<Vec<usize>> {
+        if self.block_size == 0 {
+            return vec![(0..self.keys.len()).collect()];
+        }
+
+        let mut blocks: HashMap<Vec<char>, Vec<usize>> = HashMap::new();
+
+        for (i, key) in self.keys.keys().enumerate() {
+            let chars = key.chars().collect::<Vec<_>>();
+            let len = chars.len();
+
+            // NOTE: a value must emit the n-grams of every size that could be
+            // chosen for a longer value within radius.
+            let mut sizes = (len..=(len + self.edit_radius(len)))
+                .map(|other_len| self.ngram_size(other_len).min(len.max(1)))
+                .collect::<Vec<_>>();
+
+            sizes.dedup();
+
+            let mut ngrams = sizes
+                .into_iter()
+                .flat_map(|size| chars.windows(size).map(|window| window.to_vec()))
+                .collect::<Vec<_>>();
+
+            ngrams.sort();
+            ngrams.dedup();
+
+            for ngram in ngrams {
+                blocks.entry(ngram).or_default().push(i);
+            }
+        }
+
+        let mut blocks = blocks
+            .into_values()
+            .filter(|block| block.len() > 1)
+            .collect::<Vec<_>>();
+
+        // NOTE: sorting for determinism
+        blocks.sort();
+
+        blocks
+    }
+}
+
+impl ClusteringAlgorithm for NearestNeighbours {
+    fn process(&mut self, index: usize, key: String, value: String) {
+        self.keys.entry(key).or_default().push((index, value));
+    }
+
+    fn into_clusters(self: Box<Self>) -> Vec<Cluster> {
+        let keys = self.keys.keys().map(|key| key.as_str()).collect::<Vec<_>>();
+        let mut union_find = UnionFind::new(keys.len());
+
+        let levenshtein_builder = match self.metric {
+            Metric::Levenshtein(radius) => Some(LevenshteinAutomatonBuilder::new(radius, false)),
+            _ => None,
+        };
+
+        let self_compressed_lens = match self.metric {
+            Metric::Ppm(_) => keys.iter().map(|key| deflate_len(&[key, key])).collect(),
+            _ => vec![],
+        };
+
+        let blocks = self.blocks();
+        let mut blocks_per_key: Vec<Vec<usize>> = vec![vec![]; keys.len()];
+
+        for (b, block) in blocks.iter().enumerate() {
+            for i in block.iter().copied() {
+                blocks_per_key[i].push(b);
+            }
+        }
+
+        // NOTE: a pair of values may share many blocks. So, instead of keeping
+        // track of every compared pair, we gather the candidates of each value
+        // across its blocks, only keeping the ones coming after it, and we mark
+        // them with the index of the current value to deduplicate them.
+        let mut last_seen_by: Vec<usize> = vec![usize::MAX; keys.len()];
+        let mut candidates: Vec<usize> = Vec::new();
+
+        for i in 0..keys.len() {
+            candidates.clear();
+
+            for b in blocks_per_key[i].iter().copied() {
+                for j in blocks[b].iter().copied() {
+                    if j > i && last_seen_by[j] != i {
+                        last_seen_by[j] = i;
+                        candidates.push(j);
+                    }
+                }
+            }
+
+            if candidates.is_empty() {
+                continue;
+            }
+
+            let dfa = levenshtein_builder
+                .as_ref()
+                .map(|builder| builder.build_dfa(keys[i]));
+
+            for j in candidates.iter().copied() {
+                let is_neighbour = match self.metric {
+                    Metric::Levenshtein(_) => {
+                        matches!(dfa.as_ref().unwrap().eval(keys[j]), Distance::Exact(_))
+                    }
+                    Metric::JaroWinkler(radius) => {
+                        1.0 - strsim::jaro_winkler(keys[i], keys[j]) <= radius
+                    }
+                    Metric::Ppm(radius) => {
+                        let joint =
+                            deflate_len(&[keys[i], keys[j]]) + deflate_len(&[keys[j], keys[i]]);
+                        let separate = self_compressed_lens[i] + self_compressed_lens[j];
+
+                        10.0 * (joint as f64 / separate as f64 - 1.0) <= radius
+                    }
+                };
+
+                if is_neighbour {
+                    union_find.union(i, j);
+                }
+            }
+        }
+
+        let mut components: IndexMap<usize, Vec<(usize, String)>> = new_index_map();
+
+        for (i, entries) in self.keys.into_values().enumerate() {
+            components
+                .entry(union_find.find(i))
+                .or_default()
+                .extend(entries);
+        }
+
+        components
+            .into_values()
+            .enumerate()
+            .map(|(id, mut entries)| {
+                entries.sort_by_key(|(row_index, _)| *row_index);
+
+                let mut cluster = Cluster::from_entries(id, String::new(), entries);
+                cluster.key = cluster.best().clone();
+                cluster
+            })
+            .filter(|cluster| cluster.values.len() > 1)
+            .collect()
+    }
+}
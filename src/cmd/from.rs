@@ -15,6 +15,7 @@ use simd_csv::ByteRecord;
 use crate::CliError;
 use crate::CliResult;
 use crate::config::Config;
+use crate::graph::{self, GraphTables};
 use crate::json::{GetPathOwned, JSONTabularizer};
 use crate::moonblade::Path as JSONPath;
 use crate::util::{self, ChunksIteratorExt};
@@ -32,6 +33,9 @@ enum SupportedFormat {
     Md,
     Parquet,
     Raw,
+    Gexf,
+    Graphml,
+    Graphology,
 }
 
 impl SupportedFormat {
@@ -47,10 +51,17 @@ impl SupportedFormat {
             "md" | "markdown" => Self::Md,
             "parquet" => Self::Parquet,
             "raw" => Self::Raw,
+            "gexf" => Self::Gexf,
+            "graphml" => Self::Graphml,
+            "graphology" => Self::Graphology,
             _ => return None,
         })
     }
 
+    fn is_graph(&self) -> bool {
+        matches!(self, Self::Gexf | Self::Graphml | Self::Graphology)
+    }
+
     fn infer_from_extension(path: &str) -> Option<Self> {
         let path = path.strip_suffix(".gz").unwrap_or(path);
 
@@ -89,6 +100,10 @@ Supported formats:
     - tar: tarball archive
     - md, markdown: Markdown table
     - raw: read whole input as a single CSV cell
+    - gexf: GEXF graph file (requires --nodes or --edges)
+    - graphml: GraphML graph file (requires --nodes or --edges)
+    - graphology: graphology JSON serialization (requires --nodes or --edges,
+      and cannot be inferred from file extension)
 
 Optionally supported formats (requires `xan` to be compiled using optional features):
     - parquet: Parquet frame (requires the `parquet` feature)
@@ -97,7 +112,8 @@ Some formats can be streamed, some others require the full file to be loaded int
 memory. The streamable formats are `ndjson`, `jsonl`, `parquet`, `tar`,`txt` and `npy`.
 
 Some formats will handle gzip decompression on the fly if the filename ends
-in `.gz`: `json`, `ndjson`, `jsonl`, `raw`, `tar` and `txt`.
+in `.gz`: `json`, `ndjson`, `jsonl`, `raw`, `tar`, `txt`, `gexf`, `graphml`
+and `graphology`.
 
 Tarball extraction was designed for utf8-encoded text files. Expect weird or
 broken results with other encodings or binary files.
@@ -147,6 +163,12 @@ Markdown options:
                            Negative index can be used to select from the end.
                            [default: 0]
 
+Graph options:
+    --nodes  Convert the nodes of the graph, with a \"node\" column containing
+             node keys, followed by the declared node attributes.
+    --edges  Convert the edges of the graph, with \"source\" & \"target\"
+             columns, followed by the declared edge attributes.
+
 Common options:
     -h, --help             Display this message
     -o, --output <file>    Write output to <file> instead of stdout.
@@ -169,6 +191,8 @@ struct Args {
     flag_root: Option<String>,
     flag_column: Option<String>,
     flag_nth_table: isize,
+    flag_nodes: bool,
+    flag_edges: bool,
 }
 
 impl Args {
@@ -712,6 +736,34 @@ impl Args {
 
         Ok(wtr.flush()?)
     }
+
+    fn convert_graph(&self, format: SupportedFormat) -> CliResult<()> {
+        let rdr = BufReader::new(Config::new(&self.arg_input).io_reader()?);
+
+        let GraphTables { nodes, edges } = match format {
+            SupportedFormat::Gexf => graph::read_gexf(rdr)?,
+            SupportedFormat::Graphml => graph::read_graphml(rdr)?,
+            SupportedFormat::Graphology => {
+                let value: Value =
+                    serde_json::from_reader(rdr).map_err(|err| CliError::Other(err.to_string()))?;
+
+                graph::read_graphology(&value)?
+            }
+            _ => unreachable!(),
+        };
+
+        let table = if self.flag_nodes { nodes } else { edges };
+
+        let mut wtr = self.writer()?;
+
+        wtr.write_record(&table.headers)?;
+
+        for row in table.rows {
+            wtr.write_record(&row)?;
+        }
+
+        Ok(wtr.flush()?)
+    }
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
@@ -736,6 +788,14 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         }
     };
 
+    if target_format.is_graph() {
+        if args.flag_nodes == args.flag_edges {
+            Err("either --nodes or --edges must be given when converting from a graph format!")?;
+        }
+    } else if args.flag_nodes || args.flag_edges {
+        Err("--nodes & --edges are only relevant with -f (gexf|graphml|graphology)!")?;
+    }
+
     match target_format {
         SupportedFormat::Xls => args.convert_xls(),
         SupportedFormat::NdJSON => args.convert_ndjson(),
@@ -747,5 +807,8 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         SupportedFormat::Md => args.convert_markdown(),
         SupportedFormat::Parquet => args.convert_parquet(),
         SupportedFormat::Raw => args.convert_raw(),
+        SupportedFormat::Gexf | SupportedFormat::Graphml | SupportedFormat::Graphology => {
+            args.convert_graph(target_format)
+        }
     }
 }
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::io::{Write, stdout};
 
 use colored::Colorize;
@@ -7,6 +8,7 @@ use textwrap::{fill, indent};
 
 use crate::CliResult;
 use crate::scales::GradientName;
+use crate::stopwords;
 use crate::util;
 
 fn wrap(string: &str) -> String {
@@ -760,6 +762,10 @@ https://github.com/medialab/xan/blob/master/docs/moonblade/window.md
 `xan help gradients` will print the reference of all gradients used in
 visualization commands such as `xan heatmap` or `xan spark`.
 
+`xan help stopwords` will list the languages of the builtin stoplists
+that can be used with `xan tokenize --stoplist builtin:<lang>` or the
+`is_stopword` function. Give a language to print its full stoplist.
+
 Use the -p/--pager flag to open desired documentation in a suitable
 pager.
 
@@ -773,6 +779,7 @@ Usage:
     xan help scraping [options]
     xan help window [options]
     xan help gradients [options]
+    xan help stopwords [options] [<lang>]
     xan help --help
 
 help options:
@@ -802,6 +809,8 @@ struct Args {
     cmd_scraping: bool,
     cmd_window: bool,
     cmd_gradients: bool,
+    cmd_stopwords: bool,
+    arg_lang: Option<String>,
     flag_open: bool,
     flag_pager: bool,
     flag_section: Option<String>,
@@ -816,6 +825,10 @@ impl Args {
             Err("-O/--open does not work with `xan help gradients`")?;
         }
 
+        if self.cmd_stopwords {
+            Err("-O/--open does not work with `xan help stopwords`")?;
+        }
+
         let url = format!(
             "https://github.com/medialab/xan/blob/master/docs/moonblade/{}.md",
             if self.cmd_cheatsheet {
@@ -968,6 +981,60 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         writeln!(&mut out)?;
 
         return Ok(());
+    } else if args.cmd_stopwords {
+        if args.flag_md {
+            Err("`xan help stopwords` does not work with --md!")?;
+        }
+
+        if let Some(lang) = &args.arg_lang {
+            let mut words = stopwords::builtin_stopwords(lang)?
+                .iter()
+                .copied()
+                .collect::<Vec<_>>();
+            words.sort_unstable();
+
+            if args.flag_json {
+                writeln!(&mut out, "{}", serde_json::to_string_pretty(&words)?)?;
+            } else {
+                for word in words {
+                    writeln!(&mut out, "{}", word)?;
+                }
+            }
+
+            return Ok(());
+        }
+
+        if args.flag_json {
+            let map = stopwords::LANGUAGES
+                .iter()
+                .map(|(code, _, _)| {
+                    let mut words = stopwords::builtin_stopwords(code)
+                        .unwrap()
+                        .iter()
+                        .copied()
+                        .collect::<Vec<_>>();
+                    words.sort_unstable();
+
+                    (*code, words)
+                })
+                .collect::<BTreeMap<_, _>>();
+
+            writeln!(&mut out, "{}", serde_json::to_string_pretty(&map)?)?;
+
+            return Ok(());
+        }
+
+        writeln!(&mut out, "{}", "Builtin stoplists".bold())?;
+        writeln!(&mut out)?;
+        for (code, name, _) in stopwords::LANGUAGES.iter() {
+            writeln!(
+                &mut out,
+                "{} ({}): {} words",
+                code.cyan(),
+                name,
+                stopwords::builtin_stopwords(code).unwrap().len()
+            )?;
+        }
     }
 
     Ok(())
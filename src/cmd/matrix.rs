@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use simd_csv::ByteRecord;
 
 use crate::CliResult;
@@ -5,8 +7,156 @@ use crate::collections::{HashMap, IndexSet, new_index_set};
 use crate::config::{Config, Delimiter};
 use crate::moonblade::agg::CovarianceWelford;
 use crate::select::SelectedColumns;
+use crate::sparse::SparseMatrix;
+use crate::statistics::{kendall_correlation, spearman_correlation};
 use crate::util;
 
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(try_from = "String")]
+enum CorrelationMethod {
+    Pearson,
+    Spearman,
+    Kendall,
+}
+
+type RankCorrelation = fn(&[f64], &[f64]) -> Option<f64>;
+
+impl CorrelationMethod {
+    // NOTE: pearson is computed in a streaming fashion, whereas rank-based
+    // correlations need to buffer the columns.
+    fn rank_correlation(&self) -> Option<RankCorrelation> {
+        match self {
+            Self::Pearson => None,
+            Self::Spearman => Some(spearman_correlation),
+            Self::Kendall => Some(kendall_correlation),
+        }
+    }
+}
+
+impl TryFrom<String> for CorrelationMethod {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(match value.as_str() {
+            "pearson" => Self::Pearson,
+            "spearman" => Self::Spearman,
+            "kendall" => Self::Kendall,
+            _ => {
+                return Err(format!("unsupported correlation --method \"{}\"", &value));
+            }
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(try_from = "String")]
+enum MatrixFormat {
+    Csv,
+    Mtx,
+    Npz,
+}
+
+impl MatrixFormat {
+    fn is_sparse(&self) -> bool {
+        !matches!(self, Self::Csv)
+    }
+}
+
+impl TryFrom<String> for MatrixFormat {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(match value.as_str() {
+            "csv" => Self::Csv,
+            "mtx" => Self::Mtx,
+            "npz" => Self::Npz,
+            _ => return Err(format!("unsupported --format \"{}\"", &value)),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(try_from = "String")]
+enum Normalization {
+    Row,
+    Pmi,
+    Cosine,
+}
+
+impl Normalization {
+    // NOTE: relies on the row & column sums of the whole matrix
+    fn apply(&self, matrix: &mut SparseMatrix) {
+        let row_sums = matrix.row_sums();
+        let col_sums = matrix.col_sums();
+
+        match self {
+            Self::Row => matrix.map_values(|i, _, value| value / row_sums[i]),
+            Self::Pmi => {
+                let total = row_sums.iter().sum::<f64>();
+
+                matrix
+                    .map_values(|i, j, value| (value * total / (row_sums[i] * col_sums[j])).log2());
+            }
+            Self::Cosine => {
+                matrix.map_values(|i, j, value| value / (row_sums[i] * col_sums[j]).sqrt())
+            }
+        }
+    }
+}
+
+impl TryFrom<String> for Normalization {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(match value.as_str() {
+            "row" => Self::Row,
+            "pmi" => Self::Pmi,
+            "cosine" => Self::Cosine,
+            _ => return Err(format!("unsupported --normalize \"{}\"", &value)),
+        })
+    }
+}
+
+// NOTE: row & column labels are written in sidecar CSV files, e.g.
+// "matrix.rows.csv" & "matrix.cols.csv" for "matrix.mtx".
+fn write_sparse_matrix<'a>(
+    matrix: SparseMatrix,
+    format: MatrixFormat,
+    output: &str,
+    row_labels: (&[u8], impl Iterator<Item = &'a [u8]>),
+    col_labels: (&[u8], impl Iterator<Item = &'a [u8]>),
+) -> CliResult<()> {
+    fn write_labels<'a>(
+        path: &Path,
+        extension: &str,
+        (header, labels): (&[u8], impl Iterator<Item = &'a [u8]>),
+    ) -> CliResult<()> {
+        let mut writer = Config::new(&Some(
+            path.with_extension(extension).to_string_lossy().to_string(),
+        ))
+        .simd_writer()?;
+
+        writer.write_record([header])?;
+
+        for label in labels {
+            writer.write_record([label])?;
+        }
+
+        Ok(writer.flush()?)
+    }
+
+    let output_path = Path::new(output);
+
+    write_labels(output_path, "rows.csv", row_labels)?;
+    write_labels(output_path, "cols.csv", col_labels)?;
+
+    match format {
+        MatrixFormat::Mtx => matrix.write_matrix_market(output_path),
+        MatrixFormat::Npz => matrix.write_npz(output_path),
+        MatrixFormat::Csv => unreachable!(),
+    }
+}
+
 enum Axes {
     Homogeneous(IndexSet<Vec<u8>>),
     Heterogeneous {
@@ -55,6 +205,20 @@ impl Axes {
         }
     }
 
+    fn get_x_label(&self, index: usize) -> &Vec<u8> {
+        match self {
+            Self::Homogeneous(labels) => labels.get_index(index).unwrap(),
+            Self::Heterogeneous { x, .. } => x.get_index(index).unwrap(),
+        }
+    }
+
+    fn y_labels(&self) -> impl Iterator<Item = &Vec<u8>> {
+        match self {
+            Self::Homogeneous(labels) => labels.iter(),
+            Self::Heterogeneous { y, .. } => y.iter(),
+        }
+    }
+
     fn get_y_label(&self, index: usize) -> &Vec<u8> {
         match self {
             Self::Homogeneous(labels) => labels.get_index(index).unwrap(),
@@ -72,13 +236,19 @@ Supported modes:
     count - convert a pair of columns into a full count matrix (a bipartite
             adjacency matrix, or co-occurrence matrix, if you will).
     corr  - convert a selection of columns into a full
-            correlation matrix.
+            correlation matrix. Pearson, Spearman & Kendall (tau-b)
+            correlations are supported.
 
 Note that the difference between the `adj` and `count` mode is that `count`
 considers its `x` & `y` labels as two separate sets while `adj` considers `source`
 and `target` labels as parts of the same set. This also means `adj` produces a
 square matrix while `count` produces a rectangular one.
 
+Note that the `corr` mode ignores empty cells, using pairwise-complete
+observations for each pair of columns. Use the -L/--long flag to output
+the number of such observations along with the coefficients in long format,
+with one row per pair of columns.
+
 Usage:
     xan matrix adj [options] <source> <target> [<input>]
     xan matrix count [options] <x> <y> [<input>]
@@ -86,7 +256,19 @@ Usage:
     xan matrix --help
 
 matrix adj/count options:
-    -w, --weight <column>  Optional column containing a weight for edges.
+    -w, --weight <column>     Optional column containing a weight for edges.
+    --sparse                  Output a long format with x, y & value columns,
+                              listing only the non-empty cells of the matrix,
+                              instead of a dense matrix.
+    -N, --normalize <method>  Normalize the values of the matrix. Must be one of:
+                                - \"row\": divide values by the sum of their row,
+                                  so that each row sums to 1.
+                                - \"pmi\": pointwise mutual information of the
+                                  row & column, computed using the sums of
+                                  rows & columns.
+                                - \"cosine\": divide values by the square root
+                                  of the product of the sums of their row
+                                  & column.
 
 matrix adj options:
     -U, --undirected  Indicates that edges are undirected and that produced
@@ -96,13 +278,30 @@ matrix corr options:
     -s, --select <columns>  Columns to consider for the correlation
                             matrix.
     -D, --fill-diagonal     Whether to fill diagonal with ones.
+    -m, --method <method>   Correlation method to use. Must be one of
+                            \"pearson\", \"spearman\" or \"kendall\". Note that
+                            \"spearman\" & \"kendall\" need to store the whole
+                            selection in memory.
+                            [default: pearson]
+    -L, --long              Output a long format with x, y, coefficient & n
+                            columns instead of a dense matrix.
+
+Output format options:
+    --format <format>  Output format. Either \"csv\", \"mtx\" to write a sparse
+                       matrix in the Matrix Market format, or \"npz\" to write
+                       it as a scipy CSR matrix (as read by scipy.sparse.load_npz).
+                       Sparse formats require -o, --output and will also write
+                       two sidecar CSV files next to the output file, e.g.
+                       \"m.rows.csv\" & \"m.cols.csv\" for \"m.mtx\", containing
+                       the row & column labels of the matrix respectively.
+                       [default: csv]
 
 Common options:
     -h, --help             Display this message
     -o, --output <file>    Write output to <file> instead of stdout.
     -n, --no-headers       When set, the file will be considered as having no
                            headers.
-    -d, --delimiter <arg>  The field delimiter foDirectedr reading CSV data.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
                            Must be a single character.
 ";
 
@@ -117,9 +316,14 @@ struct Args {
     arg_source: Option<SelectedColumns>,
     arg_target: Option<SelectedColumns>,
     flag_weight: Option<SelectedColumns>,
+    flag_sparse: bool,
+    flag_normalize: Option<Normalization>,
+    flag_format: MatrixFormat,
     flag_select: SelectedColumns,
     flag_undirected: bool,
     flag_fill_diagonal: bool,
+    flag_method: CorrelationMethod,
+    flag_long: bool,
     flag_no_headers: bool,
     flag_delimiter: Option<Delimiter>,
     flag_output: Option<String>,
@@ -137,18 +341,18 @@ impl Args {
             .select(self.flag_select.clone());
 
         let mut reader = rconf.simd_reader()?;
-        let headers = reader.byte_headers()?;
+        let headers = reader.byte_headers()?.clone();
 
         let arg_source = self.arg_source.as_ref().or(self.arg_x.as_ref()).unwrap();
         let arg_target = self.arg_target.as_ref().or(self.arg_y.as_ref()).unwrap();
 
-        let source_column_index = arg_source.single_selection(headers, !rconf.no_headers)?;
-        let target_column_index = arg_target.single_selection(headers, !rconf.no_headers)?;
+        let source_column_index = arg_source.single_selection(&headers, !rconf.no_headers)?;
+        let target_column_index = arg_target.single_selection(&headers, !rconf.no_headers)?;
 
         let weight_column_index = self
             .flag_weight
             .as_ref()
-            .map(|weight_col| weight_col.single_selection(headers, !rconf.no_headers))
+            .map(|weight_col| weight_col.single_selection(&headers, !rconf.no_headers))
             .transpose()?;
 
         let mut axes = Axes::new(self.cmd_count);
@@ -189,8 +393,62 @@ impl Args {
 
         let (cols, rows) = axes.shape();
 
+        // NOTE: matrix rows are y labels while matrix columns are x labels
+        let mut matrix = SparseMatrix::new(rows, cols);
+
+        for ((x, y), val) in hash_matrix {
+            matrix.push(y, x, val);
+
+            // NOTE: no need to mirror the diagonal
+            if self.flag_undirected && x != y {
+                matrix.push(x, y, val);
+            }
+        }
+
+        matrix.sort();
+
+        if let Some(normalization) = self.flag_normalize {
+            normalization.apply(&mut matrix);
+        }
+
+        if self.flag_format.is_sparse() {
+            return write_sparse_matrix(
+                matrix,
+                self.flag_format,
+                self.flag_output.as_ref().unwrap(),
+                (
+                    &headers[target_column_index],
+                    axes.y_labels().map(|label| label.as_slice()),
+                ),
+                (
+                    &headers[source_column_index],
+                    axes.x_labels().map(|label| label.as_slice()),
+                ),
+            );
+        }
+
         let mut writer = Config::new(&self.flag_output).simd_writer()?;
         let mut output_record = ByteRecord::new();
+
+        if self.flag_sparse {
+            writer.write_record([b"x".as_slice(), b"y", b"value"])?;
+
+            for (index, row) in matrix.rows().iter().enumerate() {
+                let row_label = axes.get_y_label(index);
+
+                for (x, v) in row.iter() {
+                    output_record.clear();
+                    output_record.push_field(axes.get_x_label(*x));
+                    output_record.push_field(row_label);
+                    output_record.push_field(v.to_string().as_bytes());
+
+                    writer.write_byte_record(&output_record)?;
+                }
+            }
+
+            return Ok(writer.flush()?);
+        }
+
         output_record.push_field(b"");
 
         for value in axes.x_labels() {
@@ -200,25 +458,20 @@ impl Args {
 
         writer.write_byte_record(&output_record)?;
 
-        let mut flat_matrix: Vec<Option<f64>> = vec![None; cols * rows];
+        let mut dense_row: Vec<Option<f64>> = vec![None; cols];
 
-        for ((x, y), val) in hash_matrix.iter() {
-            let index = y * cols + x;
-            flat_matrix[index] = Some(*val);
+        for (index, row) in matrix.rows().iter().enumerate() {
+            dense_row.fill(None);
 
-            if self.flag_undirected {
-                // NOTE: no need to have a special case for diagonal.
-                let other_index = x * cols + y;
-                flat_matrix[other_index] = Some(*val);
+            for (x, v) in row.iter() {
+                dense_row[*x] = Some(*v);
             }
-        }
 
-        for (index, row) in flat_matrix.chunks_exact(cols).enumerate() {
             let row_label = axes.get_y_label(index);
             output_record.clear();
             output_record.push_field(row_label);
 
-            for v_opt in row {
+            for v_opt in dense_row.iter() {
                 match v_opt {
                     Some(v) => output_record.push_field(v.to_string().as_bytes()),
                     None => output_record.push_field(b""),
@@ -228,7 +481,7 @@ impl Args {
             writer.write_byte_record(&output_record)?;
         }
 
-        Ok(())
+        Ok(writer.flush()?)
     }
 
     fn correlation(self) -> CliResult<()> {
@@ -246,30 +499,23 @@ impl Args {
             Err("less that 2 columns in selection!")?;
         }
 
-        let mut writer = Config::new(&self.flag_output).simd_writer()?;
-        let mut output_headers = ByteRecord::new();
-        output_headers.push_field(b"");
-        output_headers.extend(sel.select(&headers));
-
-        writer.write_byte_record(&output_headers)?;
+        let rank_correlation = self.flag_method.rank_correlation();
+        let names = sel.select(&headers).collect::<Vec<_>>();
 
         let n = sel.len();
         let m = (n * (n - 1)) / 2;
 
-        for i in 0..n {
-            let mut row: Vec<Option<CovarianceWelford>> = Vec::with_capacity(n);
+        let mut welfords: Vec<CovarianceWelford> = Vec::new();
+        let mut columns: Vec<Vec<Option<f64>>> = Vec::new();
 
-            for j in 0..n {
-                if i == j {
-                    row.push(None);
-                }
+        if rank_correlation.is_none() {
+            for _ in 0..m {
+                welfords.push(CovarianceWelford::new());
+            }
+        } else {
+            for _ in 0..n {
+                columns.push(Vec::new());
             }
-        }
-
-        let mut welfords: Vec<CovarianceWelford> = Vec::with_capacity(m);
-
-        for _ in 0..m {
-            welfords.push(CovarianceWelford::new());
         }
 
         let mut record = ByteRecord::new();
@@ -279,30 +525,137 @@ impl Args {
             let values = sel
                 .select(&record)
                 .map(|cell| {
-                    fast_float::parse::<f64, &[u8]>(cell).map_err(|_| {
-                        format!(
-                            "could not parse cell \"{}\" as a float!",
-                            std::str::from_utf8(cell).unwrap()
-                        )
-                    })
+                    if cell.is_empty() {
+                        return Ok(None);
+                    }
+
+                    fast_float::parse::<f64, &[u8]>(cell)
+                        .map(Some)
+                        .map_err(|_| {
+                            format!(
+                                "could not parse cell \"{}\" as a float!",
+                                std::str::from_utf8(cell).unwrap()
+                            )
+                        })
                 })
                 .collect::<Result<Vec<_>, _>>()?;
 
+            if rank_correlation.is_some() {
+                for (column, value) in columns.iter_mut().zip(values) {
+                    column.push(value);
+                }
+
+                continue;
+            }
+
             k = 0;
 
             for i in 0..n {
                 for j in (i + 1)..n {
-                    if i == j {
-                        continue;
+                    // NOTE: using pairwise-complete observations
+                    if let (Some(x), Some(y)) = (values[i], values[j]) {
+                        welfords[k].add(x, y);
                     }
 
-                    welfords[k].add(values[i], values[j]);
+                    k += 1;
+                }
+            }
+        }
+
+        // (coefficient, n) for each pair of columns
+        let mut correlations: Vec<(Option<f64>, usize)> = Vec::with_capacity(m);
+
+        if let Some(compute) = rank_correlation {
+            let mut xs: Vec<f64> = Vec::new();
+            let mut ys: Vec<f64> = Vec::new();
+
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    xs.clear();
+                    ys.clear();
+
+                    for (x, y) in columns[i].iter().zip(columns[j].iter()) {
+                        if let (Some(x), Some(y)) = (x, y) {
+                            xs.push(*x);
+                            ys.push(*y);
+                        }
+                    }
+
+                    correlations.push((compute(&xs, &ys), xs.len()));
+                }
+            }
+        } else {
+            for welford in welfords {
+                correlations.push((welford.correlation(), welford.count()));
+            }
+        }
+
+        if self.flag_format.is_sparse() {
+            let mut matrix = SparseMatrix::new(n, n);
+
+            k = 0;
+
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    if let Some(correlation) = correlations[k].0 {
+                        matrix.push(i, j, correlation);
+                        matrix.push(j, i, correlation);
+                    }
+
+                    k += 1;
+                }
+
+                if self.flag_fill_diagonal {
+                    matrix.push(i, i, 1.0);
+                }
+            }
+
+            return write_sparse_matrix(
+                matrix,
+                self.flag_format,
+                self.flag_output.as_ref().unwrap(),
+                (b"column", names.iter().copied()),
+                (b"column", names.iter().copied()),
+            );
+        }
+
+        let mut writer = Config::new(&self.flag_output).simd_writer()?;
+
+        if self.flag_long {
+            writer.write_record([b"x", b"y", b"coefficient".as_slice(), b"n"])?;
+
+            k = 0;
+
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    let (correlation, count) = correlations[k];
+
+                    record.clear();
+                    record.push_field(names[i]);
+                    record.push_field(names[j]);
+                    record.push_field(
+                        correlation
+                            .map(|f| f.to_string())
+                            .unwrap_or_default()
+                            .as_bytes(),
+                    );
+                    record.push_field(count.to_string().as_bytes());
+
+                    writer.write_byte_record(&record)?;
 
                     k += 1;
                 }
             }
+
+            return Ok(writer.flush()?);
         }
 
+        let mut output_headers = ByteRecord::new();
+        output_headers.push_field(b"");
+        output_headers.extend(names.iter().copied());
+
+        writer.write_byte_record(&output_headers)?;
+
         let mut correlation_matrix: Vec<Vec<Option<f64>>> = Vec::new();
 
         for _ in 0..n {
@@ -313,7 +666,7 @@ impl Args {
 
         for i in 0..n {
             for j in (i + 1)..n {
-                let correlation = welfords[k].correlation();
+                let correlation = correlations[k].0;
 
                 correlation_matrix[i][j] = correlation;
                 correlation_matrix[j][i] = correlation;
@@ -322,16 +675,14 @@ impl Args {
             }
         }
 
-        for (row, name) in correlation_matrix
-            .into_iter()
-            .zip(output_headers.iter().skip(1))
-        {
+        for (i, (row, name)) in correlation_matrix.into_iter().zip(names).enumerate() {
             record.clear();
             record.push_field(name);
 
-            for cell in row {
+            for (j, cell) in row.into_iter().enumerate() {
                 match cell {
-                    None => record.push_field(if self.flag_fill_diagonal { b"1.0" } else { b"" }),
+                    None if i == j && self.flag_fill_diagonal => record.push_field(b"1.0"),
+                    None => record.push_field(b""),
                     Some(f) => record.push_field(f.to_string().as_bytes()),
                 }
             }
@@ -339,13 +690,27 @@ impl Args {
             writer.write_byte_record(&record)?;
         }
 
-        Ok(())
+        Ok(writer.flush()?)
     }
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let args: Args = util::get_args(USAGE, argv)?;
 
+    if args.cmd_corr && (args.flag_sparse || args.flag_normalize.is_some()) {
+        Err("--sparse & -N/--normalize are only relevant with `adj` & `count` modes!")?;
+    }
+
+    if args.flag_format.is_sparse() {
+        if args.flag_output.is_none() {
+            Err("cannot export a sparse matrix without a path.\nUse -o, --output!")?;
+        }
+
+        if args.flag_sparse || args.flag_long {
+            Err("--sparse & -L/--long are only relevant with --format csv!")?;
+        }
+    }
+
     if args.cmd_adj || args.cmd_count {
         args.adj_or_count()
     } else if args.cmd_corr {
@@ -33,13 +33,16 @@ pub mod join;
 pub mod map;
 pub mod matrix;
 pub mod merge;
+pub mod neardup;
 pub mod network;
+pub mod outliers;
 pub mod parallel;
 pub mod partition;
 pub mod pivot;
 pub mod plot;
 pub mod progress;
 pub mod range;
+pub mod regress;
 pub mod rename;
 pub mod reverse;
 pub mod run;
@@ -55,6 +58,7 @@ pub mod spark;
 pub mod split;
 pub mod stats;
 pub mod tail;
+pub mod test;
 pub mod to;
 pub mod tokenize;
 pub mod top;
@@ -0,0 +1,445 @@
+use paltoquet::tokenizers::{WordTokenKind, WordTokenizer, WordTokenizerBuilder};
+use pariter::IteratorExt;
+use rand::RngExt;
+use simd_csv::ByteRecord;
+
+use crate::CliResult;
+use crate::collections::HashMap;
+use crate::config::{Config, Delimiter};
+use crate::select::SelectedColumns;
+use crate::stopwords::Stoplist;
+use crate::util;
+
+// NOTE: splitmix64 finalizer, used to derive one hash function per permutation
+#[inline]
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+#[derive(Clone)]
+struct MinHasher {
+    tokenizer: WordTokenizer,
+    hasher: ahash::RandomState,
+    seeds: Vec<u64>,
+    shingle_size: usize,
+    lower: bool,
+    unidecode: bool,
+}
+
+impl MinHasher {
+    // NOTE: returns None for documents without any shingle, e.g. empty ones
+    // or ones only containing stopwords, since their signature would be
+    // identical.
+    fn signature(&self, text: &str) -> Option<Vec<u32>> {
+        let tokens = self
+            .tokenizer
+            .tokenize(text)
+            .map(|token| {
+                let mut text = token.to_pair().0;
+
+                if self.lower {
+                    text = text.to_lowercase();
+                }
+
+                if self.unidecode {
+                    text = unidecode::unidecode(&text);
+                }
+
+                text
+            })
+            .collect::<Vec<_>>();
+
+        if tokens.is_empty() {
+            return None;
+        }
+
+        let mut signature = vec![u32::MAX; self.seeds.len()];
+
+        let mut add_shingle = |shingle: &[String]| {
+            let hash = self.hasher.hash_one(shingle);
+
+            for (min, seed) in signature.iter_mut().zip(self.seeds.iter()) {
+                let permuted = (mix(hash ^ seed) >> 32) as u32;
+
+                if permuted < *min {
+                    *min = permuted;
+                }
+            }
+        };
+
+        if tokens.len() < self.shingle_size {
+            add_shingle(&tokens);
+        } else {
+            for shingle in tokens.windows(self.shingle_size) {
+                add_shingle(shingle);
+            }
+        }
+
+        Some(signature)
+    }
+}
+
+fn estimated_jaccard(a: &[u32], b: &[u32]) -> f64 {
+    let equal = a.iter().zip(b.iter()).filter(|(x, y)| x == y).count();
+
+    equal as f64 / a.len() as f64
+}
+
+struct LocalitySensitiveIndex {
+    bands: usize,
+    rows: usize,
+    threshold: f64,
+    signatures: Vec<u32>,
+    buckets: HashMap<u64, Vec<usize>>,
+}
+
+impl LocalitySensitiveIndex {
+    fn new(permutations: usize, bands: usize, threshold: f64) -> Self {
+        Self {
+            bands,
+            rows: permutations / bands,
+            threshold,
+            signatures: Vec::new(),
+            buckets: HashMap::new(),
+        }
+    }
+
+    fn signature(&self, cluster: usize) -> &[u32] {
+        let size = self.bands * self.rows;
+
+        &self.signatures[cluster * size..(cluster + 1) * size]
+    }
+
+    fn band_keys<'a>(&'a self, signature: &'a [u32]) -> impl Iterator<Item = u64> + 'a {
+        signature.chunks(self.rows).enumerate().map(|(band, rows)| {
+            rows.iter()
+                .fold(mix(band as u64), |acc, row| mix(acc ^ *row as u64))
+        })
+    }
+
+    // NOTE: returns the id of the cluster the signature belongs to, along
+    // with whether it is a new one. Only the first member of each cluster is
+    // indexed, which means memory is proportional to the number of clusters.
+    fn insert(&mut self, signature: &[u32]) -> (usize, bool) {
+        let mut best: Option<(usize, f64)> = None;
+
+        for key in self.band_keys(signature) {
+            if let Some(candidates) = self.buckets.get(&key) {
+                for candidate in candidates.iter().copied() {
+                    let similarity = estimated_jaccard(signature, self.signature(candidate));
+
+                    if similarity < self.threshold {
+                        continue;
+                    }
+
+                    match best {
+                        Some((best_candidate, best_similarity))
+                            if similarity < best_similarity
+                                || (similarity == best_similarity
+                                    && candidate > best_candidate) => {}
+                        _ => {
+                            best = Some((candidate, similarity));
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some((cluster, _)) = best {
+            return (cluster, false);
+        }
+
+        let cluster = self.signatures.len() / (self.bands * self.rows);
+
+        let keys = self.band_keys(signature).collect::<Vec<_>>();
+
+        for key in keys {
+            self.buckets.entry(key).or_default().push(cluster);
+        }
+
+        self.signatures.extend_from_slice(signature);
+
+        (cluster, true)
+    }
+}
+
+static USAGE: &str = "
+Find near-duplicate documents in a text column of a CSV file, using MinHash
+signatures over shingles of tokens, bucketed using Locality-Sensitive Hashing
+(LSH) bands, in order to avoid comparing all pairs of documents.
+
+Text is tokenized into words using the same tokenizer as `xan tokenize words`.
+Shingles are then built from sequences of consecutive tokens, whose size
+can be set using -w/--shingle-size. Documents having fewer tokens than the shingle size are
+represented by a single shingle containing all their tokens. Documents without
+any token, e.g. empty ones or ones only containing stopwords, are never
+considered near-duplicates and their cluster id is left empty.
+
+A document is considered a near-duplicate of a previously seen one when they
+share at least one LSH band and when the Jaccard similarity of their shingle
+sets, estimated from their MinHash signatures, is greater or equal to the
+value given to -T/--threshold. Note that the probability that two documents share a band
+depends on the number of bands b and of rows r per band (i.e. -P/--permutations
+divided by -b/--bands) and is approximately 1 - (1 - s^r)^b, s being their
+actual Jaccard similarity. So make sure to tweak those parameters such that
+this probability is high enough around the desired threshold.
+
+By default, the command will only write the first row of each group of
+near-duplicates, in a streaming fashion. Only the signatures of those first
+rows are kept in memory, which makes the command able to process very large
+files. You can also choose to keep all rows and add a column containing a
+cluster id using -C/--cluster, or a column indicating whether the row is a
+near-duplicate of a previous one using -f/--flag:
+
+    $ xan neardup -C cluster text articles.csv | xan groupby cluster 'count()'
+
+Usage:
+    xan neardup [options] <column> [<input>]
+    xan neardup --help
+
+neardup options:
+    -w, --shingle-size <n>    Number of consecutive tokens in a shingle.
+                              [default: 3]
+    -P, --permutations <n>    Number of hash functions used to compute MinHash
+                              signatures. [default: 128]
+    -b, --bands <n>           Number of LSH bands. Must divide -P/--permutations.
+                              [default: 32]
+    -T, --threshold <t>       Minimum estimated Jaccard similarity for two documents
+                              to be considered near-duplicates. [default: 0.8]
+    -C, --cluster <name>      Instead of filtering near-duplicates, add a column
+                              with given name containing the id of their cluster.
+    -f, --flag <name>         Instead of filtering near-duplicates, add a column
+                              with given name indicating whether a row is a
+                              near-duplicate of a previous one.
+    --seed <number>           Seed used to generate the hash functions.
+                              [default: 0]
+    -p, --parallel            Whether to use parallelization to speed up computations.
+                              Will automatically select a suitable number of threads to use
+                              based on your number of cores. Use -t, --threads if you want to
+                              indicate the number of threads yourself.
+    -t, --threads <threads>   Parellize computations using this many threads. Use -p, --parallel
+                              if you want the number of threads to be automatically chosen instead.
+
+neardup tokenization options:
+    -D, --drop <types>       Types of tokens to drop, separated by comma, e.g.
+                             \"punct,url\". See `xan tokenize --help` for a list of
+                             recognized types. Cannot work with -k, --keep.
+    -k, --keep <types>       Types of tokens to keep, separated by comma,
+                             e.g. \"word,number\". Cannot work with -D, --drop.
+    --stoplist <spec>        Stoplist of words to drop. Can be a path to a .txt file containing
+                             one word per line, or \"builtin:\" followed by comma-separated
+                             language codes, e.g. \"builtin:fr,en\". Several stoplists can be
+                             combined using \"+\", e.g. \"builtin:fr+custom.txt\".
+                             Run \"xan help stopwords\" to list available builtin languages.
+    -L, --lower              Whether to normalize token case using lower case.
+    -U, --unidecode          Whether to normalize token text to ascii.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will not be interpreted
+                           as headers.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character.
+";
+
+#[derive(Deserialize)]
+struct Args {
+    arg_column: SelectedColumns,
+    arg_input: Option<String>,
+    flag_shingle_size: usize,
+    flag_permutations: usize,
+    flag_bands: usize,
+    flag_threshold: f64,
+    flag_cluster: Option<String>,
+    flag_flag: Option<String>,
+    flag_seed: usize,
+    flag_parallel: bool,
+    flag_threads: Option<usize>,
+    flag_drop: Option<String>,
+    flag_keep: Option<String>,
+    flag_stoplist: Option<String>,
+    flag_lower: bool,
+    flag_unidecode: bool,
+    flag_output: Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter: Option<Delimiter>,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    if args.flag_shingle_size == 0 {
+        Err("-w/--shingle-size cannot be 0!")?;
+    }
+
+    if args.flag_bands == 0 || args.flag_permutations % args.flag_bands != 0 {
+        Err("-b/--bands must divide -P/--permutations!")?;
+    }
+
+    if !(0.0..=1.0).contains(&args.flag_threshold) {
+        Err("-T/--threshold must be between 0 and 1!")?;
+    }
+
+    if args.flag_drop.is_some() && args.flag_keep.is_some() {
+        Err("-D/--drop cannot work with -k/--keep!")?;
+    }
+
+    let rconf = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers)
+        .select(args.arg_column);
+
+    let mut rdr = rconf.simd_reader()?;
+    let mut wtr = Config::new(&args.flag_output).simd_writer()?;
+
+    let mut headers = rdr.byte_headers()?.clone();
+    let col_index = rconf.single_selection(&headers)?;
+
+    if !rconf.no_headers {
+        if let Some(name) = &args.flag_cluster {
+            headers.push_field(name.as_bytes());
+        }
+
+        if let Some(name) = &args.flag_flag {
+            headers.push_field(name.as_bytes());
+        }
+
+        wtr.write_byte_record(&headers)?;
+    }
+
+    let mut tokenizer_builder = WordTokenizerBuilder::new();
+
+    if let Some(kinds) = &args.flag_drop {
+        tokenizer_builder = tokenizer_builder.token_kind_blacklist(
+            kinds
+                .split(',')
+                .map(|name| name.trim_end_matches('s').parse())
+                .collect::<Result<Vec<WordTokenKind>, _>>()?,
+        );
+    } else if let Some(kinds) = &args.flag_keep {
+        tokenizer_builder = tokenizer_builder.token_kind_whitelist(
+            kinds
+                .split(',')
+                .map(|name| name.trim_end_matches('s').parse())
+                .collect::<Result<Vec<WordTokenKind>, _>>()?,
+        );
+    }
+
+    if let Some(spec) = &args.flag_stoplist {
+        for word in Stoplist::from_spec(spec)?.iter() {
+            tokenizer_builder.insert_stopword(word);
+        }
+    }
+
+    let mut rng = util::acquire_rng(Some(args.flag_seed));
+
+    let minhasher = MinHasher {
+        tokenizer: tokenizer_builder.build(),
+        hasher: ahash::RandomState::with_seeds(
+            rng.random(),
+            rng.random(),
+            rng.random(),
+            rng.random(),
+        ),
+        seeds: (0..args.flag_permutations).map(|_| rng.random()).collect(),
+        shingle_size: args.flag_shingle_size,
+        lower: args.flag_lower,
+        unidecode: args.flag_unidecode,
+    };
+
+    let mut index =
+        LocalitySensitiveIndex::new(args.flag_permutations, args.flag_bands, args.flag_threshold);
+
+    let emit_all = args.flag_cluster.is_some() || args.flag_flag.is_some();
+
+    let mut process = |record: &mut ByteRecord, signature: Option<Vec<u32>>| -> CliResult<()> {
+        let (cluster, is_new) = match signature {
+            Some(signature) => {
+                let (cluster, is_new) = index.insert(&signature);
+                (Some(cluster), is_new)
+            }
+            None => (None, true),
+        };
+
+        if !emit_all {
+            if is_new {
+                wtr.write_byte_record(record)?;
+            }
+
+            return Ok(());
+        }
+
+        if args.flag_cluster.is_some() {
+            match cluster {
+                Some(cluster) => record.push_field(cluster.to_string().as_bytes()),
+                None => record.push_field(b""),
+            }
+        }
+
+        if args.flag_flag.is_some() {
+            record.push_field(if is_new { b"false" } else { b"true" });
+        }
+
+        wtr.write_byte_record(record)?;
+
+        Ok(())
+    };
+
+    if let Some(threads) = util::parallelization(args.flag_parallel, args.flag_threads) {
+        rdr.into_byte_records()
+            .parallel_map_custom(
+                |o| o.threads(threads),
+                move |result| -> CliResult<(ByteRecord, Option<Vec<u32>>)> {
+                    let record = result?;
+
+                    let text =
+                        std::str::from_utf8(&record[col_index]).expect("could not decode utf8");
+                    let signature = minhasher.signature(text);
+
+                    Ok((record, signature))
+                },
+            )
+            .try_for_each(|result| -> CliResult<()> {
+                let (mut record, signature) = result?;
+
+                process(&mut record, signature)
+            })?;
+    } else {
+        let mut record = ByteRecord::new();
+
+        while rdr.read_byte_record(&mut record)? {
+            let text = std::str::from_utf8(&record[col_index]).expect("could not decode utf8");
+            let signature = minhasher.signature(text);
+
+            process(&mut record, signature)?;
+        }
+    }
+
+    Ok(wtr.flush()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimated_jaccard() {
+        assert_eq!(estimated_jaccard(&[1, 2, 3, 4], &[1, 2, 3, 4]), 1.0);
+        assert_eq!(estimated_jaccard(&[1, 2, 3, 4], &[1, 0, 3, 0]), 0.5);
+        assert_eq!(estimated_jaccard(&[1, 2, 3, 4], &[0, 0, 0, 0]), 0.0);
+    }
+
+    #[test]
+    fn test_locality_sensitive_index() {
+        let mut index = LocalitySensitiveIndex::new(4, 2, 0.5);
+
+        assert_eq!(index.insert(&[1, 2, 3, 4]), (0, true));
+        assert_eq!(index.insert(&[5, 6, 7, 8]), (1, true));
+        assert_eq!(index.insert(&[1, 2, 0, 0]), (0, false));
+        assert_eq!(index.insert(&[1, 0, 7, 0]), (2, true));
+        assert_eq!(index.insert(&[9, 9, 7, 8]), (1, false));
+    }
+}
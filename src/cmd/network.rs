@@ -1,12 +1,16 @@
 use std::convert::TryFrom;
 
+use serde_json::Value;
 use simd_csv::StringRecord;
 
 use crate::CliResult;
-use crate::collections::IncrementalId;
+use crate::collections::{HashSet, IncrementalId};
 use crate::config::{Config, Delimiter};
-use crate::graph::{GraphBuilder, GraphBuilderOptions};
-use crate::json::{Attributes, JSONEmptyMode, JSONTypeInferrenceBuffer};
+use crate::graph::{
+    self, CentralityOptions, GraphBuilder, GraphBuilderOptions, KnnMetric, KnnOptions,
+    LayoutAlgorithm, LayoutOptions, ProjectionOptions, ProjectionWeighting,
+};
+use crate::json::{Attributes, JSONEmptyMode, JSONType, JSONTypeInferrenceBuffer};
 use crate::select::{SelectedColumns, Selection};
 use crate::util;
 
@@ -15,9 +19,14 @@ use crate::util;
 enum OutputFormat {
     Json,
     Gexf,
+    Graphml,
+    Dot,
+    Pajek,
     Nodes,
+    Edges,
     Components,
     Stats,
+    View,
 }
 
 impl OutputFormat {
@@ -33,9 +42,14 @@ impl TryFrom<String> for OutputFormat {
         Ok(match value.as_str() {
             "json" => Self::Json,
             "gexf" => Self::Gexf,
+            "graphml" => Self::Graphml,
+            "dot" => Self::Dot,
+            "pajek" | "net" => Self::Pajek,
             "nodes" | "nodelist" => Self::Nodes,
+            "edges" | "edgelist" => Self::Edges,
             "components" => Self::Components,
             "stats" => Self::Stats,
+            "view" => Self::View,
             _ => {
                 return Err(format!(
                     "unknown \"{}\" format given to -f/--format!",
@@ -59,17 +73,41 @@ Supported input modes:
                  into an empty graph.
     `bipartite`: converts a CSV with two columns representing the
                  edges between both parts of a bipartite graph.
+    `paths`:     reads a CSV of edges, like `edgelist`, and outputs a CSV of
+                 the shortest paths between the nodes given to --from and
+                 the nodes given to --to. Pairs of unconnected nodes are
+                 reported with empty columns.
+    `ego`:       reads a CSV of edges, like `edgelist`, and outputs the CSV
+                 edgelist of the subgraph containing the nodes found at
+                 most k hops away from the nodes given to --seeds.
+    `knn`:       converts a CSV of rows described by numerical features into
+                 a graph linking each row to its k most similar rows, using
+                 either columns given to -s/--select or vectors found in the
+                 column given to --from-vectors. Edges have a \"weight\"
+                 attribute containing the similarity of both rows.
 
 Supported output formats (-f, --format):
     `json`       - Graphology JSON serialization format
                    ref: https://graphology.github.io/serialization.html
     `gexf`       - Graph eXchange XML Format
                    ref: https://gexf.net/
-    `nodelist`   - CSV nodelist, with optional degrees if using -D/--degrees
+    `graphml`    - GraphML XML format, with typed node & edge attributes
+                   ref: http://graphml.graphdrawing.org/
+    `dot`        - Graphviz DOT language
+                   ref: https://graphviz.org/doc/info/lang.html
+    `pajek`      - Pajek .net format (also available as `net`). Node & edge
+                   attributes are dropped, except for edge weights when
+                   using --weight.
+    `nodelist`   - CSV nodelist, with optional degrees if using -D/--degrees,
+                   or centrality metrics if using e.g. --pagerank
+    `edgelist`   - CSV edgelist, with edge attributes
     `components` - CSV listing connected component sizes and an arbitrary
                    representative node
     `stats`      - Single CSV row of useful graph statistics (number of nodes, edges,
                    graph type, density etc.)
+    `view`       - Draw the graph in the terminal, using a force-directed layout
+                   (forceatlas2 by default, see --layout). Only suitable for
+                   small graphs, i.e. up to a few hundred nodes.
 
 Tips & tricks:
 
@@ -91,16 +129,21 @@ Usage:
     xan network nodelist [options] <node> [<input>]
     xan network edgelist [options] <source> <target> [<input>]
     xan network bipartite [options] <part1> <part2> [<input>]
+    xan network paths [options] <source> <target> [<input>]
+    xan network ego [options] <source> <target> [<input>]
+    xan network knn [options] <node> [<input>]
     xan network --help
 
 output format options:
-    -f, --format <format>     One of \"json\", \"gexf\", \"stats\", \"components\"
-                              or \"nodelist\".
-                              [default: json]
+    -f, --format <format>     One of \"json\", \"gexf\", \"graphml\", \"dot\",
+                              \"pajek\", \"stats\", \"components\", \"nodelist\",
+                              \"edgelist\" or \"view\". Defaults to \"json\",
+                              except for `paths` & `ego` which always output
+                              CSV data.
     --gexf-version <version>  GEXF version to output. Can be one of \"1.2\"
                               or \"1.3\".
                               [default: 1.2]
-    --minify                  Whether to minify json or gexf output.
+    --minify                  Whether to minify json, gexf or graphml output.
 
 xan network options:
     -L, --largest-component  Only keep the largest connected component
@@ -129,13 +172,134 @@ bipartite options:
                          use disjoint sets of keys (i.e. if you know they share
                          no common keys at all). Incorrect graphs will be produced
                          if some keys are used by both partitions!
+    --project <part>      Project the graph onto the part given as <part1>
+                          or <part2>, i.e. build an undirected graph linking
+                          nodes of this part sharing neighbors in the other part.
+                          Edges of the projected graph have a \"weight\" attribute.
+                          Use -f edgelist to get a weighted CSV edgelist.
+    --weighting <scheme>  How to weight the edges of the projected graph. One of:
+                            - \"count\": number of shared neighbors
+                            - \"newman\": Newman's collaboration weighting, where
+                              each shared neighbor of degree d counts as 1 / (d - 1)
+                            - \"jaccard\": Jaccard index of the neighborhoods
+                            - \"cosine\": cosine similarity of the neighborhoods
+                          [default: count]
+    --min-weight <w>      Drop edges of the projected graph whose weight is
+                          less than <w>.
+    --top-k <k>           Only keep, for each node of the projected graph, its
+                          <k> heaviest edges. An edge is kept as long as it is
+                          among the heaviest of one of its endpoints.
+
+paths options:
+    --from <nodes>     Comma-separated keys of the nodes from which to find
+                       shortest paths.
+    --to <nodes>       Comma-separated keys of the nodes to reach.
+    --path-sep <sep>   Separator used to join node keys in the \"path\" column.
+                       [default: |]
+
+Shortest paths are found using a BFS, or Dijkstra's algorithm when
+using --weight, in which case weights are considered as distances
+and a \"distance\" column is added to the output.
+
+ego options:
+    --seeds <nodes>  Comma-separated keys of the nodes whose neighborhood
+                     should be extracted.
+    --hops <k>       Maximum number of hops from the seeds. The direction
+                     of edges is ignored when collecting neighbors.
+                     [default: 1]
+
+knn options:
+    -s, --select <cols>     Numerical columns to use as features.
+    --from-vectors <col>    Column containing vectors, as numbers separated
+                            by --vector-sep.
+    --vector-sep <sep>      Separator of the numbers of the vectors found in
+                            the column given to --from-vectors. [default: |]
+    -k, --neighbors <k>     Number of neighbors of each node. [default: 10]
+    --metric <metric>       Metric used to compare rows. Can be one of \"cosine\"
+                            or \"euclidean\". Euclidean distances are converted
+                            to similarities as 1 / (1 + distance). Rows whose
+                            vector is null are never linked with cosine.
+                            [default: cosine]
+    --min-similarity <s>    Only link rows whose similarity is at least <s>.
+
+Edges of the knn graph are directed, from each node towards its neighbors,
+unless using -U/--undirected, in which case reciprocal edges are merged.
+Every pair of rows is compared, so this is only suitable for a few thousand rows.
+
+xan network community options:
+    --louvain              Whether to detect communities using the Louvain algorithm,
+                           so that a \"community\" column is added to the nodelist output,
+                           or a \"community\" node attribute to the json & gexf outputs.
+                           The direction of edges is ignored when detecting communities.
+                           An existing \"community\" node attribute will be overwritten.
+    --resolution <gamma>   Resolution of the modularity optimized by the Louvain
+                           algorithm. Higher values will yield more, smaller
+                           communities. [default: 1]
+
+xan network layout options:
+    --layout <algorithm>       Compute a force-directed layout of the graph, so that
+                               \"x\" & \"y\" columns are added to the nodelist output,
+                               or node attributes to the json, graphml & dot outputs.
+                               The gexf output uses viz:position elements instead.
+                               Existing \"x\" & \"y\" node attributes will be
+                               overwritten. The direction of edges is ignored.
+                               Can be one of \"forceatlas2\" or \"fruchterman-reingold\".
+    --iterations <n>           Number of iterations of the layout algorithm.
+                               [default: 100]
+    --barnes-hut               Whether to approximate repulsion using the Barnes-Hut
+                               algorithm, which is necessary to lay out large graphs
+                               in reasonable time.
+    --barnes-hut-theta <theta>
+                               Barnes-Hut approximation threshold. Higher values are
+                               faster but less accurate. [default: 1.2]
+
+xan network -f \"view\" options:
+    --color-by <attr>  Name of a categorical node attribute, e.g. \"community\"
+                       when using --louvain, used to color nodes. Nodes are
+                       colored by connected component by default.
+    --cols <num>       Width of the drawing in terminal columns, i.e. characters.
+                       Defaults to using all your terminal's width or 80 if
+                       terminal size cannot be found (i.e. when piping to file).
+                       Can also be given as a ratio or percentage of the terminal's
+                       width e.g. \"45%\" or \"0.5\".
+    --rows <num>       Height of the drawing in terminal rows, i.e. characters.
+                       Defaults to using all your terminal's height or 30 if
+                       terminal size cannot be found (i.e. when piping to file).
+                       Can also be given as a ratio or percentage of the terminal's
+                       height e.g. \"45%\" or \"0.5\".
 
 xan network -f \"nodelist\" options:
-    -D, --degrees  Whether to compute node degrees so it can be added
-                   to relevant outputs. Currently only relevant
-                   when using -f \"nodelist\".
-    --union-find   Whether to add a \"component\" column to the output indicating
-                   the label of the component each node belongs to.
+    -D, --degrees              Whether to compute node degrees so it can be added
+                               to relevant outputs. Currently only relevant
+                               when using -f \"nodelist\".
+    --union-find               Whether to add a \"component\" column to the output
+                               indicating the label of the component each node
+                               belongs to.
+    --pagerank                 Whether to add a \"pagerank\" column to the output.
+    --damping <alpha>          Damping factor used when computing pagerank.
+                               [default: 0.85]
+    --betweenness              Whether to add a normalized \"betweenness\" column to the
+                               output.
+    --betweenness-samples <k>  Approximate betweenness by only considering shortest
+                               paths starting from <k> randomly sampled nodes.
+    --closeness                Whether to add a \"closeness\" column to the output. For
+                               directed graphs, closeness is computed using outbound
+                               distances. Disconnected graphs are handled using
+                               Wasserman & Faust's formula.
+    --eigenvector              Whether to add an \"eigenvector\" centrality column to the
+                               output. Nodes of graphs without any cycle, e.g. DAGs,
+                               all get a null centrality.
+    --hits                     Whether to add \"hub\" & \"authority\" columns to the output,
+                               computed using the HITS algorithm.
+    --weight <col>             Name of the edge column containing weights to use when
+                               computing pagerank, eigenvector, hits, communities
+                               & layouts, to write with -f pajek, or as distances
+                               when finding shortest paths.
+                               Parallel edges have their weights summed. Weights must
+                               be finite & non-negative. Betweenness & closeness always
+                               rely on unweighted shortest paths.
+    --seed <number>            RNG seed used by --betweenness-samples, --louvain
+                               & --layout.
 
 Common options:
     -h, --help             Display this message
@@ -151,13 +315,16 @@ struct Args {
     cmd_nodelist: bool,
     cmd_edgelist: bool,
     cmd_bipartite: bool,
+    cmd_paths: bool,
+    cmd_ego: bool,
+    cmd_knn: bool,
     arg_input: Option<String>,
     arg_node: Option<SelectedColumns>,
     arg_source: Option<SelectedColumns>,
     arg_target: Option<SelectedColumns>,
     arg_part1: Option<SelectedColumns>,
     arg_part2: Option<SelectedColumns>,
-    flag_format: OutputFormat,
+    flag_format: Option<OutputFormat>,
     flag_gexf_version: String,
     flag_minify: bool,
     flag_largest_component: bool,
@@ -166,15 +333,58 @@ struct Args {
     flag_nodes: Option<String>,
     flag_node_column: SelectedColumns,
     flag_disjoint_keys: bool,
+    flag_project: Option<SelectedColumns>,
+    flag_weighting: ProjectionWeighting,
+    flag_min_weight: Option<f64>,
+    flag_top_k: Option<usize>,
+    flag_from: Option<String>,
+    flag_to: Option<String>,
+    flag_path_sep: String,
+    flag_seeds: Option<String>,
+    flag_hops: usize,
+    flag_select: Option<SelectedColumns>,
+    flag_from_vectors: Option<SelectedColumns>,
+    flag_vector_sep: String,
+    flag_neighbors: usize,
+    flag_metric: KnnMetric,
+    flag_min_similarity: Option<f64>,
     flag_range: Option<u32>,
     flag_sample_size: isize,
     flag_degrees: bool,
     flag_union_find: bool,
+    flag_pagerank: bool,
+    flag_damping: f64,
+    flag_betweenness: bool,
+    flag_betweenness_samples: Option<usize>,
+    flag_closeness: bool,
+    flag_eigenvector: bool,
+    flag_hits: bool,
+    flag_weight: Option<String>,
+    flag_louvain: bool,
+    flag_layout: Option<LayoutAlgorithm>,
+    flag_iterations: usize,
+    flag_barnes_hut: bool,
+    flag_barnes_hut_theta: f64,
+    flag_resolution: f64,
+    flag_color_by: Option<String>,
+    flag_cols: Option<String>,
+    flag_rows: Option<String>,
+    flag_seed: Option<usize>,
     flag_no_headers: bool,
     flag_delimiter: Option<Delimiter>,
     flag_output: Option<String>,
 }
 
+fn add_knn_node(graph_builder: &mut GraphBuilder, key: &str) -> CliResult<()> {
+    let order = graph_builder.order();
+
+    if graph_builder.add_node(key, Attributes::default()) != order {
+        Err(format!("found duplicate node key \"{}\"!", key))?;
+    }
+
+    Ok(())
+}
+
 impl Args {
     fn sample_size(&self) -> Option<usize> {
         if self.flag_sample_size <= 0 {
@@ -184,15 +394,44 @@ impl Args {
         }
     }
 
+    fn centrality_options(&self) -> CentralityOptions {
+        CentralityOptions {
+            weight: self.flag_weight.clone(),
+            pagerank: self.flag_pagerank,
+            damping: self.flag_damping,
+            betweenness: self.flag_betweenness,
+            betweenness_samples: self.flag_betweenness_samples,
+            seed: self.flag_seed,
+            closeness: self.flag_closeness,
+            eigenvector: self.flag_eigenvector,
+            hits: self.flag_hits,
+        }
+    }
+
+    fn format(&self) -> &OutputFormat {
+        self.flag_format.as_ref().unwrap_or(&OutputFormat::Json)
+    }
+
     fn edge_attributes_irrelevant(&self) -> bool {
-        matches!(
-            &self.flag_format,
-            OutputFormat::Nodes | OutputFormat::Stats | OutputFormat::Components
-        )
+        self.flag_weight.is_none()
+            && matches!(
+                self.format(),
+                OutputFormat::Nodes
+                    | OutputFormat::Stats
+                    | OutputFormat::Components
+                    | OutputFormat::Pajek
+                    | OutputFormat::View
+            )
     }
 
     fn edges_irrelevant(&self) -> bool {
-        self.flag_format.is_nodelist() && !self.flag_degrees && !self.flag_union_find
+        self.format().is_nodelist()
+            && !self.flag_degrees
+            && !self.flag_union_find
+            && !self.flag_largest_component
+            && !self.flag_louvain
+            && self.flag_layout.is_none()
+            && self.centrality_options().is_empty()
     }
 
     fn graph_builder(&self) -> GraphBuilder {
@@ -202,7 +441,7 @@ impl Args {
         // Ultimately this means we need it only for -f=(json|stats) for now
 
         let output_needs_to_track_multi =
-            matches!(&self.flag_format, OutputFormat::Json | OutputFormat::Stats);
+            matches!(self.format(), OutputFormat::Json | OutputFormat::Stats);
 
         let mut linear_edge_store = true;
 
@@ -428,6 +667,70 @@ impl Args {
             .unwrap()
             .single_selection(&headers, !rconf.no_headers)?;
 
+        if let Some(selection) = &self.flag_project {
+            let projected_index = selection.single_selection(&headers, !rconf.no_headers)?;
+
+            let other_index = if projected_index == first_part_index {
+                second_part_index
+            } else if projected_index == second_part_index {
+                first_part_index
+            } else {
+                Err("--project must select either <part1> or <part2>!")?
+            };
+
+            let mut other_ids = IncrementalId::<String>::new();
+            let mut neighborhoods: Vec<Vec<usize>> = Vec::new();
+
+            while reader.read_record(&mut record)? {
+                let node_id = graph_builder.get_source_node_id(&record[projected_index]);
+                let other_id = other_ids.get(record[other_index].to_string());
+
+                if other_id == neighborhoods.len() {
+                    neighborhoods.push(Vec::new());
+                }
+
+                neighborhoods[other_id].push(node_id);
+            }
+
+            for neighbors in neighborhoods.iter_mut() {
+                neighbors.sort_unstable();
+                neighbors.dedup();
+            }
+
+            let options = ProjectionOptions {
+                weighting: self.flag_weighting,
+                min_weight: self.flag_min_weight,
+                top_k: self.flag_top_k,
+            };
+
+            let weight_type = if options.weighting.is_integer() {
+                JSONType::Integer
+            } else {
+                JSONType::Float
+            };
+
+            graph_builder.set_edge_model(["weight"].into_iter(), [weight_type].into_iter());
+
+            for (source, target, weight) in
+                graph::project(&neighborhoods, graph_builder.order(), &options)
+            {
+                let mut attributes = Attributes::with_capacity(1);
+
+                attributes.insert(
+                    "weight",
+                    if options.weighting.is_integer() {
+                        Value::from(weight as u64)
+                    } else {
+                        Value::from(weight)
+                    },
+                );
+
+                graph_builder.add_edge_with_attributes(source, target, attributes);
+            }
+
+            return Ok(graph_builder);
+        }
+
         let mut incremental_id =
             (!self.flag_disjoint_keys).then(IncrementalId::<(usize, String)>::new);
 
@@ -453,12 +756,104 @@ impl Args {
 
         Ok(graph_builder)
     }
+
+    fn knn(&self) -> CliResult<GraphBuilder> {
+        let rconf = Config::new(&self.arg_input)
+            .delimiter(self.flag_delimiter)
+            .no_headers(self.flag_no_headers);
+
+        let mut graph_builder = self.graph_builder();
+
+        let mut reader = rconf.reader()?;
+        let mut record = csv::StringRecord::new();
+
+        let headers = reader.byte_headers()?.clone();
+
+        let node_index = self
+            .arg_node
+            .as_ref()
+            .unwrap()
+            .single_selection(&headers, !rconf.no_headers)?;
+
+        let parse_float = |cell: &str| -> CliResult<f64> {
+            Ok(fast_float::parse::<f64, &str>(cell)
+                .map_err(|_| format!("could not parse cell \"{}\" as a float!", cell))?)
+        };
+
+        let mut vectors: Vec<Vec<f64>> = Vec::new();
+
+        if let Some(selection) = &self.flag_select {
+            let sel = selection.selection(&headers, !rconf.no_headers)?;
+
+            while reader.read_record(&mut record)? {
+                add_knn_node(&mut graph_builder, &record[node_index])?;
+
+                vectors.push(
+                    sel.iter()
+                        .map(|i| parse_float(&record[*i]))
+                        .collect::<CliResult<Vec<_>>>()?,
+                );
+            }
+        } else {
+            let vector_index = self
+                .flag_from_vectors
+                .as_ref()
+                .unwrap()
+                .single_selection(&headers, !rconf.no_headers)?;
+
+            while reader.read_record(&mut record)? {
+                add_knn_node(&mut graph_builder, &record[node_index])?;
+
+                let vector = record[vector_index]
+                    .split(self.flag_vector_sep.as_str())
+                    .map(parse_float)
+                    .collect::<CliResult<Vec<_>>>()?;
+
+                if let Some(first) = vectors.first() {
+                    if first.len() != vector.len() {
+                        Err(format!(
+                            "found vectors of inconsistent dimensions ({} & {})!",
+                            first.len(),
+                            vector.len()
+                        ))?;
+                    }
+                }
+
+                vectors.push(vector);
+            }
+        }
+
+        let options = KnnOptions {
+            metric: self.flag_metric,
+            k: self.flag_neighbors,
+            min_similarity: self.flag_min_similarity,
+        };
+
+        graph_builder.set_edge_model(["weight"].into_iter(), [JSONType::Float].into_iter());
+
+        // NOTE: reciprocal edges are only added once when undirected
+        let mut seen_pairs = HashSet::new();
+
+        for (source, target, similarity) in graph::knn(&vectors, &options) {
+            if self.flag_undirected && !seen_pairs.insert((source.min(target), source.max(target)))
+            {
+                continue;
+            }
+
+            let mut attributes = Attributes::with_capacity(1);
+            attributes.insert("weight", Value::from(similarity));
+
+            graph_builder.add_edge_with_attributes(source, target, attributes);
+        }
+
+        Ok(graph_builder)
+    }
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let mut args: Args = util::get_args(USAGE, argv)?;
 
-    if !args.flag_format.is_nodelist() {
+    if !args.format().is_nodelist() {
         if args.flag_degrees {
             Err("-D/--degrees is only relevant with -f nodelist!")?;
         }
@@ -466,13 +861,135 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         if args.flag_union_find {
             Err("--union-find is only relevant with -f nodelist!")?;
         }
+
+        if !args.centrality_options().is_empty() {
+            Err("centrality metrics are only relevant with -f nodelist!")?;
+        }
+    }
+
+    if args.flag_louvain
+        && matches!(
+            args.format(),
+            OutputFormat::Stats | OutputFormat::Components
+        )
+    {
+        Err("--louvain is not relevant with -f (stats|components)!")?;
+    }
+
+    if args.cmd_paths {
+        if args.flag_from.is_none() || args.flag_to.is_none() {
+            Err("xan network paths requires both --from & --to!")?;
+        }
+    } else if args.flag_from.is_some() || args.flag_to.is_some() {
+        Err("--from & --to are only relevant with xan network paths!")?;
+    }
+
+    if args.cmd_paths && args.flag_format.is_some() {
+        Err("-f/--format is not relevant with xan network paths!")?;
+    }
+
+    if args.cmd_ego {
+        if args.flag_seeds.is_none() {
+            Err("xan network ego requires --seeds!")?;
+        }
+
+        if !matches!(args.flag_format, None | Some(OutputFormat::Edges)) {
+            Err("xan network ego only supports -f edgelist!")?;
+        }
+    } else if args.flag_seeds.is_some() {
+        Err("--seeds is only relevant with xan network ego!")?;
+    }
+
+    if (args.cmd_paths || args.cmd_ego) && args.flag_louvain {
+        Err("--louvain is not relevant with xan network (paths|ego)!")?;
+    }
+
+    if args.flag_layout.is_some()
+        && (args.cmd_paths
+            || args.cmd_ego
+            || matches!(
+                args.format(),
+                OutputFormat::Stats
+                    | OutputFormat::Components
+                    | OutputFormat::Edges
+                    | OutputFormat::Pajek
+            ))
+    {
+        Err("--layout is only relevant with -f (json|gexf|graphml|dot|nodelist)!")?;
+    }
+
+    if matches!(args.format(), OutputFormat::View) {
+        args.flag_layout.get_or_insert(LayoutAlgorithm::ForceAtlas2);
+    } else if args.flag_color_by.is_some() || args.flag_cols.is_some() || args.flag_rows.is_some() {
+        Err("--color-by, --cols & --rows are only relevant with -f view!")?;
+    }
+
+    if args.flag_barnes_hut_theta <= 0.0 {
+        Err("--barnes-hut-theta must be positive!")?;
+    }
+
+    if args.cmd_knn {
+        if args.flag_select.is_some() == args.flag_from_vectors.is_some() {
+            Err("xan network knn requires either -s/--select or --from-vectors!")?;
+        }
+
+        if args.flag_neighbors == 0 {
+            Err("-k/--neighbors must be greater than 0!")?;
+        }
+
+        if args.flag_range.is_some() {
+            Err("--range is not relevant with xan network knn!")?;
+        }
+    } else if args.flag_select.is_some()
+        || args.flag_from_vectors.is_some()
+        || args.flag_min_similarity.is_some()
+    {
+        Err(
+            "-s/--select, --from-vectors & --min-similarity are only relevant with xan network knn!",
+        )?;
+    }
+
+    if args.flag_project.is_some() {
+        if !args.cmd_bipartite {
+            Err("--project is only relevant with xan network bipartite!")?;
+        }
+
+        if matches!(args.flag_top_k, Some(0)) {
+            Err("--top-k must be greater than 0!")?;
+        }
+
+        // NOTE: a projection is always undirected
+        args.flag_undirected = true;
+    } else if args.flag_min_weight.is_some() || args.flag_top_k.is_some() {
+        Err("--min-weight & --top-k are only relevant with --project!")?;
+    }
+
+    if args.flag_resolution <= 0.0 {
+        Err("--resolution must be positive!")?;
+    }
+
+    if !(0.0..=1.0).contains(&args.flag_damping) {
+        Err("--damping must be between 0 and 1!")?;
+    }
+
+    if args.flag_betweenness_samples.is_some() && !args.flag_betweenness {
+        Err("--betweenness-samples requires --betweenness!")?;
+    }
+
+    if args.flag_betweenness_samples == Some(0) {
+        Err("--betweenness-samples must be greater than 0!")?;
     }
 
-    if args.flag_minify && !matches!(&args.flag_format, OutputFormat::Gexf | OutputFormat::Json) {
-        Err("--minify is only relevant with -f (json|gexf)!")?;
+    if args.flag_minify
+        && !matches!(
+            args.format(),
+            OutputFormat::Gexf | OutputFormat::Json | OutputFormat::Graphml
+        )
+    {
+        Err("--minify is only relevant with -f (json|gexf|graphml)!")?;
     }
 
-    if matches!(&args.flag_format, OutputFormat::Components) && args.flag_largest_component {
+    if matches!(args.format(), OutputFormat::Components) && args.flag_largest_component {
         Err("-L/--largest-component is not relevant with -f components!")?;
     }
 
@@ -489,31 +1006,105 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         args.flag_nodes = args.arg_input.clone().or_else(|| Some("-".to_string()));
         args.flag_node_column = args.arg_node.clone().unwrap();
         args.edgelist()
-    } else if args.cmd_edgelist {
+    } else if args.cmd_edgelist || args.cmd_paths || args.cmd_ego {
         args.edgelist()
     } else if args.cmd_bipartite {
         args.bipartite()
+    } else if args.cmd_knn {
+        args.knn()
     } else {
         unreachable!()
     })?;
 
-    match args.flag_format {
+    fn parse_keys(keys: &str) -> Vec<String> {
+        keys.split(',').map(String::from).collect()
+    }
+
+    if args.cmd_paths {
+        return builder.write_csv_paths(
+            &wconf,
+            &parse_keys(args.flag_from.as_ref().unwrap()),
+            &parse_keys(args.flag_to.as_ref().unwrap()),
+            args.flag_weight.as_deref(),
+            &args.flag_path_sep,
+        );
+    }
+
+    if args.cmd_ego {
+        return builder.write_csv_ego(
+            &wconf,
+            &parse_keys(args.flag_seeds.as_ref().unwrap()),
+            args.flag_hops,
+        );
+    }
+
+    if args.flag_louvain {
+        builder.add_louvain_communities(
+            args.flag_weight.as_deref(),
+            args.flag_resolution,
+            args.flag_seed,
+        )?;
+    }
+
+    if let Some(algorithm) = args.flag_layout {
+        builder.add_layout(
+            args.flag_weight.as_deref(),
+            &LayoutOptions {
+                algorithm,
+                iterations: args.flag_iterations,
+                barnes_hut_theta: args.flag_barnes_hut.then_some(args.flag_barnes_hut_theta),
+            },
+            args.flag_seed,
+        )?;
+    }
+
+    match args.format() {
         OutputFormat::Stats => builder.write_csv_stats(&wconf, args.flag_largest_component),
         OutputFormat::Nodes => builder.write_csv_nodelist(
             &wconf,
             args.flag_largest_component,
             args.flag_degrees,
             args.flag_union_find,
+            &args.centrality_options(),
         ),
         OutputFormat::Components => builder.write_csv_components(&wconf),
+        OutputFormat::Edges => builder.write_csv_edgelist(&wconf, args.flag_largest_component),
         OutputFormat::Gexf => builder.write_gexf(
             &wconf,
             &args.flag_gexf_version,
             args.flag_minify,
             args.flag_largest_component,
         ),
+        OutputFormat::Graphml => {
+            builder.write_graphml(&wconf, args.flag_minify, args.flag_largest_component)
+        }
+        OutputFormat::Dot => builder.write_dot(&wconf, args.flag_largest_component),
+        OutputFormat::Pajek => builder.write_pajek(
+            &wconf,
+            args.flag_weight.as_deref(),
+            args.flag_largest_component,
+        ),
         OutputFormat::Json => {
             builder.write_json(&wconf, args.flag_minify, args.flag_largest_component)
         }
+        OutputFormat::View => {
+            let cols = util::acquire_term_cols_ratio(&args.flag_cols)?;
+            let rows = util::acquire_term_rows_ratio(&args.flag_rows)?;
+
+            if cols < 10 {
+                Err("not enough cols to draw!")?;
+            }
+
+            if rows < 5 {
+                Err("not enough rows to draw!")?;
+            }
+
+            builder.print_view(
+                args.flag_color_by.as_deref(),
+                args.flag_largest_component,
+                cols,
+                rows,
+            )
+        }
     }
 }
@@ -0,0 +1,505 @@
+use simd_csv::ByteRecord;
+
+use crate::cmd::stats::linear_time_median;
+use crate::collections::ClusteredInsertHashmap;
+use crate::config::{Config, Delimiter};
+use crate::moonblade::agg::aggregators::{ApproxQuantiles, Numbers};
+use crate::moonblade::{DynamicNumber, Welford};
+use crate::select::{SelectedColumns, Selection};
+use crate::util;
+use crate::{CliError, CliResult};
+
+// NOTE: consistency constant so that MAD estimates the standard deviation
+// of normally distributed values.
+const MAD_CONSTANT: f64 = 0.6745;
+
+// NOTE: number of values to see before judging rows in single pass mode
+const WARMUP: usize = 10;
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(try_from = "String")]
+enum Method {
+    Iqr,
+    ZScore,
+    Mad,
+}
+
+impl Method {
+    fn default_threshold(&self) -> f64 {
+        match self {
+            Self::Iqr => 1.5,
+            Self::ZScore => 3.0,
+            Self::Mad => 3.5,
+        }
+    }
+}
+
+impl TryFrom<String> for Method {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(match value.as_str() {
+            "iqr" => Self::Iqr,
+            "zscore" => Self::ZScore,
+            "mad" => Self::Mad,
+            _ => return Err(format!("unsupported --method \"{}\"", &value)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Bounds {
+    Iqr { q1: f64, q3: f64 },
+    ZScore { mean: f64, stddev: f64 },
+    Mad { median: f64, mad: f64 },
+}
+
+fn ratio(distance: f64, scale: f64) -> f64 {
+    if distance == 0.0 {
+        0.0
+    } else {
+        distance / scale
+    }
+}
+
+impl Bounds {
+    fn score(&self, value: f64) -> f64 {
+        match self {
+            Self::Iqr { q1, q3 } => {
+                let distance = if value < *q1 {
+                    q1 - value
+                } else if value > *q3 {
+                    value - q3
+                } else {
+                    0.0
+                };
+
+                ratio(distance, q3 - q1)
+            }
+            Self::ZScore { mean, stddev } => ratio((value - mean).abs(), *stddev),
+            Self::Mad { median, mad } => ratio(MAD_CONSTANT * (value - median).abs(), *mad),
+        }
+    }
+}
+
+// NOTE: merging the digest is costly, so we only flush its buffer when it
+// grows as large as the digest itself, which means flushing often at the
+// beginning so early rows are judged against up-to-date statistics, then only
+// when the buffer is full.
+fn add_approx(quantiles: &mut ApproxQuantiles, value: f64) {
+    quantiles.add(value);
+
+    if quantiles.buffered() as f64 >= quantiles.digest().count() {
+        quantiles.flush();
+    }
+}
+
+fn approx_count(quantiles: &ApproxQuantiles) -> usize {
+    quantiles.digest().count() as usize + quantiles.buffered()
+}
+
+#[derive(Debug, Clone)]
+enum Accumulator {
+    Welford(Welford),
+    Numbers(Numbers),
+    Values(Vec<f64>),
+    ApproxQuantiles(Box<ApproxQuantiles>),
+    ApproxDeviations(Box<(ApproxQuantiles, ApproxQuantiles)>),
+}
+
+impl Accumulator {
+    fn new(method: Method, approx: bool) -> Self {
+        match (method, approx) {
+            (Method::ZScore, _) => Self::Welford(Welford::new()),
+            (Method::Iqr, false) => Self::Numbers(Numbers::new()),
+            (Method::Iqr, true) => Self::ApproxQuantiles(Box::new(ApproxQuantiles::new())),
+            (Method::Mad, false) => Self::Values(Vec::new()),
+            (Method::Mad, true) => {
+                Self::ApproxDeviations(Box::new((ApproxQuantiles::new(), ApproxQuantiles::new())))
+            }
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        match self {
+            Self::Welford(welford) => welford.add(value),
+            Self::Numbers(numbers) => numbers.add(DynamicNumber::Float(value)),
+            Self::Values(values) => values.push(value),
+            Self::ApproxQuantiles(quantiles) => add_approx(quantiles, value),
+            Self::ApproxDeviations(inner) => {
+                let (quantiles, deviations) = inner.as_mut();
+
+                add_approx(quantiles, value);
+
+                // NOTE: deviations are computed against the current median estimate
+                add_approx(deviations, (value - quantiles.get(0.5)).abs());
+            }
+        }
+    }
+
+    fn count(&self) -> usize {
+        match self {
+            Self::Welford(welford) => welford.count(),
+            Self::Numbers(_) | Self::Values(_) => unreachable!(),
+            Self::ApproxQuantiles(quantiles) => approx_count(quantiles),
+            Self::ApproxDeviations(inner) => approx_count(&inner.0),
+        }
+    }
+
+    // NOTE: exact accumulators must only be finalized once
+    fn bounds(&mut self) -> Option<Bounds> {
+        match self {
+            Self::Welford(welford) => Some(Bounds::ZScore {
+                mean: welford.mean()?,
+                stddev: welford.sample_stddev()?,
+            }),
+            Self::Numbers(numbers) => {
+                numbers.finalize(false);
+
+                let quartiles = numbers.quartiles()?;
+
+                Some(Bounds::Iqr {
+                    q1: quartiles[0].as_float(),
+                    q3: quartiles[2].as_float(),
+                })
+            }
+            Self::Values(values) => {
+                if values.is_empty() {
+                    return None;
+                }
+
+                let median = linear_time_median(values);
+
+                let mut deviations = values
+                    .iter()
+                    .map(|value| (value - median).abs())
+                    .collect::<Vec<_>>();
+
+                Some(Bounds::Mad {
+                    median,
+                    mad: linear_time_median(&mut deviations),
+                })
+            }
+            Self::ApproxQuantiles(quantiles) => {
+                if quantiles.digest().is_empty() {
+                    return None;
+                }
+
+                Some(Bounds::Iqr {
+                    q1: quantiles.get(0.25),
+                    q3: quantiles.get(0.75),
+                })
+            }
+            Self::ApproxDeviations(inner) => {
+                let (quantiles, deviations) = inner.as_ref();
+
+                if quantiles.digest().is_empty() {
+                    return None;
+                }
+
+                Some(Bounds::Mad {
+                    median: quantiles.get(0.5),
+                    mad: deviations.get(0.5),
+                })
+            }
+        }
+    }
+}
+
+fn parse_float(cell: &[u8]) -> CliResult<Option<f64>> {
+    if cell.is_empty() {
+        return Ok(None);
+    }
+
+    fast_float::parse::<f64, &[u8]>(cell)
+        .map(Some)
+        .map_err(|_| {
+            CliError::Other(format!(
+                "could not parse cell \"{}\" as a float!",
+                String::from_utf8_lossy(cell)
+            ))
+        })
+}
+
+fn parse_values(sel: &Selection, record: &ByteRecord) -> CliResult<Vec<Option<f64>>> {
+    sel.select(record).map(parse_float).collect()
+}
+
+static USAGE: &str = "
+Find rows containing outlier values in the selected numerical columns.
+
+Supported methods (-m/--method):
+    iqr     - values lying outside of the [q1 - t * iqr, q3 + t * iqr] fences,
+              iqr being the interquartile range (q3 - q1). Default threshold
+              is 1.5.
+    zscore  - values whose absolute z-score, i.e. their distance to the mean
+              in standard deviations, is greater than the threshold. Default
+              threshold is 3.
+    mad     - values whose absolute modified z-score, relying on the median
+              absolute deviation (MAD), is greater than the threshold. Default
+              threshold is 3.5.
+
+A row is considered an outlier if any of its values in the selected columns is
+an outlier. Empty cells are ignored. Statistics can be computed per group
+using the -g/--groupby flag.
+
+By default, the command will only output outlier rows. Use -v/--invert to
+drop them instead, or -f/--flag to add a column indicating whether each row
+is an outlier. The --score flag can also be used to add a column containing
+the maximum score of the row's values (distance to the fences in iqr units for
+the iqr method, or absolute z-score for the other ones).
+
+By default, the command needs to read the input twice to compute exact
+statistics first. Use -a/--approx to run in a single pass instead, in which
+case each row will be judged against the approximate statistics of the rows
+seen before it (the first 10 values of each column, per group, are never
+considered outliers). Note that this mode is automatically used when
+reading from stdin.
+
+Also note that exact iqr & mad methods need to store the selected values in
+memory.
+
+Examples:
+
+Finding rows with outlier prices:
+
+    $ xan outliers -s price data.csv
+
+Dropping outliers, using the modified z-score, per category:
+
+    $ xan outliers -s price -m mad -g category -v data.csv
+
+Flagging outliers:
+
+    $ xan outliers -s price,surface -f is_outlier data.csv
+
+Usage:
+    xan outliers [options] [<input>]
+    xan outliers --help
+
+outliers options:
+    -s, --select <cols>    Numerical columns to consider.
+    -m, --method <method>  Method used to find outliers, must be one of \"iqr\",
+                           \"zscore\" or \"mad\". [default: iqr]
+    -T, --threshold <t>    Threshold used by the chosen method. Defaults to 1.5
+                           for iqr, 3 for zscore & 3.5 for mad.
+    -g, --groupby <cols>   Compute statistics per group, as defined by given
+                           column selection.
+    -v, --invert           Drop outlier rows instead of keeping them.
+    -f, --flag <name>      Instead of filtering rows, add a column with given <name>
+                           indicating whether a row is an outlier.
+    --score <name>         Add a column with given <name> containing the
+                           maximum score of the row's values.
+    -a, --approx           Run in a single pass, using approximated statistics.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will not be interpreted
+                           as headers.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character.
+";
+
+#[derive(Deserialize, Debug)]
+struct Args {
+    arg_input: Option<String>,
+    flag_select: SelectedColumns,
+    flag_method: Method,
+    flag_threshold: Option<f64>,
+    flag_groupby: Option<SelectedColumns>,
+    flag_invert: bool,
+    flag_flag: Option<String>,
+    flag_score: Option<String>,
+    flag_approx: bool,
+    flag_output: Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter: Option<Delimiter>,
+}
+
+struct OutlierWriter<W: std::io::Write> {
+    writer: simd_csv::Writer<W>,
+    threshold: f64,
+    invert: bool,
+    flag: bool,
+    score: bool,
+}
+
+impl<W: std::io::Write> OutlierWriter<W> {
+    fn write(&mut self, record: &ByteRecord, score: Option<f64>) -> CliResult<()> {
+        let is_outlier = score.is_some_and(|s| s > self.threshold);
+
+        if !self.flag && is_outlier == self.invert {
+            return Ok(());
+        }
+
+        let mut output_record = record.clone();
+
+        if self.flag {
+            output_record.push_field(if is_outlier { b"true" } else { b"false" });
+        }
+
+        if self.score {
+            output_record.push_field(score.map(|s| s.to_string()).unwrap_or_default().as_bytes());
+        }
+
+        self.writer.write_byte_record(&output_record)?;
+
+        Ok(())
+    }
+}
+
+fn max_score<'a>(
+    values: &[Option<f64>],
+    bounds: impl Iterator<Item = Option<&'a Bounds>>,
+) -> Option<f64> {
+    values
+        .iter()
+        .zip(bounds)
+        .filter_map(|(value, bounds)| Some(bounds?.score((*value)?)))
+        .reduce(f64::max)
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let rconf = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers)
+        .select(args.flag_select.clone());
+
+    let approx = args.flag_approx || rconf.is_std();
+    let method = args.flag_method;
+
+    if args.flag_invert && args.flag_flag.is_some() {
+        Err("-v/--invert does not make sense with -f/--flag!")?;
+    }
+
+    let mut reader = rconf.simd_reader()?;
+    let headers = reader.byte_headers()?.clone();
+
+    let sel = rconf.selection(&headers)?;
+
+    let groupby_sel_opt = args
+        .flag_groupby
+        .as_ref()
+        .map(|cols| cols.selection(&headers, !rconf.no_headers))
+        .transpose()?;
+
+    let get_group = |record: &ByteRecord| -> Vec<Vec<u8>> {
+        match &groupby_sel_opt {
+            Some(sel) => sel.collect(record),
+            None => vec![],
+        }
+    };
+
+    let new_accumulators = || -> Vec<Accumulator> {
+        (0..sel.len())
+            .map(|_| Accumulator::new(method, approx))
+            .collect()
+    };
+
+    let mut writer = OutlierWriter {
+        writer: Config::new(&args.flag_output).simd_writer()?,
+        threshold: args
+            .flag_threshold
+            .unwrap_or_else(|| method.default_threshold()),
+        invert: args.flag_invert,
+        flag: args.flag_flag.is_some(),
+        score: args.flag_score.is_some(),
+    };
+
+    if !rconf.no_headers {
+        let mut output_headers = headers.clone();
+
+        if let Some(name) = &args.flag_flag {
+            output_headers.push_field(name.as_bytes());
+        }
+
+        if let Some(name) = &args.flag_score {
+            output_headers.push_field(name.as_bytes());
+        }
+
+        writer.writer.write_byte_record(&output_headers)?;
+    }
+
+    let mut groups: ClusteredInsertHashmap<Vec<Vec<u8>>, Vec<Accumulator>> =
+        ClusteredInsertHashmap::new();
+
+    let mut record = ByteRecord::new();
+
+    // Single pass, judging rows against statistics of previous ones
+    if approx {
+        while reader.read_byte_record(&mut record)? {
+            let values = parse_values(&sel, &record)?;
+            let accumulators = groups.insert_with(get_group(&record), new_accumulators);
+
+            let bounds = accumulators
+                .iter_mut()
+                .map(|accumulator| {
+                    if accumulator.count() < WARMUP {
+                        None
+                    } else {
+                        accumulator.bounds()
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            writer.write(
+                &record,
+                max_score(&values, bounds.iter().map(|b| b.as_ref())),
+            )?;
+
+            for (accumulator, value) in accumulators.iter_mut().zip(values) {
+                if let Some(value) = value {
+                    accumulator.add(value);
+                }
+            }
+        }
+
+        return Ok(writer.writer.flush()?);
+    }
+
+    // First pass, computing statistics
+    while reader.read_byte_record(&mut record)? {
+        let values = parse_values(&sel, &record)?;
+        let accumulators = groups.insert_with(get_group(&record), new_accumulators);
+
+        for (accumulator, value) in accumulators.iter_mut().zip(values) {
+            if let Some(value) = value {
+                accumulator.add(value);
+            }
+        }
+    }
+
+    let mut bounds_per_group: ClusteredInsertHashmap<Vec<Vec<u8>>, Vec<Option<Bounds>>> =
+        ClusteredInsertHashmap::new();
+
+    for (group, mut accumulators) in groups.into_iter() {
+        bounds_per_group.insert_with(group, || {
+            accumulators
+                .iter_mut()
+                .map(|accumulator| accumulator.bounds())
+                .collect()
+        });
+    }
+
+    // Second pass, finding outliers
+    let mut reader = rconf.simd_reader()?;
+
+    if !rconf.no_headers {
+        reader.byte_headers()?;
+    }
+
+    while reader.read_byte_record(&mut record)? {
+        let values = parse_values(&sel, &record)?;
+        let bounds = bounds_per_group.get(&get_group(&record)).unwrap();
+
+        writer.write(
+            &record,
+            max_score(&values, bounds.iter().map(|b| b.as_ref())),
+        )?;
+    }
+
+    Ok(writer.writer.flush()?)
+}
@@ -0,0 +1,678 @@
+use simd_csv::ByteRecord;
+
+use crate::collections::ClusteredInsertHashmap;
+use crate::config::{Config, Delimiter};
+use crate::select::SelectedColumns;
+use crate::statistics::{invert_matrix, normal_two_sided_pvalue, student_t_two_sided_pvalue};
+use crate::util;
+use crate::{CliError, CliResult};
+
+const LOGISTIC_MAX_ITERATIONS: usize = 100;
+const LOGISTIC_TOLERANCE: f64 = 1e-10;
+
+fn parse_float(cell: &[u8]) -> CliResult<Option<f64>> {
+    if cell.is_empty() {
+        return Ok(None);
+    }
+
+    fast_float::parse::<f64, &[u8]>(cell)
+        .map(Some)
+        .map_err(|_| {
+            CliError::Other(format!(
+                "could not parse cell \"{}\" as a float!",
+                String::from_utf8_lossy(cell)
+            ))
+        })
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+fn mat_vec(matrix: &[f64], vector: &[f64]) -> Vec<f64> {
+    matrix
+        .chunks_exact(vector.len())
+        .map(|row| dot(row, vector))
+        .collect()
+}
+
+#[derive(Debug)]
+struct Fit {
+    n: usize,
+    logistic: bool,
+    coefficients: Vec<f64>,
+    stderrs: Vec<Option<f64>>,
+    r2: Option<f64>,
+    adj_r2: Option<f64>,
+    df: Option<f64>,
+}
+
+impl Fit {
+    fn statistic(&self, i: usize) -> Option<f64> {
+        self.stderrs[i].map(|stderr| self.coefficients[i] / stderr)
+    }
+
+    fn pvalue(&self, i: usize) -> Option<f64> {
+        self.statistic(i).and_then(|statistic| {
+            if self.logistic {
+                Some(normal_two_sided_pvalue(statistic))
+            } else {
+                self.df.map(|df| student_t_two_sided_pvalue(statistic, df))
+            }
+        })
+    }
+
+    fn predict(&self, x: &[f64]) -> f64 {
+        let value = dot(&self.coefficients, x);
+
+        if self.logistic { sigmoid(value) } else { value }
+    }
+}
+
+// NOTE: ordinary least squares only need to stream the means & co-moments of
+// the independent variables & target, so we don't need to store the rows.
+// Co-moments are accumulated around the running means, like Welford's
+// algorithm, on values shifted by the first row, so that large offsets in the
+// data don't wreck precision.
+#[derive(Debug, Clone)]
+struct LinearModel {
+    p: usize,
+    n: usize,
+    shift_x: Vec<f64>,
+    shift_y: f64,
+    mean_x: Vec<f64>,
+    mean_y: f64,
+    sxx: Vec<f64>,
+    sxy: Vec<f64>,
+    syy: f64,
+    // NOTE: scratch buffer to avoid allocating for each row
+    delta_x: Vec<f64>,
+}
+
+impl LinearModel {
+    fn new(p: usize) -> Self {
+        // NOTE: the intercept is not part of the co-moments
+        let k = p - 1;
+
+        Self {
+            p,
+            n: 0,
+            shift_x: vec![0.0; k],
+            shift_y: 0.0,
+            mean_x: vec![0.0; k],
+            mean_y: 0.0,
+            sxx: vec![0.0; k * k],
+            sxy: vec![0.0; k],
+            syy: 0.0,
+            delta_x: vec![0.0; k],
+        }
+    }
+
+    fn add(&mut self, x: &[f64], y: f64) {
+        let k = self.p - 1;
+        let x = &x[1..];
+
+        if self.n == 0 {
+            self.shift_x.copy_from_slice(x);
+            self.shift_y = y;
+        }
+
+        self.n += 1;
+
+        let n = self.n as f64;
+
+        for ((delta, mean), (value, shift)) in self
+            .delta_x
+            .iter_mut()
+            .zip(self.mean_x.iter_mut())
+            .zip(x.iter().zip(self.shift_x.iter()))
+        {
+            *delta = (value - shift) - *mean;
+            *mean += *delta / n;
+        }
+
+        let y = y - self.shift_y;
+        let delta_y = y - self.mean_y;
+        self.mean_y += delta_y / n;
+
+        for i in 0..k {
+            for (j, (value, shift)) in x.iter().zip(self.shift_x.iter()).enumerate().skip(i) {
+                self.sxx[i * k + j] += self.delta_x[i] * ((value - shift) - self.mean_x[j]);
+            }
+
+            self.sxy[i] += self.delta_x[i] * (y - self.mean_y);
+        }
+
+        self.syy += delta_y * (y - self.mean_y);
+    }
+
+    fn fit(&self) -> Option<Fit> {
+        let p = self.p;
+        let k = p - 1;
+
+        // NOTE: scaling the co-moments to a correlation matrix before
+        // inverting it, so that predictors of very different magnitudes
+        // remain comparable when testing for singularity.
+        let scales = (0..k)
+            .map(|i| self.sxx[i * k + i].sqrt())
+            .collect::<Vec<_>>();
+
+        if self.n == 0 || scales.iter().any(|scale| *scale == 0.0) {
+            return None;
+        }
+
+        let mut correlations = vec![0.0; k * k];
+
+        for i in 0..k {
+            for j in i..k {
+                let value = self.sxx[i * k + j] / (scales[i] * scales[j]);
+
+                correlations[i * k + j] = value;
+                correlations[j * k + i] = value;
+            }
+        }
+
+        let mut inverse = invert_matrix(&correlations, k)?;
+
+        for i in 0..k {
+            for j in 0..k {
+                inverse[i * k + j] /= scales[i] * scales[j];
+            }
+        }
+
+        let mean_x = self
+            .mean_x
+            .iter()
+            .zip(self.shift_x.iter())
+            .map(|(mean, shift)| mean + shift)
+            .collect::<Vec<_>>();
+
+        let slopes = mat_vec(&inverse, &self.sxy);
+        let intercept = (self.mean_y - dot(&slopes, &self.mean_x))
+            + (self.shift_y - dot(&slopes, &self.shift_x));
+
+        let n = self.n as f64;
+        let df = self
+            .n
+            .checked_sub(p)
+            .filter(|df| *df > 0)
+            .map(|df| df as f64);
+
+        let sse = (self.syy - dot(&slopes, &self.sxy)).max(0.0);
+        let sst = self.syy;
+
+        let (stderrs, adj_r2) = match df {
+            Some(df) => {
+                let sigma2 = sse / df;
+
+                // NOTE: the variance of the intercept is sigma2 * (1/n + m'S^-1m)
+                let intercept_variance = 1.0 / n + dot(&mean_x, &mat_vec(&inverse, &mean_x));
+
+                (
+                    std::iter::once(intercept_variance)
+                        .chain((0..k).map(|i| inverse[i * k + i]))
+                        .map(|variance| Some((sigma2 * variance).sqrt()))
+                        .collect(),
+                    (sst > 0.0).then(|| 1.0 - (sse / df) / (sst / (n - 1.0))),
+                )
+            }
+            None => (vec![None; p], None),
+        };
+
+        let mut coefficients = Vec::with_capacity(p);
+        coefficients.push(intercept);
+        coefficients.extend(slopes);
+
+        Some(Fit {
+            n: self.n,
+            logistic: false,
+            coefficients,
+            stderrs,
+            r2: (sst > 0.0).then(|| 1.0 - sse / sst),
+            adj_r2,
+            df,
+        })
+    }
+}
+
+// NOTE: logistic regression is fitted using iteratively reweighted least
+// squares, which requires multiple passes over the rows.
+#[derive(Debug, Clone)]
+struct LogisticModel {
+    p: usize,
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+}
+
+impl LogisticModel {
+    fn new(p: usize) -> Self {
+        Self {
+            p,
+            xs: Vec::new(),
+            ys: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, x: &[f64], y: f64) {
+        self.xs.extend_from_slice(x);
+        self.ys.push(y);
+    }
+
+    fn fit(&self) -> Option<Fit> {
+        let p = self.p;
+        let n = self.ys.len();
+
+        if n == 0 {
+            return None;
+        }
+
+        let mut coefficients = vec![0.0; p];
+        let mut inverse: Vec<f64> = vec![];
+
+        for _ in 0..LOGISTIC_MAX_ITERATIONS {
+            let mut hessian = vec![0.0; p * p];
+            let mut gradient = vec![0.0; p];
+
+            for (x, y) in self.xs.chunks_exact(p).zip(self.ys.iter()) {
+                let prob = sigmoid(dot(&coefficients, x));
+                let weight = prob * (1.0 - prob);
+
+                for i in 0..p {
+                    for j in 0..p {
+                        hessian[i * p + j] += weight * x[i] * x[j];
+                    }
+
+                    gradient[i] += (y - prob) * x[i];
+                }
+            }
+
+            inverse = invert_matrix(&hessian, p)?;
+
+            let step = mat_vec(&inverse, &gradient);
+            let mut max_delta: f64 = 0.0;
+
+            for (coefficient, delta) in coefficients.iter_mut().zip(step) {
+                *coefficient += delta;
+                max_delta = max_delta.max(delta.abs());
+            }
+
+            if !max_delta.is_finite() {
+                return None;
+            }
+
+            if max_delta < LOGISTIC_TOLERANCE {
+                break;
+            }
+        }
+
+        let mean_y = self.ys.iter().sum::<f64>() / n as f64;
+
+        let log_likelihood: f64 = self
+            .xs
+            .chunks_exact(p)
+            .zip(self.ys.iter())
+            .map(|(x, y)| {
+                let prob = sigmoid(dot(&coefficients, x)).clamp(f64::EPSILON, 1.0 - f64::EPSILON);
+                y * prob.ln() + (1.0 - y) * (1.0 - prob).ln()
+            })
+            .sum();
+
+        let null_log_likelihood = if mean_y > 0.0 && mean_y < 1.0 {
+            n as f64 * (mean_y * mean_y.ln() + (1.0 - mean_y) * (1.0 - mean_y).ln())
+        } else {
+            0.0
+        };
+
+        Some(Fit {
+            n,
+            logistic: true,
+            coefficients,
+            stderrs: (0..p).map(|i| Some(inverse[i * p + i].sqrt())).collect(),
+            r2: (null_log_likelihood < 0.0).then(|| 1.0 - log_likelihood / null_log_likelihood),
+            adj_r2: None,
+            df: n.checked_sub(p).map(|df| df as f64),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Model {
+    Linear(LinearModel),
+    Logistic(LogisticModel),
+}
+
+impl Model {
+    fn new(p: usize, logistic: bool) -> Self {
+        if logistic {
+            Self::Logistic(LogisticModel::new(p))
+        } else {
+            Self::Linear(LinearModel::new(p))
+        }
+    }
+
+    fn n(&self) -> usize {
+        match self {
+            Self::Linear(model) => model.n,
+            Self::Logistic(model) => model.ys.len(),
+        }
+    }
+
+    fn add(&mut self, x: &[f64], y: f64) {
+        match self {
+            Self::Linear(model) => model.add(x, y),
+            Self::Logistic(model) => model.add(x, y),
+        }
+    }
+
+    fn fit(&self) -> Option<Fit> {
+        match self {
+            Self::Linear(model) => model.fit(),
+            Self::Logistic(model) => model.fit(),
+        }
+    }
+}
+
+static USAGE: &str = "
+Fit a linear regression model using ordinary least squares, predicting the <y>
+column using the <x> column selection as independent variables. An intercept
+is always added to the model.
+
+This command only needs to stream sufficient statistics and can therefore
+work on arbitrarily large files, using memory proportional to the square of
+the number of independent variables.
+
+Use the -l/--logistic flag to fit a logistic regression model instead, in which
+case <y> must only contain 0 or 1. Note that logistic regression is fitted
+using iteratively reweighted least squares, which needs multiple passes over
+the data. It is therefore NOT streamed and will buffer the selected columns
+in memory, i.e. 8 bytes per selected cell (<y> included) for every row.
+
+Rows where any of the selected cells is empty will be ignored.
+
+By default, the command outputs one row per model term (the intercept then the
+independent variables) with the following columns:
+
+    term         - name of the term
+    coefficient  - estimated coefficient
+    stderr       - standard error of the coefficient
+    statistic    - t statistic (or Wald z statistic for logistic regression)
+    pvalue       - two-sided p-value of the statistic
+    n            - number of rows used to fit the model
+    r2           - coefficient of determination (replaced by McFadden's
+                   pseudo_r2 for logistic regression)
+    adj_r2       - adjusted coefficient of determination (only for
+                   linear regression)
+
+Use the -R/--residuals flag to output the input file instead, with additional
+\"prediction\" & \"residual\" columns. This requires reading the input twice and
+will therefore not work on stdin.
+
+Examples:
+
+Regress price on surface & number of rooms:
+
+    $ xan regress price surface,rooms houses.csv
+
+Same, per city:
+
+    $ xan regress -g city price surface,rooms houses.csv
+
+Logistic regression of survival:
+
+    $ xan regress -l survived age,fare titanic.csv
+
+Appending predictions & residuals:
+
+    $ xan regress -R price surface,rooms houses.csv
+
+Usage:
+    xan regress [options] <y> <x> [<input>]
+    xan regress --help
+
+regress options:
+    -l, --logistic        Fit a logistic regression model on a 0/1 target.
+    -g, --groupby <cols>  Fit one model per group defined by given column
+                          selection.
+    -R, --residuals       Output input rows with additional \"prediction\" &
+                          \"residual\" columns instead of model coefficients.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will not be interpreted
+                           as headers.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character.
+";
+
+#[derive(Deserialize, Debug)]
+struct Args {
+    arg_y: SelectedColumns,
+    arg_x: SelectedColumns,
+    arg_input: Option<String>,
+    flag_logistic: bool,
+    flag_groupby: Option<SelectedColumns>,
+    flag_residuals: bool,
+    flag_output: Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter: Option<Delimiter>,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let rconf = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers);
+
+    if args.flag_residuals && rconf.is_std() {
+        Err("-R/--residuals cannot work on stdin as it needs to read the input twice!")?;
+    }
+
+    let mut reader = rconf.simd_reader()?;
+    let headers = reader.byte_headers()?.clone();
+
+    let y_index = args.arg_y.single_selection(&headers, !rconf.no_headers)?;
+    let x_sel = args.arg_x.selection(&headers, !rconf.no_headers)?;
+
+    let groupby_sel_opt = args
+        .flag_groupby
+        .as_ref()
+        .map(|cols| cols.selection(&headers, !rconf.no_headers))
+        .transpose()?;
+
+    let p = x_sel.len() + 1;
+
+    // NOTE: returns the row's design vector, with a leading 1 for the intercept,
+    // and target, or None if some cell is empty.
+    let parse_row = |record: &ByteRecord, x: &mut Vec<f64>| -> CliResult<Option<f64>> {
+        x.clear();
+        x.push(1.0);
+
+        for cell in x_sel.select(record) {
+            match parse_float(cell)? {
+                None => return Ok(None),
+                Some(value) => x.push(value),
+            }
+        }
+
+        let y = match parse_float(&record[y_index])? {
+            None => return Ok(None),
+            Some(y) => y,
+        };
+
+        if args.flag_logistic && y != 0.0 && y != 1.0 {
+            Err(format!(
+                "-l/--logistic expects <y> to only contain 0 or 1 but found \"{}\"!",
+                String::from_utf8_lossy(&record[y_index])
+            ))?;
+        }
+
+        Ok(Some(y))
+    };
+
+    let get_group = |record: &ByteRecord| -> Vec<Vec<u8>> {
+        match &groupby_sel_opt {
+            Some(sel) => sel.collect(record),
+            None => vec![],
+        }
+    };
+
+    let mut models: ClusteredInsertHashmap<Vec<Vec<u8>>, Model> = ClusteredInsertHashmap::new();
+
+    let mut record = ByteRecord::new();
+    let mut x: Vec<f64> = Vec::with_capacity(p);
+
+    while reader.read_byte_record(&mut record)? {
+        let model = models.insert_with(get_group(&record), || Model::new(p, args.flag_logistic));
+
+        if let Some(y) = parse_row(&record, &mut x)? {
+            model.add(&x, y);
+        }
+    }
+
+    let mut writer = Config::new(&args.flag_output).simd_writer()?;
+    let mut output_record = ByteRecord::new();
+
+    if args.flag_residuals {
+        let fits: ClusteredInsertHashmap<Vec<Vec<u8>>, Option<Fit>> = {
+            let mut fits = ClusteredInsertHashmap::new();
+
+            for (group, model) in models.into_iter() {
+                fits.insert_with(group, || model.fit());
+            }
+
+            fits
+        };
+
+        let mut reader = rconf.simd_reader()?;
+
+        if !rconf.no_headers {
+            output_record.extend(headers.iter());
+            output_record.push_field(b"prediction");
+            output_record.push_field(b"residual");
+
+            writer.write_byte_record(&output_record)?;
+        }
+
+        while reader.read_byte_record(&mut record)? {
+            output_record.clear();
+            output_record.extend(record.iter());
+
+            let fit_opt = fits.get(&get_group(&record)).and_then(|fit| fit.as_ref());
+
+            match (fit_opt, parse_row(&record, &mut x)?) {
+                (Some(fit), Some(y)) => {
+                    let prediction = fit.predict(&x);
+
+                    output_record.push_field(prediction.to_string().as_bytes());
+                    output_record.push_field((y - prediction).to_string().as_bytes());
+                }
+                _ => {
+                    output_record.push_field(b"");
+                    output_record.push_field(b"");
+                }
+            }
+
+            writer.write_byte_record(&output_record)?;
+        }
+
+        return Ok(writer.flush()?);
+    }
+
+    if let Some(sel) = &groupby_sel_opt {
+        if rconf.no_headers {
+            for i in 0..sel.len() {
+                output_record.push_field(format!("group_{}", i).as_bytes());
+            }
+        } else {
+            output_record.extend(sel.select(&headers));
+        }
+    }
+
+    output_record.extend([
+        b"term".as_slice(),
+        b"coefficient",
+        b"stderr",
+        b"statistic",
+        b"pvalue",
+        b"n",
+    ]);
+
+    if args.flag_logistic {
+        output_record.push_field(b"pseudo_r2");
+    } else {
+        output_record.push_field(b"r2");
+        output_record.push_field(b"adj_r2");
+    }
+
+    writer.write_byte_record(&output_record)?;
+
+    let terms = std::iter::once(b"intercept".to_vec())
+        .chain(if rconf.no_headers {
+            x_sel
+                .iter()
+                .map(|i| i.to_string().into_bytes())
+                .collect::<Vec<_>>()
+        } else {
+            x_sel.select(&headers).map(|name| name.to_vec()).collect()
+        })
+        .collect::<Vec<_>>();
+
+    let format_float = |value: Option<f64>| -> Vec<u8> {
+        value
+            .map(|f| f.to_string().into_bytes())
+            .unwrap_or_default()
+    };
+
+    for (group, model) in models.into_iter() {
+        let fit_opt = model.fit();
+
+        for (i, term) in terms.iter().enumerate() {
+            output_record.clear();
+
+            for cell in group.iter() {
+                output_record.push_field(cell);
+            }
+
+            output_record.push_field(term);
+
+            match &fit_opt {
+                None => {
+                    for _ in 0..4 {
+                        output_record.push_field(b"");
+                    }
+
+                    output_record.push_field(model.n().to_string().as_bytes());
+
+                    if args.flag_logistic {
+                        output_record.push_field(b"");
+                    } else {
+                        output_record.push_field(b"");
+                        output_record.push_field(b"");
+                    }
+                }
+                Some(fit) => {
+                    output_record.push_field(fit.coefficients[i].to_string().as_bytes());
+                    output_record.push_field(&format_float(fit.stderrs[i]));
+                    output_record.push_field(&format_float(fit.statistic(i)));
+                    output_record.push_field(&format_float(fit.pvalue(i)));
+                    output_record.push_field(fit.n.to_string().as_bytes());
+                    output_record.push_field(&format_float(fit.r2));
+
+                    if !args.flag_logistic {
+                        output_record.push_field(&format_float(fit.adj_r2));
+                    }
+                }
+            }
+
+            writer.write_byte_record(&output_record)?;
+        }
+    }
+
+    Ok(writer.flush()?)
+}
@@ -0,0 +1,614 @@
+use simd_csv::ByteRecord;
+
+use crate::collections::{
+    ClusteredInsertHashmap, IndexMap, IndexSet, new_index_map, new_index_set,
+};
+use crate::config::{Config, Delimiter};
+use crate::moonblade::agg::{CovarianceWelford, Welford};
+use crate::select::SelectedColumns;
+use crate::statistics::{
+    average_ranks, chi2_sf, kolmogorov_sf, normal_two_sided_pvalue, spearman_correlation,
+    student_t_two_sided_pvalue,
+};
+use crate::util;
+use crate::{CliError, CliResult};
+
+fn parse_float(cell: &[u8]) -> CliResult<Option<f64>> {
+    if cell.is_empty() {
+        return Ok(None);
+    }
+
+    let value = fast_float::parse::<f64, &[u8]>(cell).map_err(|_| {
+        CliError::Other(format!(
+            "could not parse cell \"{}\" as a float!",
+            String::from_utf8_lossy(cell)
+        ))
+    })?;
+
+    if value.is_nan() {
+        return Err(CliError::Other(format!(
+            "found NaN cell \"{}\"!",
+            String::from_utf8_lossy(cell)
+        )));
+    }
+
+    Ok(Some(value))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TestKind {
+    Student,
+    Welch,
+    Paired,
+    MannWhitney,
+    KolmogorovSmirnov,
+    ChiSquare,
+    Pearson,
+    Spearman,
+}
+
+impl TestKind {
+    fn headers(&self) -> &'static [&'static str] {
+        match self {
+            Self::Student | Self::Welch | Self::Paired => &[
+                "n_x",
+                "n_y",
+                "mean_x",
+                "mean_y",
+                "statistic",
+                "df",
+                "pvalue",
+            ],
+            Self::MannWhitney | Self::KolmogorovSmirnov => &["n_x", "n_y", "statistic", "pvalue"],
+            Self::ChiSquare => &["n", "statistic", "df", "pvalue", "cramers_v"],
+            Self::Pearson | Self::Spearman => &["n", "coefficient", "statistic", "df", "pvalue"],
+        }
+    }
+
+    fn is_two_samples(&self) -> bool {
+        matches!(
+            self,
+            Self::Student | Self::Welch | Self::MannWhitney | Self::KolmogorovSmirnov
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Accumulator {
+    Moments(Welford, Welford),
+    PairedMoments(Welford, Welford, Welford),
+    Samples(Vec<f64>, Vec<f64>),
+    Contingency(IndexMap<(Vec<u8>, Vec<u8>), u64>),
+    Covariance(CovarianceWelford),
+}
+
+impl Accumulator {
+    fn new(kind: TestKind) -> Self {
+        match kind {
+            TestKind::Student | TestKind::Welch => Self::Moments(Welford::new(), Welford::new()),
+            TestKind::Paired => Self::PairedMoments(Welford::new(), Welford::new(), Welford::new()),
+            TestKind::MannWhitney | TestKind::KolmogorovSmirnov | TestKind::Spearman => {
+                Self::Samples(Vec::new(), Vec::new())
+            }
+            TestKind::ChiSquare => Self::Contingency(new_index_map()),
+            TestKind::Pearson => Self::Covariance(CovarianceWelford::new()),
+        }
+    }
+
+    fn add_to_sample(&mut self, first: bool, value: f64) {
+        match self {
+            Self::Moments(x, y) => {
+                if first {
+                    x.add(value);
+                } else {
+                    y.add(value);
+                }
+            }
+            Self::Samples(x, y) => {
+                if first {
+                    x.push(value);
+                } else {
+                    y.push(value);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn add_pair(&mut self, x: f64, y: f64) {
+        match self {
+            Self::PairedMoments(xs, ys, diffs) => {
+                xs.add(x);
+                ys.add(y);
+                diffs.add(x - y);
+            }
+            Self::Samples(xs, ys) => {
+                xs.push(x);
+                ys.push(y);
+            }
+            Self::Covariance(welford) => {
+                welford.add(x, y);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn add_categories(&mut self, x: &[u8], y: &[u8]) {
+        match self {
+            Self::Contingency(table) => {
+                table
+                    .entry((x.to_vec(), y.to_vec()))
+                    .and_modify(|count| *count += 1)
+                    .or_insert(1);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn results(&self, kind: TestKind) -> Vec<Option<f64>> {
+        match (kind, self) {
+            (TestKind::Student | TestKind::Welch, Self::Moments(x, y)) => {
+                let (n1, n2) = (x.count() as f64, y.count() as f64);
+
+                let (statistic, df) =
+                    match (x.mean(), y.mean(), x.sample_variance(), y.sample_variance()) {
+                        (Some(m1), Some(m2), Some(v1), Some(v2)) => {
+                            if kind == TestKind::Welch {
+                                let (a, b) = (v1 / n1, v2 / n2);
+                                let df = (a + b).powi(2)
+                                    / (a.powi(2) / (n1 - 1.0) + b.powi(2) / (n2 - 1.0));
+
+                                (Some((m1 - m2) / (a + b).sqrt()), Some(df))
+                            } else {
+                                let df = n1 + n2 - 2.0;
+                                let pooled = ((n1 - 1.0) * v1 + (n2 - 1.0) * v2) / df;
+
+                                (
+                                    Some((m1 - m2) / (pooled * (1.0 / n1 + 1.0 / n2)).sqrt()),
+                                    Some(df),
+                                )
+                            }
+                        }
+                        _ => (None, None),
+                    };
+
+                let pvalue = statistic
+                    .zip(df)
+                    .map(|(t, df)| student_t_two_sided_pvalue(t, df));
+
+                vec![
+                    Some(n1),
+                    Some(n2),
+                    x.mean(),
+                    y.mean(),
+                    statistic,
+                    df,
+                    pvalue,
+                ]
+            }
+            (TestKind::Paired, Self::PairedMoments(x, y, diffs)) => {
+                let n = diffs.count() as f64;
+                let df = n - 1.0;
+
+                let statistic = diffs
+                    .mean()
+                    .zip(diffs.sample_stddev())
+                    .map(|(mean, stddev)| mean / (stddev / n.sqrt()));
+
+                let pvalue = statistic.map(|t| student_t_two_sided_pvalue(t, df));
+
+                vec![
+                    Some(n),
+                    Some(n),
+                    x.mean(),
+                    y.mean(),
+                    statistic,
+                    statistic.map(|_| df),
+                    pvalue,
+                ]
+            }
+            (TestKind::MannWhitney, Self::Samples(x, y)) => {
+                let (n1, n2) = (x.len() as f64, y.len() as f64);
+
+                if x.is_empty() || y.is_empty() {
+                    return vec![Some(n1), Some(n2), None, None];
+                }
+
+                let values = x.iter().chain(y.iter()).copied().collect::<Vec<_>>();
+                let (ranks, ties_correction) = average_ranks(&values);
+
+                let r1: f64 = ranks[..x.len()].iter().sum();
+                let u1 = r1 - n1 * (n1 + 1.0) / 2.0;
+                let u2 = n1 * n2 - u1;
+
+                // NOTE: normal approximation, with tie & continuity corrections
+                let n = n1 + n2;
+                let mu = n1 * n2 / 2.0;
+                let sigma =
+                    (n1 * n2 / 12.0 * ((n + 1.0) - ties_correction / (n * (n - 1.0)))).sqrt();
+
+                let pvalue = if sigma == 0.0 {
+                    1.0
+                } else {
+                    let z = (u1.max(u2) - mu - 0.5) / sigma;
+                    normal_two_sided_pvalue(z)
+                };
+
+                vec![Some(n1), Some(n2), Some(u1), Some(pvalue)]
+            }
+            (TestKind::KolmogorovSmirnov, Self::Samples(x, y)) => {
+                let (n1, n2) = (x.len() as f64, y.len() as f64);
+
+                if x.is_empty() || y.is_empty() {
+                    return vec![Some(n1), Some(n2), None, None];
+                }
+
+                let mut x = x.clone();
+                let mut y = y.clone();
+
+                x.sort_by(|a, b| a.total_cmp(b));
+                y.sort_by(|a, b| a.total_cmp(b));
+
+                let (mut i, mut j) = (0, 0);
+                let mut d: f64 = 0.0;
+
+                while i < x.len() && j < y.len() {
+                    let value = x[i].min(y[j]);
+
+                    while i < x.len() && x[i] == value {
+                        i += 1;
+                    }
+
+                    while j < y.len() && y[j] == value {
+                        j += 1;
+                    }
+
+                    d = d.max((i as f64 / n1 - j as f64 / n2).abs());
+                }
+
+                let en = (n1 * n2 / (n1 + n2)).sqrt();
+                let pvalue = kolmogorov_sf((en + 0.12 + 0.11 / en) * d);
+
+                vec![Some(n1), Some(n2), Some(d), Some(pvalue)]
+            }
+            (TestKind::ChiSquare, Self::Contingency(table)) => {
+                let mut x_totals: IndexMap<&[u8], u64> = new_index_map();
+                let mut y_totals: IndexMap<&[u8], u64> = new_index_map();
+                let mut n: u64 = 0;
+
+                for ((x, y), count) in table.iter() {
+                    *x_totals.entry(x).or_default() += count;
+                    *y_totals.entry(y).or_default() += count;
+                    n += count;
+                }
+
+                let (r, c) = (x_totals.len(), y_totals.len());
+
+                if r < 2 || c < 2 {
+                    return vec![Some(n as f64), None, None, None, None];
+                }
+
+                let nf = n as f64;
+                let mut statistic = 0.0;
+
+                for (x, x_total) in x_totals.iter() {
+                    for (y, y_total) in y_totals.iter() {
+                        let expected = (*x_total * *y_total) as f64 / nf;
+                        let observed =
+                            table.get(&(x.to_vec(), y.to_vec())).copied().unwrap_or(0) as f64;
+
+                        statistic += (observed - expected).powi(2) / expected;
+                    }
+                }
+
+                let df = ((r - 1) * (c - 1)) as f64;
+                let cramers_v = (statistic / (nf * (r.min(c) - 1) as f64)).sqrt();
+
+                vec![
+                    Some(nf),
+                    Some(statistic),
+                    Some(df),
+                    Some(chi2_sf(statistic, df)),
+                    Some(cramers_v),
+                ]
+            }
+            (TestKind::Pearson | TestKind::Spearman, _) => {
+                let (n, coefficient) = match self {
+                    Self::Covariance(welford) => (welford.count() as f64, welford.correlation()),
+                    Self::Samples(x, y) => (x.len() as f64, spearman_correlation(x, y)),
+                    _ => unreachable!(),
+                };
+
+                let coefficient = coefficient.filter(|r| r.is_finite());
+                let df = n - 2.0;
+
+                if df < 1.0 || coefficient.is_none() {
+                    return vec![Some(n), coefficient, None, None, None];
+                }
+
+                let r = coefficient.unwrap();
+                let statistic = r * (df / (1.0 - r * r)).sqrt();
+
+                vec![
+                    Some(n),
+                    Some(r),
+                    Some(statistic),
+                    Some(df),
+                    Some(student_t_two_sided_pvalue(statistic, df)),
+                ]
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+static USAGE: &str = "
+Run statistical hypothesis tests over CSV data.
+
+Supported tests:
+    t     - Student's t-test comparing the means of two samples. Use -W/--welch
+            to avoid assuming equal variances, or -P/--paired to run a paired
+            t-test over values found on the same rows.
+    mw    - Mann-Whitney U test (a.k.a. Wilcoxon rank-sum test) comparing the
+            distributions of two samples, using a normal approximation with
+            tie & continuity corrections. Reported statistic is U for <x>.
+    ks    - two-sample Kolmogorov-Smirnov test comparing the distributions of
+            two samples, using the asymptotic distribution.
+    chi2  - chi-square test of independence of two categorical columns
+            (without Yates' continuity correction).
+    corr  - Pearson correlation test of two numerical columns, or Spearman's
+            rank correlation test when using -S/--spearman.
+
+By default, the two samples compared by the t, mw & ks tests are the non-empty
+values of the <x> & <y> columns respectively. Use -c/--by-category if <x>
+contains the values and <y> a categorical column with exactly two distinct
+values splitting them into two samples instead, e.g.:
+
+    $ xan test t -c score treatment data.csv
+
+The chi2 & corr tests (and the paired t-test) always consider pairs of values
+found on the same row, ignoring rows where any of the cells is empty.
+
+Note that all reported p-values are two-sided, and that the mw, ks & corr -S
+tests need to store the samples in memory.
+
+Tests can also be run per group using the -g/--groupby flag, in which case
+the output will contain one row per group, starting with the group columns.
+
+The output will always contain a \"x\" & \"y\" column containing the name of the
+tested columns, or the categories when using -c/--by-category, followed by the
+following columns, depending on the test:
+
+    t     - n_x, n_y, mean_x, mean_y, statistic, df, pvalue
+    mw    - n_x, n_y, statistic, pvalue
+    ks    - n_x, n_y, statistic, pvalue
+    chi2  - n, statistic, df, pvalue, cramers_v
+    corr  - n, coefficient, statistic, df, pvalue
+
+Usage:
+    xan test t [options] <x> <y> [<input>]
+    xan test mw [options] <x> <y> [<input>]
+    xan test ks [options] <x> <y> [<input>]
+    xan test chi2 [options] <x> <y> [<input>]
+    xan test corr [options] <x> <y> [<input>]
+    xan test --help
+
+test t options:
+    -W, --welch   Run Welch's t-test, i.e. do not assume equal variances.
+    -P, --paired  Run a paired t-test.
+
+test t, mw & ks options:
+    -c, --by-category  Indicates that <x> contains the values and <y> a column
+                       with exactly two categories splitting them into
+                       two samples, ordered by first appearance.
+
+test corr options:
+    -S, --spearman  Test Spearman's rank correlation instead of Pearson's.
+
+Common options:
+    -h, --help             Display this message
+    -g, --groupby <cols>   Run the test per group defined by given column
+                           selection.
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will not be interpreted
+                           as headers.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character.
+";
+
+#[derive(Deserialize, Debug)]
+struct Args {
+    cmd_t: bool,
+    cmd_mw: bool,
+    cmd_ks: bool,
+    cmd_chi2: bool,
+    cmd_corr: bool,
+    arg_x: SelectedColumns,
+    arg_y: SelectedColumns,
+    arg_input: Option<String>,
+    flag_welch: bool,
+    flag_paired: bool,
+    flag_by_category: bool,
+    flag_spearman: bool,
+    flag_groupby: Option<SelectedColumns>,
+    flag_output: Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter: Option<Delimiter>,
+}
+
+impl Args {
+    fn kind(&self) -> CliResult<TestKind> {
+        if !self.cmd_t && (self.flag_welch || self.flag_paired) {
+            Err("-W/--welch & -P/--paired only make sense with `t` test!")?;
+        }
+
+        if !self.cmd_corr && self.flag_spearman {
+            Err("-S/--spearman only makes sense with `corr` test!")?;
+        }
+
+        if self.flag_welch && self.flag_paired {
+            Err("-W/--welch & -P/--paired are mutually exclusive!")?;
+        }
+
+        if self.flag_by_category && (self.cmd_chi2 || self.cmd_corr || self.flag_paired) {
+            Err("-c/--by-category does not make sense with paired tests!")?;
+        }
+
+        Ok(if self.cmd_t {
+            if self.flag_welch {
+                TestKind::Welch
+            } else if self.flag_paired {
+                TestKind::Paired
+            } else {
+                TestKind::Student
+            }
+        } else if self.cmd_mw {
+            TestKind::MannWhitney
+        } else if self.cmd_ks {
+            TestKind::KolmogorovSmirnov
+        } else if self.cmd_chi2 {
+            TestKind::ChiSquare
+        } else if self.flag_spearman {
+            TestKind::Spearman
+        } else {
+            TestKind::Pearson
+        })
+    }
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    let kind = args.kind()?;
+
+    let rconf = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers);
+
+    let mut reader = rconf.simd_reader()?;
+    let headers = reader.byte_headers()?.clone();
+
+    let x_index = args.arg_x.single_selection(&headers, !rconf.no_headers)?;
+    let y_index = args.arg_y.single_selection(&headers, !rconf.no_headers)?;
+
+    let groupby_sel_opt = args
+        .flag_groupby
+        .as_ref()
+        .map(|cols| cols.selection(&headers, !rconf.no_headers))
+        .transpose()?;
+
+    let mut categories: IndexSet<Vec<u8>> = new_index_set();
+    let mut groups: ClusteredInsertHashmap<Vec<Vec<u8>>, Accumulator> =
+        ClusteredInsertHashmap::new();
+
+    let mut record = ByteRecord::new();
+
+    while reader.read_byte_record(&mut record)? {
+        let group = match &groupby_sel_opt {
+            Some(sel) => sel.collect(&record),
+            None => vec![],
+        };
+
+        let accumulator = groups.insert_with(group, || Accumulator::new(kind));
+
+        let x_cell = &record[x_index];
+        let y_cell = &record[y_index];
+
+        if args.flag_by_category {
+            if y_cell.is_empty() {
+                continue;
+            }
+
+            if let Some(value) = parse_float(x_cell)? {
+                let (index, _) = categories.insert_full(y_cell.to_vec());
+
+                if index > 1 {
+                    Err(format!(
+                        "-c/--by-category expects <y> to contain exactly two categories but found a third one: \"{}\"!",
+                        String::from_utf8_lossy(y_cell)
+                    ))?;
+                }
+
+                accumulator.add_to_sample(index == 0, value);
+            }
+        } else if kind.is_two_samples() {
+            if let Some(value) = parse_float(x_cell)? {
+                accumulator.add_to_sample(true, value);
+            }
+
+            if let Some(value) = parse_float(y_cell)? {
+                accumulator.add_to_sample(false, value);
+            }
+        } else if kind == TestKind::ChiSquare {
+            if !x_cell.is_empty() && !y_cell.is_empty() {
+                accumulator.add_categories(x_cell, y_cell);
+            }
+        } else if let (Some(x), Some(y)) = (parse_float(x_cell)?, parse_float(y_cell)?) {
+            accumulator.add_pair(x, y);
+        }
+    }
+
+    let (x_label, y_label) = if args.flag_by_category {
+        if categories.len() < 2 {
+            Err("-c/--by-category expects <y> to contain exactly two categories!")?;
+        }
+
+        (categories[0].clone(), categories[1].clone())
+    } else if rconf.no_headers {
+        (
+            x_index.to_string().into_bytes(),
+            y_index.to_string().into_bytes(),
+        )
+    } else {
+        (headers[x_index].to_vec(), headers[y_index].to_vec())
+    };
+
+    let mut writer = Config::new(&args.flag_output).simd_writer()?;
+    let mut output_record = ByteRecord::new();
+
+    if let Some(sel) = &groupby_sel_opt {
+        if rconf.no_headers {
+            for i in 0..sel.len() {
+                output_record.push_field(format!("group_{}", i).as_bytes());
+            }
+        } else {
+            output_record.extend(sel.select(&headers));
+        }
+    }
+
+    output_record.push_field(b"x");
+    output_record.push_field(b"y");
+
+    for name in kind.headers() {
+        output_record.push_field(name.as_bytes());
+    }
+
+    writer.write_byte_record(&output_record)?;
+
+    for (group, accumulator) in groups.into_iter() {
+        output_record.clear();
+
+        for cell in group {
+            output_record.push_field(&cell);
+        }
+
+        output_record.push_field(&x_label);
+        output_record.push_field(&y_label);
+
+        for (name, value) in kind.headers().iter().zip(accumulator.results(kind)) {
+            match value {
+                None => output_record.push_field(b""),
+                Some(f) => {
+                    if *name == "n" || name.starts_with("n_") {
+                        output_record.push_field((f as usize).to_string().as_bytes());
+                    } else {
+                        output_record.push_field(f.to_string().as_bytes());
+                    }
+                }
+            }
+        }
+
+        writer.write_byte_record(&output_record)?;
+    }
+
+    Ok(writer.flush()?)
+}
@@ -13,15 +13,38 @@ use simd_csv::ByteRecord;
 use crate::CliResult;
 use crate::collections::{HashMap, HashSet};
 use crate::config::{Config, Delimiter};
+use crate::langdetect;
 use crate::moonblade::{GlobalVariables, Program};
 use crate::select::SelectedColumns;
+use crate::snowball::Language;
+use crate::stopwords::Stoplist;
 use crate::util;
 
-fn get_stemmer(name: &str) -> Result<fn(&str) -> Cow<str>, String> {
+#[derive(Clone, Copy)]
+enum Stemmer {
+    Carry,
+    S,
+    Snowball(Language),
+}
+
+impl Stemmer {
+    fn stem<'a>(&self, token: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::Carry => Cow::Owned(carry_stemmer(token)),
+            Self::S => s_stemmer(token),
+            Self::Snowball(language) => Cow::Owned(language.stem(token)),
+        }
+    }
+}
+
+fn get_stemmer(name: &str) -> Result<Stemmer, String> {
     Ok(match name {
-        "carry" => |n: &str| Cow::Owned(carry_stemmer(n)),
-        "s" => s_stemmer,
-        _ => return Err(format!("unknown stemmer \"{}\"", name)),
+        "carry" => Stemmer::Carry,
+        "s" => Stemmer::S,
+        _ => match name.strip_prefix("snowball:") {
+            Some(language) => Stemmer::Snowball(Language::try_from(language)?),
+            None => return Err(format!("unknown stemmer \"{}\"", name)),
+        },
     })
 }
 
@@ -49,9 +72,64 @@ enum TokenWhitelist {
     WithoutId(HashSet<String>),
 }
 
+// NOTE: phrases are stored joined by the separator used to merge them
+#[derive(Clone)]
+struct Phrases {
+    sep: String,
+    phrases: HashSet<String>,
+    max_len: usize,
+}
+
+impl Phrases {
+    fn new(sep: &str) -> Self {
+        Self {
+            sep: sep.to_string(),
+            phrases: HashSet::new(),
+            max_len: 0,
+        }
+    }
+
+    fn add(&mut self, phrase: &str) {
+        let tokens = phrase.split_whitespace().collect::<Vec<_>>();
+
+        if tokens.len() < 2 {
+            return;
+        }
+
+        self.max_len = self.max_len.max(tokens.len());
+        self.phrases.insert(tokens.join(&self.sep));
+    }
+
+    fn merge(&self, tokens: Vec<(String, WordTokenKind)>) -> Vec<(String, WordTokenKind)> {
+        let mut merged = Vec::with_capacity(tokens.len());
+        let mut i: usize = 0;
+
+        'outer: while i < tokens.len() {
+            for l in (2..=self.max_len.min(tokens.len() - i)).rev() {
+                let candidate = tokens[i..i + l]
+                    .iter()
+                    .map(|token| token.0.as_str())
+                    .collect::<Vec<_>>()
+                    .join(&self.sep);
+
+                if self.phrases.contains(&candidate) {
+                    merged.push((candidate, WordTokenKind::Word));
+                    i += l;
+                    continue 'outer;
+                }
+            }
+
+            merged.push(tokens[i].clone());
+            i += 1;
+        }
+
+        merged
+    }
+}
+
 static USAGE: &str = "
 Tokenize the given text column by splitting it either into words, sentences
-or paragraphs.
+or paragraphs. The command is also able to detect the language of the text.
 
 # tokenize words
 
@@ -101,10 +179,30 @@ sentence with a new \"sentence\" column at the end.
 Tokenize the given text by splitting it into paragraphs, emitting one row per
 paragraph, with a new \"paragraph\" column at the end.
 
+# tokenize lang
+
+Detect the language of the given text column, using an embedded model that
+does not require network access, and add a \"lang\" column containing its
+ISO 639-1 code (e.g. \"en\" or \"fr\"), along with a \"lang_confidence\" column
+containing a score between 0 and 1. The \"lang\" column will be empty if the
+language could not be detected.
+
+Since the confidence is low for short or ambiguous texts, you might want to
+discard unreliable detections using --min-confidence.
+
+Supported languages are: af, ak, am, ar, az, be, bg, bn, ca, cs, da, de, el, en,
+eo, es, et, fa, fi, fr, gu, he, hi, hr, hu, hy, id, it, ja, jv, ka, km, kn, ko,
+la, lt, lv, mk, ml, mr, my, nb, ne, nl, or, pa, pl, pt, ro, ru, si, sk, sl, sn,
+sr, sv, ta, te, th, tk, tl, tr, uk, ur, uz, vi, yi, zh & zu.
+
+This is typically useful to route rows to a per-language processing:
+    $ xan tokenize lang text file.csv | xan partition lang
+
 ---
 
 Note that the command will always drop the text column from the
-output unless you pass --keep-text to the command.
+output unless you pass --keep-text to the command (except for the
+\"lang\" subcommand, which always keeps it).
 
 Tips:
 
@@ -118,11 +216,13 @@ Usage:
     xan tokenize words [options] <column> [<input>]
     xan tokenize sentences [options] <column> [<input>]
     xan tokenize paragraphs [options] <column> [<input>]
+    xan tokenize lang [options] <column> [<input>]
     xan tokenize --help
 
 tokenize options:
     -c, --column <name>      Name for the token column. Will default to \"tokens\", \"token\"
-                             when -T/--token-type is provided, \"paragraphs\" or \"sentences\".
+                             when -T/--token-type is provided, \"paragraphs\", \"sentences\"
+                             or \"lang\".
     -p, --parallel           Whether to use parallelization to speed up computations.
                              Will automatically select a suitable number of threads to use
                              based on your number of cores. Use -t, --threads if you want to
@@ -147,7 +247,11 @@ tokenize words options:
                              See the list of recognized types above.
     -m, --min-token <n>      Minimum characters count of a token to be included in the output.
     -M, --max-token <n>      Maximum characters count of a token to be included in the output.
-    --stoplist <path>        Path to a .txt stoplist containing one word per line.
+    --stoplist <spec>        Stoplist of words to drop. Can be a path to a .txt file containing
+                             one word per line, or \"builtin:\" followed by comma-separated
+                             language codes, e.g. \"builtin:fr,en\". Several stoplists can be
+                             combined using \"+\", e.g. \"builtin:fr+custom.txt\".
+                             Run \"xan help stopwords\" to list available builtin languages.
     -J, --filter-junk        Whether to apply some heuristics to filter out words that look like junk.
     -L, --lower              Whether to normalize token case using lower case.
     -U, --unidecode          Whether to normalize token text to ascii.
@@ -156,15 +260,28 @@ tokenize words options:
                                 - \"s\": a basic stemmer removing typical plural inflections in
                                          most European languages.
                                 - \"carry\": a stemmer targeting the French language.
+                                - \"snowball:<lang>\": a Snowball stemmer for given language,
+                                         e.g. \"snowball:en\". Supported languages are
+                                         \"nl\" (dutch), \"en\" (english), \"fr\" (french),
+                                         \"de\" (german), \"it\" (italian),
+                                         \"pt\" (portuguese), \"es\" (spanish)
+                                         & \"sv\" (swedish).
     -V, --vocab <name>       Path to a CSV file containing allowed vocabulary (or \"-\" for stdin).
     --vocab-token <col>      Column of vocabulary file containing allowed tokens.
                              [default: token]
     --vocab-token-id <col>   Column of vocabulary file containing a token id to emit in place of the
                              token itself.
+    --phrases <path>         Path to a CSV file containing phrases, i.e. sequences of tokens separated
+                             by a space, that will be merged into single tokens, joined using the
+                             separator given to --ngrams-sep. Phrases are matched greedily, longest
+                             first, against the normalized tokens. Such a file is typically produced
+                             by the \"xan vocab collocations\" command.
+    --phrases-column <col>   Column of phrases file containing the phrases.
+                             [default: collocation]
     --sep <delim>            Character used to join tokens in the output cells. Will default
                              to a space.
-    --ngrams-sep <delim>     Separator to be use to join ngrams tokens.
-                             [default: §]
+    --ngrams-sep <delim>     Separator to be use to join ngrams tokens, or the tokens
+                             of phrases given to --phrases. [default: §]
     -u, --uniq               Sort and deduplicate the tokens.
     -F, --flatmap <expr>     Evaluate an expression for each extracted token and return nothing,
                              or a transformed token or a list of tokens. The evaluated expression
@@ -180,6 +297,12 @@ tokenize paragraphs options:
 tokenize sentences options:
     --squeeze  Collapse consecutive whitespace to produce a tidy output.
 
+tokenize lang options:
+    --langs <codes>           Restrict detection to the given languages, separated
+                              by comma, e.g. \"en,fr,de\".
+    --min-confidence <score>  Leave the \"lang\" column empty when the confidence of
+                              the detection is lower than given score.
+
 Common options:
     -h, --help             Display this message
     -o, --output <file>    Write output to <file> instead of stdout.
@@ -196,6 +319,7 @@ struct Args {
     cmd_words: bool,
     cmd_sentences: bool,
     cmd_paragraphs: bool,
+    cmd_lang: bool,
     flag_column: Option<String>,
     flag_token_type: Option<String>,
     flag_output: Option<String>,
@@ -221,10 +345,14 @@ struct Args {
     flag_vocab: Option<String>,
     flag_vocab_token: SelectedColumns,
     flag_vocab_token_id: Option<SelectedColumns>,
+    flag_phrases: Option<String>,
+    flag_phrases_column: SelectedColumns,
     flag_uniq: bool,
     flag_flatmap: Option<String>,
     flag_aerated: bool,
     flag_squeeze: bool,
+    flag_langs: Option<String>,
+    flag_min_confidence: Option<f64>,
 }
 
 impl Args {
@@ -233,13 +361,19 @@ impl Args {
     }
 
     fn validate(&self) -> Result<(), &str> {
-        if self.cmd_sentences || self.cmd_paragraphs {
+        if self.cmd_sentences || self.cmd_paragraphs || self.cmd_lang {
             if self.flag_ngrams.is_some() {
-                return Err("--ngrams cannot work with paragraphs nor sentences!");
+                return Err("--ngrams cannot work with paragraphs, sentences nor lang!");
             }
 
             if self.flag_token_type.is_some() {
-                return Err("-T,--token-type cannot work with paragraphs nor sentences!");
+                return Err("-T,--token-type cannot work with paragraphs, sentences nor lang!");
+            }
+        }
+
+        if let Some(langs) = &self.flag_langs {
+            if langs.split(',').any(|lang| !langdetect::is_supported(lang)) {
+                return Err("--langs contains unsupported languages!");
             }
         }
 
@@ -305,6 +439,8 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                 "paragraph"
             } else if args.cmd_sentences {
                 "sentence"
+            } else if args.cmd_lang {
+                "lang"
             } else {
                 "tokens"
             }
@@ -312,7 +448,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     };
 
     if !rconfig.no_headers {
-        if !args.flag_keep_text {
+        if !args.flag_keep_text && !args.cmd_lang {
             headers = headers
                 .iter()
                 .enumerate()
@@ -322,6 +458,10 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
         headers.push_field(token_column_name.as_bytes());
 
+        if args.cmd_lang {
+            headers.push_field(format!("{}_confidence", token_column_name).as_bytes());
+        }
+
         if let Some(name) = &args.flag_token_type {
             headers.push_field(name.as_bytes());
         }
@@ -357,14 +497,8 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         tokenizer_builder = tokenizer_builder.max_token_char_count(max);
     }
 
-    if let Some(path) = args.flag_stoplist {
-        let mut contents = String::new();
-
-        Config::new(&Some(path))
-            .io_reader()?
-            .read_to_string(&mut contents)?;
-
-        for word in contents.lines() {
+    if let Some(spec) = &args.flag_stoplist {
+        for word in Stoplist::from_spec(spec)?.iter() {
             tokenizer_builder.insert_stopword(word);
         }
     }
@@ -405,6 +539,26 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         })
         .transpose()?;
 
+    let phrases_opt = args
+        .flag_phrases
+        .map(|path| -> CliResult<Phrases> {
+            let config = Config::new(&Some(path)).select(args.flag_phrases_column);
+            let mut phrases_reader = config.simd_reader()?;
+            let phrases_headers = phrases_reader.byte_headers()?;
+
+            let phrase_pos = config.single_selection(phrases_headers)?;
+
+            let mut phrases = Phrases::new(&args.flag_ngrams_sep);
+            let mut phrases_record = ByteRecord::new();
+
+            while phrases_reader.read_byte_record(&mut phrases_record)? {
+                phrases.add(std::str::from_utf8(&phrases_record[phrase_pos])?);
+            }
+
+            Ok(phrases)
+        })
+        .transpose()?;
+
     if args.flag_filter_junk {
         tokenizer_builder = tokenizer_builder.filter_junk();
     }
@@ -413,12 +567,39 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
     let hyphen_splitter = Regex::new(r"-+").unwrap();
 
+    let candidate_langs = args
+        .flag_langs
+        .as_ref()
+        .map(|langs| langs.split(',').map(String::from).collect::<Vec<_>>());
+
     // NOTE: everything in this function will be parallelized
     let tokenize = move |index: usize,
                          record: &ByteRecord,
                          string: &str|
           -> CliResult<Vec<(String, WordTokenKind)>> {
-        if args.cmd_paragraphs {
+        if args.cmd_lang {
+            // NOTE: detected language and confidence are emitted as two "tokens"
+            let candidates = candidate_langs
+                .as_ref()
+                .map(|langs| langs.iter().map(|lang| lang.as_str()).collect::<Vec<_>>());
+
+            return Ok(match langdetect::detect(string, candidates.as_deref()) {
+                Some(detection)
+                    if args
+                        .flag_min_confidence
+                        .is_none_or(|min| detection.confidence >= min) =>
+                {
+                    vec![
+                        (detection.lang.to_string(), WordTokenKind::Word),
+                        (detection.confidence.to_string(), WordTokenKind::Number),
+                    ]
+                }
+                _ => vec![
+                    (String::new(), WordTokenKind::Word),
+                    (String::new(), WordTokenKind::Number),
+                ],
+            });
+        } else if args.cmd_paragraphs {
             return Ok(split_paragraphs(string, args.flag_aerated)
                 .map(|paragraph| (paragraph.to_string(), WordTokenKind::Word))
                 .collect());
@@ -460,7 +641,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             }
 
             if let Some(stemmer) = &stemmer_opt {
-                text = stemmer(&text).into_owned();
+                text = stemmer.stem(&text).into_owned();
             }
 
             if let Some(whitelist) = &whitelist_opt {
@@ -482,14 +663,20 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             Some((text, pair.1))
         });
 
+        let tokens = match &phrases_opt {
+            Some(phrases) => phrases.merge(tokens.collect()),
+            None => tokens.collect(),
+        };
+
         let mut collected_tokens: Vec<(String, WordTokenKind)> = if let Some(range) = &ngrams {
             tokens
+                .into_iter()
                 .map(|token| token.0)
                 .ngrams_range(range.clone())
                 .map(|gram| (gram.join(&args.flag_ngrams_sep), WordTokenKind::Word))
                 .collect()
         } else {
-            tokens.collect()
+            tokens
         };
 
         if let Some(program) = &flatmap_program_opt {
@@ -530,7 +717,15 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                             output_record: &mut ByteRecord,
                             tokens: Vec<(String, WordTokenKind)>|
      -> CliResult<()> {
-        if args.cmd_paragraphs || args.cmd_sentences {
+        if args.cmd_lang {
+            output_record.clone_from(record);
+
+            for token in tokens {
+                output_record.push_field(token.0.as_bytes());
+            }
+
+            wtr.write_byte_record(output_record)?;
+        } else if args.cmd_paragraphs || args.cmd_sentences {
             for token in tokens {
                 output_record.clear();
 
@@ -8,8 +8,11 @@
 use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::num::NonZeroUsize;
+use std::path::Path;
 use std::rc::Rc;
 
+use rand::{Rng, RngExt};
+
 use bstr::ByteSlice;
 use simd_csv::ByteRecord;
 
@@ -19,6 +22,8 @@ use crate::collections::ClusteredInsertHashmap;
 use crate::collections::{HashMap, hash_map::Entry};
 use crate::config::{Config, Delimiter};
 use crate::select::SelectedColumns;
+use crate::sparse::SparseMatrix;
+use crate::stopwords::Stoplist;
 use crate::util;
 
 #[derive(Clone, Copy, Debug, Deserialize)]
@@ -58,7 +63,7 @@ any separator given to the --sep flag).
 The command considers, by default, documents to be a single row of the input
 but can also be symbolized by the value of a column selection given to -D/--doc.
 
-This command can compute 5 kinds of differents vocabulary statistics:
+This command can compute 8 kinds of differents vocabulary statistics:
 
 1. corpus-level statistics (using the \"corpus\" subcommand):
     - doc_count: number of documents in the corpus
@@ -114,6 +119,56 @@ This command can compute 5 kinds of differents vocabulary statistics:
     - count: total number of co-occurrences
     - lgl: the specificity score (ratio of statistically relevant co-occurrences)
 
+6. keyphrase-level statistics (using the \"keyphrases\" subcommand):
+    - (*doc): columns representing the document (named like the input)
+    - keyphrase: some candidate keyphrase, i.e. a sequence of tokens
+    - count: number of occurrences of the keyphrase in the document
+    - score: score of the keyphrase (higher is better with \"rake\", lower
+      is better with \"yake\")
+
+    Keyphrases are extracted from each document using either the RAKE or
+    the YAKE algorithm, and only the top -t/--top ones will be emitted.
+    Candidate keyphrases never span stopwords (for RAKE), punctuation tokens
+    or, when using --sep, multiple rows. This means you should not drop
+    punctuation when tokenizing and that you should give a stoplist using
+    the --stoplist flag, e.g. \"builtin:en\", so that stopwords are kept in the
+    token stream but used as keyphrase delimiters.
+
+7. topic-level statistics (using the \"topics\" subcommand):
+    - topic: index of the topic, starting from 0
+    - token: one of the most probable tokens for the topic
+    - probability: probability of the token given the topic
+
+    Topics are found using Latent Dirichlet Allocation (LDA), fitted through
+    collapsed Gibbs sampling, and only the top --top-words tokens per topic
+    will be emitted. Use --doc-topics to also write the topic distribution of
+    each document in a separate CSV file, with the following columns:
+
+    - (*doc): columns representing the document (named like the input)
+    - topic_0, topic_1, ...: probability of each topic given the document
+
+    Results depend on the random initialization of the sampler, so use --seed
+    if you need them to be reproducible.
+
+8. collocation-level statistics (using the \"collocations\" subcommand):
+    - collocation: a sequence of contiguous tokens, separated by a space
+    - n: number of tokens in the collocation
+    - count: number of occurrences of the collocation
+    - expected_count: expected number of occurrences of the collocation
+    - G2: G2 score (log-likelihood ratio)
+    - t_score: t-score
+    - pmi: pointwise mutual information
+    - npmi: normalized pointwise mutual information
+
+    Collocations of up to --max-words tokens are scored by measuring the
+    association between their last token and the sequence of tokens preceding
+    it. Like keyphrases, collocations never span punctuation tokens, stopwords
+    given to --stoplist or, when using --sep, multiple rows. Rows are sorted
+    by descending G2 score and can be filtered using the --min-count and
+    the --G2-significance flags. The output can then be given to \"xan
+    tokenize words\" through its --phrases flag, in order to merge the
+    collocations into single tokens.
+
 Note that you should generally avoid giving too much importance wrt
 the statistical relevance of both chi2 & G2 scores when considering
 less than 5 items (absolute term frequencies or co-occurrence counts).
@@ -124,6 +179,9 @@ Usage:
     xan vocab doc [options] [<input>]
     xan vocab doc-token [options] [<input>]
     xan vocab cooc [options] [<input>]
+    xan vocab keyphrases [options] [<input>]
+    xan vocab topics [options] [<input>]
+    xan vocab collocations [options] [<input>]
     xan vocab --help
 
 vocab options:
@@ -137,6 +195,12 @@ vocab options:
     --implode                If given, will implode the file over the token column so that
                              it becomes possible to process a file containing only one token
                              per row. Cannot be used without -D, --doc.
+    --stoplist <spec>        Stoplist of tokens to ignore. Can be a path to a .txt file
+                             containing one word per line, or \"builtin:\" followed by
+                             comma-separated language codes, e.g. \"builtin:fr,en\".
+                             Several stoplists can be combined using \"+\", e.g.
+                             \"builtin:fr+custom.txt\". Run \"xan help stopwords\"
+                             to list available builtin languages.
 
 vocab doc-token options:
     --tf-weight <weight>         TF weighting scheme. One of \"count\", \"binary\", \"ratio\",
@@ -147,6 +211,37 @@ vocab doc-token options:
                                  chi2 score that must be above the given significance level. Accepted
                                  levels include \"0.5\", \"0.1\", \"0.05\", \"0.025\", \"0.01\",
                                  \"0.005\" and \"0.001\".
+    --format <format>            Output format. Either \"csv\", \"mtx\" to write a sparse
+                                 document-term matrix in the Matrix Market format, or \"npz\"
+                                 to write it as a scipy CSR matrix (as read by
+                                 scipy.sparse.load_npz). Sparse formats require -o, --output
+                                 and will also write two sidecar CSV files next to the output
+                                 file, e.g. \"dtm.rows.csv\" & \"dtm.cols.csv\" for \"dtm.npz\",
+                                 containing the row (document) & column (token) labels of the
+                                 matrix respectively. [default: csv]
+    --value <metric>             Metric to use as the values of the sparse matrix. One of
+                                 \"tf\", \"expected_tf\", \"tfidf\", \"bm25\" or \"chi2\".
+                                 [default: tf]
+
+vocab keyphrases options:
+    -m, --method <method>        Keyphrase extraction method. Either \"rake\" or \"yake\".
+                                 [default: rake]
+    -t, --top <n>                Number of keyphrases to emit per document.
+                                 [default: 10]
+    --max-words <n>              Maximum number of tokens in a keyphrase, or in
+                                 a collocation. [default: 3]
+
+vocab topics options:
+    -k, --topics <n>             Number of topics to find. [default: 10]
+    --iterations <n>             Number of Gibbs sampling iterations. [default: 200]
+    --alpha <value>              Dirichlet prior of the doc-topic distributions. Lower
+                                 values make documents focus on fewer topics. [default: 0.1]
+    --beta <value>               Dirichlet prior of the topic-token distributions. Lower
+                                 values make topics focus on fewer tokens. [default: 0.01]
+    --top-words <n>              Number of tokens to emit per topic. [default: 10]
+    --doc-topics <path>          Path of a CSV file where the topic distribution of each
+                                 document will be written.
+    --seed <number>              RNG seed.
 
 vocab cooc options:
     -w, --window <n>             Size of the co-occurrence window, in number of tokens around the currently
@@ -158,16 +253,16 @@ vocab cooc options:
     -F, --forward                Whether to only consider a forward window when traversing token contexts.
     --distrib                    Compute directed distributional similarity metrics instead.
     --specificity                Compute the lgl specificity score per token instead.
-    --min-count <n>              Minimum number of co-occurrence count to be included in the result.
-                                 [default: 1]
+    --min-count <n>              Minimum number of co-occurrence count (or collocation count)
+                                 to be included in the result. [default: 1]
     --chi2-significance <value>  Filter doc,token pairs by only keeping significant ones wrt their
                                  chi2 score that must be above the given significance level. Accepted
                                  levels include \"0.5\", \"0.1\", \"0.05\", \"0.025\", \"0.01\",
                                  \"0.005\" and \"0.001\".
-    --G2-significance <value>    Filter doc,token pairs by only keeping significant ones wrt their
-                                 G2 score that must be above the given significance level. Accepted
-                                 levels include \"0.5\", \"0.1\", \"0.05\", \"0.025\", \"0.01\",
-                                 \"0.005\" and \"0.001\".
+    --G2-significance <value>    Filter doc,token pairs (or collocations) by only keeping significant
+                                 ones wrt their G2 score that must be above the given significance
+                                 level. Accepted levels include \"0.5\", \"0.1\", \"0.05\", \"0.025\",
+                                 \"0.01\", \"0.005\" and \"0.001\".
 
 Common options:
     -h, --help             Display this message
@@ -185,15 +280,21 @@ struct Args {
     cmd_doc_token: bool,
     cmd_corpus: bool,
     cmd_cooc: bool,
+    cmd_keyphrases: bool,
+    cmd_topics: bool,
+    cmd_collocations: bool,
     arg_input: Option<String>,
     flag_token: Option<SelectedColumns>,
     flag_doc: Option<SelectedColumns>,
     flag_sep: Option<String>,
     flag_implode: bool,
+    flag_stoplist: Option<String>,
     flag_tf_weight: TfWeighting,
     flag_k1_value: f64,
     flag_b_value: f64,
     flag_chi2_significance: Option<SignificanceLevel>,
+    flag_format: DocTokenFormat,
+    flag_value: DocTokenMetric,
     #[serde(rename = "flag_G2_significance")]
     flag_g2_significance: Option<SignificanceLevel>,
     flag_window: Option<NonZeroUsize>,
@@ -201,6 +302,16 @@ struct Args {
     flag_distrib: bool,
     flag_specificity: bool,
     flag_min_count: usize,
+    flag_method: KeyphraseMethod,
+    flag_top: usize,
+    flag_max_words: NonZeroUsize,
+    flag_topics: NonZeroUsize,
+    flag_iterations: usize,
+    flag_alpha: f64,
+    flag_beta: f64,
+    flag_top_words: usize,
+    flag_doc_topics: Option<String>,
+    flag_seed: Option<usize>,
     flag_output: Option<String>,
     flag_no_headers: bool,
     flag_delimiter: Option<Delimiter>,
@@ -232,6 +343,15 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         ));
     }
 
+    let stoplist = args
+        .flag_stoplist
+        .as_ref()
+        .map(|spec| Stoplist::from_spec(spec))
+        .transpose()?;
+
+    let is_stopword =
+        |token: &[u8]| -> bool { stoplist.as_ref().is_some_and(|s| s.contains(token.trim())) };
+
     let chi2_significance = args.flag_chi2_significance.map(|s| s.get());
     let g2_significance = args.flag_g2_significance.map(|s| s.get());
 
@@ -256,7 +376,148 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     let mut record = ByteRecord::new();
     let mut i: usize = 0;
 
-    let mut wtr = Config::new(&args.flag_output).simd_writer()?;
+    if !matches!(args.flag_format, DocTokenFormat::Csv) {
+        if !args.cmd_doc_token {
+            Err("--format only works with the doc-token subcommand!")?;
+        }
+
+        if args.flag_output.is_none() {
+            Err("cannot export a sparse matrix without a path.\nUse -o, --output!")?;
+        }
+    }
+
+    // NOTE: sparse matrix formats are written separately
+    let mut wtr = Config::new(if matches!(args.flag_format, DocTokenFormat::Csv) {
+        &args.flag_output
+    } else {
+        &None
+    })
+    .simd_writer()?;
+
+    // NOTE: both keyphrases & collocations need to process documents as sentences
+    if args.cmd_keyphrases || args.cmd_collocations {
+        if args.cmd_keyphrases {
+            let mut output_headers = ByteRecord::new();
+
+            if let Some(sel) = &doc_sel {
+                for col_name in sel.select(&headers) {
+                    output_headers.push_field(col_name);
+                }
+            } else {
+                output_headers.push_field(b"doc");
+            }
+
+            output_headers.push_field(b"keyphrase");
+            output_headers.push_field(b"count");
+            output_headers.push_field(b"score");
+
+            wtr.write_byte_record(&output_headers)?;
+        }
+
+        let extractor = KeyphraseExtractor {
+            method: args.flag_method,
+            top: args.flag_top,
+            max_words: args.flag_max_words.get(),
+        };
+
+        let mut collocations = Collocations::new(args.flag_max_words.get());
+
+        let mut output_record = ByteRecord::new();
+
+        let mut process_document =
+            |document: &Document, sentences: &[Vec<Token>]| -> CliResult<()> {
+                if args.cmd_collocations {
+                    collocations.add_sentences(sentences, is_stopword);
+                    return Ok(());
+                }
+
+                for (keyphrase, count, score) in extractor.extract(sentences, is_stopword) {
+                    output_record.clone_from(document);
+                    output_record.push_field(&keyphrase);
+                    output_record.push_field(count.to_string().as_bytes());
+                    output_record.push_field(score.to_string().as_bytes());
+
+                    wtr.write_byte_record(&output_record)?;
+                }
+
+                Ok(())
+            };
+
+        match &doc_sel {
+            None => {
+                let sep = args.flag_sep.as_ref().unwrap();
+
+                while rdr.read_byte_record(&mut record)? {
+                    let mut document = ByteRecord::new();
+                    document.push_field(i.to_string().as_bytes());
+
+                    let sentences = KeyphraseExtractor::split_sentences(
+                        record[token_pos].split_str(sep).map(|token| token.trim()),
+                    );
+
+                    process_document(&document, &sentences)?;
+
+                    i += 1;
+                }
+            }
+            Some(sel) => {
+                let mut doc_sentences: ClusteredInsertHashmap<Document, Vec<Vec<Token>>> =
+                    ClusteredInsertHashmap::new();
+
+                while rdr.read_byte_record(&mut record)? {
+                    let doc = sel.select(&record).collect();
+                    let sentences = doc_sentences.insert_with(doc, Vec::new);
+
+                    match &args.flag_sep {
+                        // NOTE: rows are considered as sentence boundaries
+                        Some(sep) => {
+                            sentences.extend(KeyphraseExtractor::split_sentences(
+                                record[token_pos].split_str(sep).map(|token| token.trim()),
+                            ));
+                        }
+                        None => {
+                            let token = record[token_pos].trim();
+
+                            if KeyphraseExtractor::is_punctuation(token) {
+                                sentences.push(vec![]);
+                            } else {
+                                match sentences.last_mut() {
+                                    Some(sentence) => sentence.push(token.to_vec()),
+                                    None => sentences.push(vec![token.to_vec()]),
+                                }
+                            }
+                        }
+                    };
+                }
+
+                for (document, sentences) in doc_sentences.into_iter() {
+                    process_document(&document, &sentences)?;
+                }
+            }
+        };
+
+        if args.cmd_collocations {
+            let output_headers: [&[u8]; 8] = [
+                b"collocation",
+                b"n",
+                b"count",
+                b"expected_count",
+                b"G2",
+                b"t_score",
+                b"pmi",
+                b"npmi",
+            ];
+
+            wtr.write_record(output_headers)?;
+            collocations.for_each_collocation_record(
+                args.flag_min_count,
+                g2_significance,
+                |r| wtr.write_byte_record(r),
+            )?;
+        }
+
+        return Ok(wtr.flush()?);
+    }
 
     if args.cmd_cooc {
         let mut cooccurrences = Cooccurrences::default();
@@ -290,11 +551,15 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                     match &args.flag_sep {
                         Some(sep) => {
                             for token in record[token_pos].split_str(sep) {
-                                tokens.push(Rc::from(token));
+                                if !is_stopword(token) {
+                                    tokens.push(Rc::from(token));
+                                }
                             }
                         }
                         None => {
-                            tokens.push(Rc::from(&record[token_pos]));
+                            if !is_stopword(&record[token_pos]) {
+                                tokens.push(Rc::from(&record[token_pos]));
+                            }
                         }
                     };
                 }
@@ -317,8 +582,11 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             // Separator, doc = row or sel, both models
             (Some(sep), _, model) => {
                 while rdr.read_byte_record(&mut record)? {
-                    let bag_of_words: Vec<Rc<TokenRef>> =
-                        record[token_pos].split_str(sep).map(Rc::from).collect();
+                    let bag_of_words: Vec<Rc<TokenRef>> = record[token_pos]
+                        .split_str(sep)
+                        .filter(|token| !is_stopword(token))
+                        .map(Rc::from)
+                        .collect();
 
                     for i in 0..bag_of_words.len() {
                         let source = &bag_of_words[i];
@@ -363,6 +631,10 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                 let mut current_opt: Option<(Document, Vec<Rc<TokenRef>>)> = None;
 
                 while rdr.read_byte_record(&mut record)? {
+                    if is_stopword(&record[token_pos]) {
+                        continue;
+                    }
+
                     let doc = sel.select(&record).collect();
                     let token = Rc::from(&record[token_pos]);
 
@@ -449,14 +721,14 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             for token in record[token_pos].split_str(sep) {
                 let token: Token = token.trim().to_vec();
 
-                if !token.is_empty() {
+                if !token.is_empty() && !is_stopword(&token) {
                     vocab.add(document.clone(), token);
                 }
             }
         } else {
             let token: Token = record[token_pos].trim().to_vec();
 
-            if !token.is_empty() {
+            if !token.is_empty() && !is_stopword(&token) {
                 vocab.add(document, token);
             }
         }
@@ -476,6 +748,72 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         ];
         wtr.write_record(headers)?;
         vocab.for_each_token_level_record(|r| wtr.write_byte_record(r))?;
+    } else if args.cmd_doc_token && !matches!(args.flag_format, DocTokenFormat::Csv) {
+        let output_path = Path::new(args.flag_output.as_ref().unwrap());
+
+        let mut rows_wtr = Config::new(&Some(
+            output_path
+                .with_extension("rows.csv")
+                .to_string_lossy()
+                .to_string(),
+        ))
+        .simd_writer()?;
+
+        let mut output_headers = ByteRecord::new();
+
+        if let Some(sel) = &doc_sel {
+            for col_name in sel.select(&headers) {
+                output_headers.push_field(col_name);
+            }
+        } else {
+            output_headers.push_field(b"doc");
+        }
+
+        rows_wtr.write_byte_record(&output_headers)?;
+
+        for (doc, _) in vocab.documents.iter() {
+            rows_wtr.write_byte_record(doc)?;
+        }
+
+        rows_wtr.flush()?;
+
+        let mut cols_wtr = Config::new(&Some(
+            output_path
+                .with_extension("cols.csv")
+                .to_string_lossy()
+                .to_string(),
+        ))
+        .simd_writer()?;
+
+        cols_wtr.write_record([b"token"])?;
+
+        for token_stats in vocab.tokens.iter() {
+            cols_wtr.write_record([&token_stats.text])?;
+        }
+
+        cols_wtr.flush()?;
+
+        let mut matrix = SparseMatrix::new(vocab.doc_count(), vocab.tokens.len());
+
+        vocab.for_each_doc_token_scores(
+            args.flag_k1_value,
+            args.flag_b_value,
+            args.flag_tf_weight,
+            chi2_significance,
+            |row, col, scores| -> Result<(), CliError> {
+                if let Some(value) = scores.get(args.flag_value) {
+                    matrix.push(row, col, value);
+                }
+
+                Ok(())
+            },
+        )?;
+
+        match args.flag_format {
+            DocTokenFormat::Mtx => matrix.write_matrix_market(output_path)?,
+            DocTokenFormat::Npz => matrix.write_npz(output_path)?,
+            DocTokenFormat::Csv => unreachable!(),
+        };
     } else if args.cmd_doc_token {
         let mut output_headers = ByteRecord::new();
 
@@ -519,6 +857,61 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         wtr.write_byte_record(&output_headers)?;
 
         vocab.for_each_doc_level_record(|r| wtr.write_byte_record(r))?;
+    } else if args.cmd_topics {
+        let mut lda = Lda::new(
+            &vocab,
+            args.flag_topics.get(),
+            args.flag_alpha,
+            args.flag_beta,
+        );
+
+        let mut rng = util::acquire_rng(args.flag_seed);
+
+        lda.fit(&mut rng, args.flag_iterations);
+
+        wtr.write_record([b"topic".as_slice(), b"token", b"probability"])?;
+
+        for topic in 0..lda.k {
+            for (token_id, probability) in lda.top_tokens(topic, args.flag_top_words) {
+                wtr.write_record([
+                    topic.to_string().as_bytes(),
+                    &vocab.tokens[token_id].text,
+                    probability.to_string().as_bytes(),
+                ])?;
+            }
+        }
+
+        if let Some(path) = &args.flag_doc_topics {
+            let mut doc_topics_wtr = Config::new(&Some(path.clone())).simd_writer()?;
+
+            let mut output_headers = ByteRecord::new();
+
+            if let Some(sel) = &doc_sel {
+                for col_name in sel.select(&headers) {
+                    output_headers.push_field(col_name);
+                }
+            } else {
+                output_headers.push_field(b"doc");
+            }
+
+            for topic in 0..lda.k {
+                output_headers.push_field(format!("topic_{}", topic).as_bytes());
+            }
+
+            doc_topics_wtr.write_byte_record(&output_headers)?;
+
+            for (doc_index, (doc, _)) in vocab.documents.iter().enumerate() {
+                let mut output_record = doc.clone();
+
+                for probability in lda.doc_topics(doc_index) {
+                    output_record.push_field(probability.to_string().as_bytes());
+                }
+
+                doc_topics_wtr.write_byte_record(&output_record)?;
+            }
+
+            doc_topics_wtr.flush()?;
+        }
     } else if args.cmd_corpus {
         let headers: [&[u8]; 4] = [
             b"doc_count",
@@ -623,6 +1016,589 @@ impl TryFrom<String> for TfWeighting {
     }
 }
 
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(try_from = "String")]
+enum DocTokenFormat {
+    Csv,
+    Mtx,
+    Npz,
+}
+
+impl TryFrom<String> for DocTokenFormat {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(match value.as_str() {
+            "csv" => Self::Csv,
+            "mtx" => Self::Mtx,
+            "npz" => Self::Npz,
+            _ => return Err(format!("unsupported --format \"{}\"", &value)),
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(try_from = "String")]
+enum DocTokenMetric {
+    Tf,
+    ExpectedTf,
+    Tfidf,
+    Bm25,
+    Chi2,
+}
+
+impl TryFrom<String> for DocTokenMetric {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(match value.as_str() {
+            "tf" => Self::Tf,
+            "expected_tf" => Self::ExpectedTf,
+            "tfidf" => Self::Tfidf,
+            "bm25" => Self::Bm25,
+            "chi2" => Self::Chi2,
+            _ => return Err(format!("unsupported --value \"{}\"", &value)),
+        })
+    }
+}
+
+struct DocTokenScores {
+    tf: f64,
+    expected_tf: f64,
+    tfidf: f64,
+    bm25: f64,
+    chi2: Option<f64>,
+}
+
+impl DocTokenScores {
+    fn get(&self, metric: DocTokenMetric) -> Option<f64> {
+        match metric {
+            DocTokenMetric::Tf => Some(self.tf),
+            DocTokenMetric::ExpectedTf => Some(self.expected_tf),
+            DocTokenMetric::Tfidf => Some(self.tfidf),
+            DocTokenMetric::Bm25 => Some(self.bm25),
+            DocTokenMetric::Chi2 => self.chi2,
+        }
+    }
+}
+
+// NOTE: LDA fitted using collapsed Gibbs sampling, as described in:
+// Griffiths, T. L., & Steyvers, M. (2004). Finding scientific topics.
+struct Lda {
+    k: usize,
+    alpha: f64,
+    beta: f64,
+    vocabulary_size: usize,
+    // NOTE: documents as lists of (token, topic assignment)
+    documents: Vec<Vec<(TokenID, usize)>>,
+    doc_topic_counts: Vec<Vec<u64>>,
+    topic_token_counts: Vec<Vec<u64>>,
+    topic_counts: Vec<u64>,
+}
+
+impl Lda {
+    fn new(vocab: &Vocabulary, k: usize, alpha: f64, beta: f64) -> Self {
+        let vocabulary_size = vocab.distinct_token_count();
+
+        let documents = vocab
+            .documents
+            .iter()
+            .map(|(_, doc_stats)| {
+                let mut tokens = doc_stats
+                    .tokens
+                    .iter()
+                    .map(|(token_id, doc_token_stats)| (*token_id, doc_token_stats.tf))
+                    .collect::<Vec<_>>();
+
+                // NOTE: sorting so that results only depend on the rng
+                tokens.sort_by_key(|(token_id, _)| *token_id);
+
+                tokens
+                    .into_iter()
+                    .flat_map(|(token_id, tf)| std::iter::repeat_n((token_id, 0), tf as usize))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            k,
+            alpha,
+            beta,
+            vocabulary_size,
+            doc_topic_counts: vec![vec![0; k]; documents.len()],
+            topic_token_counts: vec![vec![0; vocabulary_size]; k],
+            topic_counts: vec![0; k],
+            documents,
+        }
+    }
+
+    fn fit<R: Rng + ?Sized>(&mut self, rng: &mut R, iterations: usize) {
+        for (doc_index, document) in self.documents.iter_mut().enumerate() {
+            for (token_id, topic) in document.iter_mut() {
+                *topic = rng.random_range(0..self.k);
+
+                self.doc_topic_counts[doc_index][*topic] += 1;
+                self.topic_token_counts[*topic][*token_id] += 1;
+                self.topic_counts[*topic] += 1;
+            }
+        }
+
+        let beta_sum = self.beta * self.vocabulary_size as f64;
+        let mut weights = vec![0.0; self.k];
+
+        for _ in 0..iterations {
+            for (doc_index, document) in self.documents.iter_mut().enumerate() {
+                let doc_topic_counts = &mut self.doc_topic_counts[doc_index];
+
+                for (token_id, topic) in document.iter_mut() {
+                    doc_topic_counts[*topic] -= 1;
+                    self.topic_token_counts[*topic][*token_id] -= 1;
+                    self.topic_counts[*topic] -= 1;
+
+                    let mut total = 0.0;
+
+                    for (t, weight) in weights.iter_mut().enumerate() {
+                        *weight = (doc_topic_counts[t] as f64 + self.alpha)
+                            * (self.topic_token_counts[t][*token_id] as f64 + self.beta)
+                            / (self.topic_counts[t] as f64 + beta_sum);
+
+                        total += *weight;
+                    }
+
+                    let mut threshold = rng.random::<f64>() * total;
+
+                    *topic = self.k - 1;
+
+                    for (t, weight) in weights.iter().enumerate() {
+                        threshold -= weight;
+
+                        if threshold <= 0.0 {
+                            *topic = t;
+                            break;
+                        }
+                    }
+
+                    doc_topic_counts[*topic] += 1;
+                    self.topic_token_counts[*topic][*token_id] += 1;
+                    self.topic_counts[*topic] += 1;
+                }
+            }
+        }
+    }
+
+    fn top_tokens(&self, topic: usize, n: usize) -> Vec<(TokenID, f64)> {
+        let denominator = self.topic_counts[topic] as f64 + self.beta * self.vocabulary_size as f64;
+
+        let mut tokens = self.topic_token_counts[topic]
+            .iter()
+            .enumerate()
+            .map(|(token_id, count)| (token_id, (*count as f64 + self.beta) / denominator))
+            .collect::<Vec<_>>();
+
+        tokens.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        tokens.truncate(n);
+
+        tokens
+    }
+
+    fn doc_topics(&self, doc_index: usize) -> impl Iterator<Item = f64> + '_ {
+        let doc_topic_counts = &self.doc_topic_counts[doc_index];
+        let denominator = self.documents[doc_index].len() as f64 + self.alpha * self.k as f64;
+
+        doc_topic_counts
+            .iter()
+            .map(move |count| (*count as f64 + self.alpha) / denominator)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(try_from = "String")]
+enum KeyphraseMethod {
+    Rake,
+    Yake,
+}
+
+impl TryFrom<String> for KeyphraseMethod {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(match value.as_str() {
+            "rake" => Self::Rake,
+            "yake" => Self::Yake,
+            _ => return Err(format!("unknown keyphrase method \"{}\"", &value)),
+        })
+    }
+}
+
+#[derive(Default)]
+struct YakeWordStats {
+    tf: usize,
+    tf_upper: usize,
+    tf_acronym: usize,
+    sentences: Vec<usize>,
+    left: HashMap<Token, usize>,
+    right: HashMap<Token, usize>,
+}
+
+impl YakeWordStats {
+    fn dispersion(neighbours: &HashMap<Token, usize>) -> f64 {
+        let total = neighbours.values().sum::<usize>();
+
+        if total == 0 {
+            0.0
+        } else {
+            neighbours.len() as f64 / total as f64
+        }
+    }
+}
+
+// NOTE: collocations of n tokens are scored as the association between their
+// n - 1 first tokens and their last one, which is a generalization of the
+// usual bigram case.
+struct Collocations {
+    max_words: usize,
+    counts: HashMap<Vec<Token>, usize>,
+    token_count: usize,
+}
+
+impl Collocations {
+    fn new(max_words: usize) -> Self {
+        Self {
+            max_words,
+            counts: HashMap::new(),
+            token_count: 0,
+        }
+    }
+
+    fn add_sentences<F>(&mut self, sentences: &[Vec<Token>], is_stopword: F)
+    where
+        F: Fn(&[u8]) -> bool,
+    {
+        for sentence in sentences {
+            for span in sentence.split(|token| is_stopword(token)) {
+                self.token_count += span.len();
+
+                for i in 0..span.len() {
+                    for j in (i + 1)..=(i + self.max_words).min(span.len()) {
+                        *self.counts.entry(span[i..j].to_vec()).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn for_each_collocation_record<F, E>(
+        &self,
+        min_count: usize,
+        g2_significance: Option<f64>,
+        mut callback: F,
+    ) -> Result<(), E>
+    where
+        F: FnMut(&ByteRecord) -> Result<(), E>,
+    {
+        let n = self.token_count;
+
+        let mut rows = Vec::new();
+
+        for (collocation, count) in self.counts.iter() {
+            if collocation.len() < 2 || *count < min_count {
+                continue;
+            }
+
+            let xy = *count;
+            let x = self.counts[&collocation[..collocation.len() - 1]];
+            let y = self.counts[&collocation[collocation.len() - 1..]];
+
+            let g2 = compute_g2(x, y, xy, n);
+
+            if let Some(level) = g2_significance {
+                if g2 < level {
+                    continue;
+                }
+            }
+
+            let expected = (x * y) as f64 / n as f64;
+            let t_score = (xy as f64 - expected) / (xy as f64).sqrt();
+            let pmi = compute_pmi(x, y, xy, n);
+            let npmi = compute_npmi(xy, n, pmi);
+
+            rows.push((
+                collocation.join(&b' '),
+                collocation.len(),
+                xy,
+                expected,
+                g2,
+                t_score,
+                pmi,
+                npmi,
+            ));
+        }
+
+        rows.sort_by(|a, b| b.4.total_cmp(&a.4).then_with(|| a.0.cmp(&b.0)));
+
+        let mut record = ByteRecord::new();
+
+        for row in rows {
+            record.clear();
+            record.push_field(&row.0);
+            record.push_field(row.1.to_string().as_bytes());
+            record.push_field(row.2.to_string().as_bytes());
+            record.push_field(row.3.to_string().as_bytes());
+            record.push_field(row.4.to_string().as_bytes());
+            record.push_field(row.5.to_string().as_bytes());
+            record.push_field(row.6.to_string().as_bytes());
+            record.push_field(row.7.to_string().as_bytes());
+
+            callback(&record)?;
+        }
+
+        Ok(())
+    }
+}
+
+// References:
+// Rose, S., Engel, D., Cramer, N., & Cowley, W. (2010). Automatic Keyword Extraction
+// from Individual Documents. Text Mining: Applications and Theory, 1-20.
+//
+// Campos, R., Mangaravite, V., Pasquali, A., Jorge, A., Nunes, C., & Jatowt, A. (2020).
+// YAKE! Keyword extraction from single documents using multiple local features.
+// Information Sciences, 509, 257-289.
+struct KeyphraseExtractor {
+    method: KeyphraseMethod,
+    top: usize,
+    max_words: usize,
+}
+
+impl KeyphraseExtractor {
+    fn is_punctuation(token: &[u8]) -> bool {
+        !token.chars().any(|c| c.is_alphanumeric())
+    }
+
+    fn split_sentences<'a>(tokens: impl Iterator<Item = &'a [u8]>) -> Vec<Vec<Token>> {
+        let mut sentences = vec![vec![]];
+
+        for token in tokens {
+            if token.is_empty() {
+                continue;
+            }
+
+            if Self::is_punctuation(token) {
+                sentences.push(vec![]);
+            } else {
+                sentences.last_mut().unwrap().push(token.to_vec());
+            }
+        }
+
+        sentences
+    }
+
+    fn extract<F>(&self, sentences: &[Vec<Token>], is_stopword: F) -> Vec<(Token, usize, f64)>
+    where
+        F: Fn(&[u8]) -> bool,
+    {
+        let mut keyphrases = match self.method {
+            KeyphraseMethod::Rake => self.rake(sentences, is_stopword),
+            KeyphraseMethod::Yake => self.yake(sentences, is_stopword),
+        };
+
+        let sign = match self.method {
+            KeyphraseMethod::Rake => -1.0,
+            KeyphraseMethod::Yake => 1.0,
+        };
+
+        keyphrases.sort_by(|a, b| {
+            (sign * a.2)
+                .total_cmp(&(sign * b.2))
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        keyphrases.truncate(self.top);
+
+        keyphrases
+    }
+
+    fn rake<F>(&self, sentences: &[Vec<Token>], is_stopword: F) -> Vec<(Token, usize, f64)>
+    where
+        F: Fn(&[u8]) -> bool,
+    {
+        let mut candidates: Vec<Vec<Token>> = Vec::new();
+
+        for sentence in sentences {
+            for phrase in sentence.split(|token| is_stopword(token)) {
+                if !phrase.is_empty() && phrase.len() <= self.max_words {
+                    candidates.push(phrase.iter().map(|token| token.to_lowercase()).collect());
+                }
+            }
+        }
+
+        // NOTE: (frequency, degree)
+        let mut word_stats: HashMap<&Token, (usize, usize)> = HashMap::new();
+
+        for phrase in candidates.iter() {
+            for word in phrase {
+                let stats = word_stats.entry(word).or_default();
+                stats.0 += 1;
+                stats.1 += phrase.len();
+            }
+        }
+
+        let mut keyphrases: HashMap<Token, (usize, f64)> = HashMap::new();
+
+        for phrase in candidates.iter() {
+            let entry = keyphrases.entry(phrase.join(&b' ')).or_insert_with(|| {
+                let score = phrase
+                    .iter()
+                    .map(|word| {
+                        let (frequency, degree) = word_stats[word];
+                        degree as f64 / frequency as f64
+                    })
+                    .sum::<f64>();
+
+                (0, score)
+            });
+
+            entry.0 += 1;
+        }
+
+        keyphrases
+            .into_iter()
+            .map(|(phrase, (count, score))| (phrase, count, score))
+            .collect()
+    }
+
+    fn yake<F>(&self, sentences: &[Vec<Token>], is_stopword: F) -> Vec<(Token, usize, f64)>
+    where
+        F: Fn(&[u8]) -> bool,
+    {
+        let mut word_stats: HashMap<Token, YakeWordStats> = HashMap::new();
+
+        let lowercase_sentences = sentences
+            .iter()
+            .map(|sentence| {
+                sentence
+                    .iter()
+                    .map(|token| token.to_lowercase())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for (i, (sentence, lowercase_sentence)) in
+            sentences.iter().zip(lowercase_sentences.iter()).enumerate()
+        {
+            for (j, (token, word)) in sentence.iter().zip(lowercase_sentence.iter()).enumerate() {
+                if is_stopword(token) {
+                    continue;
+                }
+
+                let stats = word_stats.entry(word.clone()).or_default();
+
+                stats.tf += 1;
+
+                if token.chars().count() > 1 && token.chars().all(|c| c.is_uppercase()) {
+                    stats.tf_acronym += 1;
+                } else if j > 0 && token.chars().next().is_some_and(|c| c.is_uppercase()) {
+                    stats.tf_upper += 1;
+                }
+
+                if stats.sentences.last() != Some(&i) {
+                    stats.sentences.push(i);
+                }
+
+                if j > 0 {
+                    *stats
+                        .left
+                        .entry(lowercase_sentence[j - 1].clone())
+                        .or_insert(0) += 1;
+                }
+
+                if j + 1 < sentence.len() {
+                    *stats
+                        .right
+                        .entry(lowercase_sentence[j + 1].clone())
+                        .or_insert(0) += 1;
+                }
+            }
+        }
+
+        if word_stats.is_empty() {
+            return vec![];
+        }
+
+        let sentence_count = sentences.len() as f64;
+        let tfs = word_stats.values().map(|stats| stats.tf as f64);
+        let n = word_stats.len() as f64;
+        let mean_tf = tfs.clone().sum::<f64>() / n;
+        let std_tf = (tfs.map(|tf| (tf - mean_tf).powi(2)).sum::<f64>() / n).sqrt();
+        let max_tf = word_stats.values().map(|stats| stats.tf).max().unwrap() as f64;
+
+        let word_scores: HashMap<&Token, f64> = word_stats
+            .iter()
+            .map(|(word, stats)| {
+                let tf = stats.tf as f64;
+
+                let t_case = stats.tf_upper.max(stats.tf_acronym) as f64 / (1.0 + tf.ln());
+                let median_sentence = stats.sentences[stats.sentences.len() / 2] as f64;
+                let t_pos = (3.0 + median_sentence).ln().ln();
+                let t_freq = tf / (mean_tf + std_tf);
+                let t_rel = 1.0
+                    + (YakeWordStats::dispersion(&stats.left)
+                        + YakeWordStats::dispersion(&stats.right))
+                        * (tf / max_tf);
+                let t_diff = stats.sentences.len() as f64 / sentence_count;
+
+                let score = (t_rel * t_pos) / (t_case + t_freq / t_rel + t_diff / t_rel);
+
+                (word, score)
+            })
+            .collect();
+
+        let mut keyphrases: HashMap<Token, (usize, f64, f64)> = HashMap::new();
+
+        for (sentence, lowercase_sentence) in sentences.iter().zip(lowercase_sentences.iter()) {
+            for start in 0..sentence.len() {
+                if is_stopword(&sentence[start]) {
+                    continue;
+                }
+
+                for end in start..(start + self.max_words).min(sentence.len()) {
+                    if is_stopword(&sentence[end]) {
+                        continue;
+                    }
+
+                    let words = &lowercase_sentence[start..=end];
+                    let phrase = words.join(&b' ');
+
+                    keyphrases
+                        .entry(phrase)
+                        .or_insert_with(|| {
+                            let mut product = 1.0;
+                            let mut sum = 0.0;
+
+                            for (token, word) in sentence[start..=end].iter().zip(words) {
+                                if is_stopword(token) {
+                                    continue;
+                                }
+
+                                let score = word_scores[word];
+                                product *= score;
+                                sum += score;
+                            }
+
+                            (0, product, sum)
+                        })
+                        .0 += 1;
+                }
+            }
+        }
+
+        keyphrases
+            .into_iter()
+            .map(|(phrase, (count, product, sum))| {
+                (phrase, count, product / (count as f64 * (1.0 + sum)))
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug)]
 struct DocumentTokenStats {
     tf: u64,
@@ -786,8 +1762,9 @@ impl Vocabulary {
         Ok(())
     }
 
-    fn for_each_doc_token_level_record<F, E>(
-        self,
+    // NOTE: the callback receives the document index, the token id & scores
+    fn for_each_doc_token_scores<F, E>(
+        &self,
         k1: f64,
         b: f64,
         tf_weighting: TfWeighting,
@@ -795,7 +1772,7 @@ impl Vocabulary {
         mut callback: F,
     ) -> Result<(), E>
     where
-        F: FnMut(&ByteRecord) -> Result<(), E>,
+        F: FnMut(usize, TokenID, DocTokenScores) -> Result<(), E>,
     {
         let n = self.doc_count();
 
@@ -805,16 +1782,11 @@ impl Vocabulary {
 
         let average_doc_len = self.average_doc_len();
 
-        // Aggregating stats for bm25 and chi2
-        let mut record = ByteRecord::new();
-
-        for (doc, doc_stats) in self.documents.into_iter() {
+        for (doc_index, (_, doc_stats)) in self.documents.iter().enumerate() {
             let doc_len = doc_stats.doc_len();
 
-            for (token_id, doc_token_stats) in doc_stats.tokens {
-                record.clear();
-
-                let token_stats = &self.tokens[token_id];
+            for (token_id, doc_token_stats) in doc_stats.tokens.iter() {
+                let token_stats = &self.tokens[*token_id];
 
                 let expected_tf =
                     (token_stats.gf as usize * doc_len) as f64 / self.token_count as f64;
@@ -837,32 +1809,69 @@ impl Vocabulary {
                 let tf = tf_weighting.compute(doc_token_stats.tf, doc_len);
                 let idf = token_stats.idf(n);
 
-                for cell in doc.iter() {
+                callback(
+                    doc_index,
+                    *token_id,
+                    DocTokenScores {
+                        tf,
+                        expected_tf,
+                        tfidf: tf * idf,
+                        bm25: doc_token_stats.bm25(idf, doc_len, average_doc_len, k1, b),
+                        chi2: (!under_represented).then_some(chi2),
+                    },
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn for_each_doc_token_level_record<F, E>(
+        &self,
+        k1: f64,
+        b: f64,
+        tf_weighting: TfWeighting,
+        chi2_significance: Option<f64>,
+        mut callback: F,
+    ) -> Result<(), E>
+    where
+        F: FnMut(&ByteRecord) -> Result<(), E>,
+    {
+        let documents = self
+            .documents
+            .iter()
+            .map(|(doc, _)| doc)
+            .collect::<Vec<_>>();
+
+        let mut record = ByteRecord::new();
+
+        self.for_each_doc_token_scores(
+            k1,
+            b,
+            tf_weighting,
+            chi2_significance,
+            |doc_index, token_id, scores| {
+                record.clear();
+
+                for cell in documents[doc_index].iter() {
                     record.push_field(cell);
                 }
 
-                record.push_field(&token_stats.text);
-                record.push_field(tf.to_string().as_bytes());
-                record.push_field(expected_tf.to_string().as_bytes());
-                record.push_field((tf * idf).to_string().as_bytes());
-                record.push_field(
-                    doc_token_stats
-                        .bm25(idf, doc_len, average_doc_len, k1, b)
-                        .to_string()
-                        .as_bytes(),
-                );
+                record.push_field(&self.tokens[token_id].text);
+                record.push_field(scores.tf.to_string().as_bytes());
+                record.push_field(scores.expected_tf.to_string().as_bytes());
+                record.push_field(scores.tfidf.to_string().as_bytes());
+                record.push_field(scores.bm25.to_string().as_bytes());
 
-                if !under_represented {
+                if let Some(chi2) = scores.chi2 {
                     record.push_field(chi2.to_string().as_bytes());
                 } else {
                     record.push_field(b"");
                 }
 
-                callback(&record)?;
-            }
-        }
-
-        Ok(())
+                callback(&record)
+            },
+        )
     }
 }
 
@@ -0,0 +1,78 @@
+// NOTE: parallel edges are merged by summing their weights, so that an
+// unweighted multigraph is seen as a graph weighted by edge multiplicity.
+// When weights represent distances however, only the lightest edge is kept.
+#[derive(Debug)]
+pub struct Adjacency {
+    outbound: Vec<Vec<(usize, f64)>>,
+}
+
+fn merge_neighbors(neighbors: &mut Vec<(usize, f64)>, distances: bool) {
+    neighbors.sort_by_key(|(neighbor, _)| *neighbor);
+    neighbors.dedup_by(|current, previous| {
+        if current.0 == previous.0 {
+            if distances {
+                previous.1 = previous.1.min(current.1);
+            } else {
+                previous.1 += current.1;
+            }
+            true
+        } else {
+            false
+        }
+    });
+}
+
+impl Adjacency {
+    pub fn new(
+        order: usize,
+        directed: bool,
+        edges: impl Iterator<Item = (usize, usize, f64)>,
+    ) -> Self {
+        Self::build(order, directed, false, edges)
+    }
+
+    pub fn new_distances(
+        order: usize,
+        directed: bool,
+        edges: impl Iterator<Item = (usize, usize, f64)>,
+    ) -> Self {
+        Self::build(order, directed, true, edges)
+    }
+
+    fn build(
+        order: usize,
+        directed: bool,
+        distances: bool,
+        edges: impl Iterator<Item = (usize, usize, f64)>,
+    ) -> Self {
+        let mut outbound = vec![Vec::new(); order];
+
+        for (source, target, weight) in edges {
+            outbound[source].push((target, weight));
+
+            if !directed && source != target {
+                outbound[target].push((source, weight));
+            }
+        }
+
+        for neighbors in outbound.iter_mut() {
+            merge_neighbors(neighbors, distances);
+        }
+
+        Self { outbound }
+    }
+
+    #[inline]
+    pub fn order(&self) -> usize {
+        self.outbound.len()
+    }
+
+    #[inline]
+    pub fn out_neighbors(&self, node: usize) -> &[(usize, f64)] {
+        &self.outbound[node]
+    }
+
+    pub fn out_weight(&self, node: usize) -> f64 {
+        self.outbound[node].iter().map(|(_, weight)| weight).sum()
+    }
+}
@@ -0,0 +1,419 @@
+// Node centrality metrics. Implementations follow the ones found in networkx
+// so that results can easily be compared, except for closeness which relies
+// on outbound distances for directed graphs.
+use std::collections::VecDeque;
+
+use super::Adjacency;
+
+const MAX_ITERATIONS: usize = 100;
+
+fn normalize_by_max(vector: &mut [f64]) {
+    let max = vector.iter().copied().fold(0.0, f64::max);
+
+    if max > 0.0 {
+        for x in vector.iter_mut() {
+            *x /= max;
+        }
+    }
+}
+
+fn normalize_by_sum(vector: &mut [f64]) {
+    let sum = vector.iter().sum::<f64>();
+
+    if sum > 0.0 {
+        for x in vector.iter_mut() {
+            *x /= sum;
+        }
+    }
+}
+
+fn l1_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).abs()).sum()
+}
+
+pub fn pagerank(graph: &Adjacency, damping: f64) -> Result<Vec<f64>, String> {
+    let n = graph.order();
+
+    if n == 0 {
+        return Ok(vec![]);
+    }
+
+    let nf = n as f64;
+    let tolerance = 1e-6;
+
+    let out_weights = (0..n)
+        .map(|node| graph.out_weight(node))
+        .collect::<Vec<_>>();
+
+    let mut x = vec![1.0 / nf; n];
+    let mut last = vec![0.0; n];
+
+    for _ in 0..MAX_ITERATIONS {
+        std::mem::swap(&mut x, &mut last);
+
+        // NOTE: dangling nodes redistribute their rank to every node
+        let dangling_sum = damping
+            * (0..n)
+                .filter(|node| out_weights[*node] == 0.0)
+                .map(|node| last[node])
+                .sum::<f64>();
+
+        x.fill(dangling_sum / nf + (1.0 - damping) / nf);
+
+        for node in 0..n {
+            // NOTE: nodes whose edges all have a zero weight are also dangling
+            if out_weights[node] == 0.0 {
+                continue;
+            }
+
+            for (neighbor, weight) in graph.out_neighbors(node) {
+                x[*neighbor] += damping * last[node] * weight / out_weights[node];
+            }
+        }
+
+        if l1_distance(&x, &last) < nf * tolerance {
+            return Ok(x);
+        }
+    }
+
+    Err(format!(
+        "pagerank did not converge after {} iterations!",
+        MAX_ITERATIONS
+    ))
+}
+
+// NOTE: using Kahn's algorithm
+fn is_acyclic(graph: &Adjacency) -> bool {
+    let n = graph.order();
+    let mut in_degrees = vec![0usize; n];
+
+    for node in 0..n {
+        for (neighbor, _) in graph.out_neighbors(node) {
+            in_degrees[*neighbor] += 1;
+        }
+    }
+
+    let mut stack = (0..n)
+        .filter(|node| in_degrees[*node] == 0)
+        .collect::<Vec<_>>();
+
+    let mut visited: usize = 0;
+
+    while let Some(node) = stack.pop() {
+        visited += 1;
+
+        for (neighbor, _) in graph.out_neighbors(node) {
+            in_degrees[*neighbor] -= 1;
+
+            if in_degrees[*neighbor] == 0 {
+                stack.push(*neighbor);
+            }
+        }
+    }
+
+    visited == n
+}
+
+pub fn eigenvector(graph: &Adjacency) -> Result<Vec<f64>, String> {
+    let n = graph.order();
+
+    if n == 0 {
+        return Ok(vec![]);
+    }
+
+    // NOTE: the adjacency matrix of a graph without any cycle is nilpotent,
+    // so its only eigenvalue is 0 and power iteration would never converge.
+    if is_acyclic(graph) {
+        return Ok(vec![0.0; n]);
+    }
+
+    let nf = n as f64;
+    let tolerance = 1e-6;
+
+    let mut x = vec![1.0 / nf; n];
+    let mut last = vec![0.0; n];
+
+    for _ in 0..MAX_ITERATIONS {
+        std::mem::swap(&mut x, &mut last);
+
+        // NOTE: starting from the identity makes sure the iteration converges
+        // even for bipartite graphs
+        x.copy_from_slice(&last);
+
+        for (node, value) in last.iter().enumerate() {
+            for (neighbor, weight) in graph.out_neighbors(node) {
+                x[*neighbor] += value * weight;
+            }
+        }
+
+        let norm = x.iter().map(|v| v * v).sum::<f64>().sqrt();
+
+        if norm > 0.0 {
+            for v in x.iter_mut() {
+                *v /= norm;
+            }
+        }
+
+        if l1_distance(&x, &last) < nf * tolerance {
+            return Ok(x);
+        }
+    }
+
+    Err(format!(
+        "eigenvector centrality did not converge after {} iterations!",
+        MAX_ITERATIONS
+    ))
+}
+
+// NOTE: returns (hubs, authorities)
+pub fn hits(graph: &Adjacency) -> Result<(Vec<f64>, Vec<f64>), String> {
+    let n = graph.order();
+
+    if n == 0 {
+        return Ok((vec![], vec![]));
+    }
+
+    let tolerance = 1e-8;
+
+    let mut hubs = vec![1.0 / n as f64; n];
+    let mut last = vec![0.0; n];
+    let mut authorities = vec![0.0; n];
+
+    for _ in 0..MAX_ITERATIONS {
+        std::mem::swap(&mut hubs, &mut last);
+
+        hubs.fill(0.0);
+        authorities.fill(0.0);
+
+        for (node, hub) in last.iter().enumerate() {
+            for (neighbor, weight) in graph.out_neighbors(node) {
+                authorities[*neighbor] += hub * weight;
+            }
+        }
+
+        for (node, hub) in hubs.iter_mut().enumerate() {
+            for (neighbor, weight) in graph.out_neighbors(node) {
+                *hub += authorities[*neighbor] * weight;
+            }
+        }
+
+        normalize_by_max(&mut hubs);
+        normalize_by_max(&mut authorities);
+
+        if l1_distance(&hubs, &last) < tolerance {
+            normalize_by_sum(&mut hubs);
+            normalize_by_sum(&mut authorities);
+
+            return Ok((hubs, authorities));
+        }
+    }
+
+    Err(format!(
+        "hits did not converge after {} iterations!",
+        MAX_ITERATIONS
+    ))
+}
+
+// NOTE: breadth-first search returning the nodes in order of discovery,
+// along with their distance to the source & their number of shortest paths.
+struct ShortestPaths {
+    stack: Vec<usize>,
+    predecessors: Vec<Vec<usize>>,
+    sigma: Vec<f64>,
+    distances: Vec<Option<usize>>,
+    queue: VecDeque<usize>,
+}
+
+impl ShortestPaths {
+    fn new(n: usize) -> Self {
+        Self {
+            stack: Vec::with_capacity(n),
+            predecessors: vec![Vec::new(); n],
+            sigma: vec![0.0; n],
+            distances: vec![None; n],
+            queue: VecDeque::new(),
+        }
+    }
+
+    fn run(&mut self, graph: &Adjacency, source: usize) {
+        self.stack.clear();
+        self.predecessors.iter_mut().for_each(Vec::clear);
+        self.sigma.fill(0.0);
+        self.distances.fill(None);
+
+        self.sigma[source] = 1.0;
+        self.distances[source] = Some(0);
+        self.queue.push_back(source);
+
+        while let Some(node) = self.queue.pop_front() {
+            self.stack.push(node);
+
+            let distance = self.distances[node].unwrap();
+
+            for (neighbor, _) in graph.out_neighbors(node) {
+                let neighbor = *neighbor;
+
+                match self.distances[neighbor] {
+                    None => {
+                        self.distances[neighbor] = Some(distance + 1);
+                        self.queue.push_back(neighbor);
+                    }
+                    Some(d) if d != distance + 1 => continue,
+                    _ => (),
+                }
+
+                self.sigma[neighbor] += self.sigma[node];
+                self.predecessors[neighbor].push(node);
+            }
+        }
+    }
+}
+
+// NOTE: `sources` can be given to approximate the metric using a sample of
+// source nodes instead of all of them.
+pub fn betweenness(graph: &Adjacency, sources: Option<&[usize]>) -> Vec<f64> {
+    let n = graph.order();
+
+    let mut centralities = vec![0.0; n];
+    let mut paths = ShortestPaths::new(n);
+    let mut delta = vec![0.0; n];
+
+    let all_sources = (0..n).collect::<Vec<_>>();
+    let sources = sources.unwrap_or(&all_sources);
+
+    for source in sources.iter().copied() {
+        paths.run(graph, source);
+        delta.fill(0.0);
+
+        while let Some(node) = paths.stack.pop() {
+            let coefficient = (1.0 + delta[node]) / paths.sigma[node];
+
+            for predecessor in paths.predecessors[node].iter() {
+                delta[*predecessor] += paths.sigma[*predecessor] * coefficient;
+            }
+
+            if node != source {
+                centralities[node] += delta[node];
+            }
+        }
+    }
+
+    if n > 2 {
+        let mut scale = 1.0 / ((n - 1) * (n - 2)) as f64;
+
+        if sources.len() < n {
+            scale *= n as f64 / sources.len() as f64;
+        }
+
+        for c in centralities.iter_mut() {
+            *c *= scale;
+        }
+    }
+
+    centralities
+}
+
+// NOTE: using Wasserman & Faust's formula to deal with disconnected graphs
+pub fn closeness(graph: &Adjacency) -> Vec<f64> {
+    let n = graph.order();
+
+    let mut centralities = vec![0.0; n];
+    let mut paths = ShortestPaths::new(n);
+
+    for (node, centrality) in centralities.iter_mut().enumerate() {
+        paths.run(graph, node);
+
+        let mut reachable: usize = 0;
+        let mut total_distance: usize = 0;
+
+        for distance in paths.distances.iter().flatten() {
+            if *distance > 0 {
+                reachable += 1;
+                total_distance += distance;
+            }
+        }
+
+        if total_distance > 0 {
+            let r = reachable as f64;
+
+            *centrality = (r / total_distance as f64) * (r / (n - 1) as f64);
+        }
+    }
+
+    centralities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_approx(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < 1e-4, "{:?} != {:?}", a, b);
+        }
+    }
+
+    fn path() -> Adjacency {
+        Adjacency::new(3, false, [(0, 1, 1.0), (1, 2, 1.0)].into_iter())
+    }
+
+    #[test]
+    fn test_pagerank() {
+        let cycle = Adjacency::new(3, true, [(0, 1, 1.0), (1, 2, 1.0), (2, 0, 1.0)].into_iter());
+
+        assert_approx(&pagerank(&cycle, 0.85).unwrap(), &[1.0 / 3.0; 3]);
+        assert_approx(&pagerank(&path(), 0.85).unwrap(), &[0.2567, 0.4865, 0.2567]);
+    }
+
+    #[test]
+    fn test_betweenness() {
+        assert_approx(&betweenness(&path(), None), &[0.0, 1.0, 0.0]);
+
+        let directed = Adjacency::new(3, true, [(0, 1, 1.0), (1, 2, 1.0)].into_iter());
+
+        assert_approx(&betweenness(&directed, None), &[0.0, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn test_closeness() {
+        assert_approx(&closeness(&path()), &[2.0 / 3.0, 1.0, 2.0 / 3.0]);
+    }
+
+    #[test]
+    fn test_eigenvector() {
+        let triangle = Adjacency::new(
+            3,
+            false,
+            [(0, 1, 1.0), (1, 2, 1.0), (0, 2, 1.0)].into_iter(),
+        );
+
+        assert_approx(&eigenvector(&triangle).unwrap(), &[1.0 / 3f64.sqrt(); 3]);
+
+        let path = Adjacency::new(4, true, [(0, 1, 1.0), (1, 2, 1.0), (2, 3, 1.0)].into_iter());
+
+        assert_eq!(eigenvector(&path).unwrap(), vec![0.0; 4]);
+    }
+
+    #[test]
+    fn test_is_acyclic() {
+        let path = Adjacency::new(3, true, [(0, 1, 1.0), (1, 2, 1.0)].into_iter());
+        let cycle = Adjacency::new(3, true, [(0, 1, 1.0), (1, 2, 1.0), (2, 0, 1.0)].into_iter());
+        let self_loop = Adjacency::new(2, true, [(0, 1, 1.0), (1, 1, 1.0)].into_iter());
+
+        assert!(is_acyclic(&path));
+        assert!(!is_acyclic(&cycle));
+        assert!(!is_acyclic(&self_loop));
+    }
+
+    #[test]
+    fn test_hits() {
+        let star = Adjacency::new(3, true, [(0, 1, 1.0), (0, 2, 1.0)].into_iter());
+
+        let (hubs, authorities) = hits(&star).unwrap();
+
+        assert_approx(&hubs, &[1.0, 0.0, 0.0]);
+        assert_approx(&authorities, &[0.0, 0.5, 0.5]);
+    }
+}
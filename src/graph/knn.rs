@@ -0,0 +1,159 @@
+// Exact k-nearest neighbors graph construction. Every pair of vectors is
+// compared, which is fine for the few thousand rows such graphs are usually
+// built from.
+use std::convert::TryFrom;
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(try_from = "String")]
+pub enum KnnMetric {
+    Cosine,
+    Euclidean,
+}
+
+impl TryFrom<String> for KnnMetric {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(match value.as_str() {
+            "cosine" => Self::Cosine,
+            "euclidean" => Self::Euclidean,
+            _ => return Err(format!("unknown metric \"{}\"!", value)),
+        })
+    }
+}
+
+pub struct KnnOptions {
+    pub metric: KnnMetric,
+    pub k: usize,
+    pub min_similarity: Option<f64>,
+}
+
+fn norm(vector: &[f64]) -> f64 {
+    vector.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+// NOTE: euclidean distances are turned into similarities as 1 / (1 + d), so
+// that both metrics yield weights where higher means closer. Cosine similarity
+// is undefined for null vectors, which are therefore never linked.
+fn similarity(metric: KnnMetric, a: &[f64], b: &[f64], norms: (f64, f64)) -> Option<f64> {
+    Some(match metric {
+        KnnMetric::Cosine => {
+            if norms.0 == 0.0 || norms.1 == 0.0 {
+                return None;
+            }
+
+            a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>() / (norms.0 * norms.1)
+        }
+        KnnMetric::Euclidean => {
+            let distance = a
+                .iter()
+                .zip(b)
+                .map(|(x, y)| (x - y).powi(2))
+                .sum::<f64>()
+                .sqrt();
+
+            1.0 / (1.0 + distance)
+        }
+    })
+}
+
+// NOTE: returns, for each vector, the edges linking it to its k most similar
+// vectors, by decreasing similarity. Ties are broken using vector order.
+pub fn knn(vectors: &[Vec<f64>], options: &KnnOptions) -> Vec<(usize, usize, f64)> {
+    let norms = vectors.iter().map(|v| norm(v)).collect::<Vec<_>>();
+
+    let mut edges = Vec::new();
+    let mut candidates: Vec<(usize, f64)> = Vec::with_capacity(vectors.len());
+
+    let compare = |a: &(usize, f64), b: &(usize, f64)| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0));
+
+    for (i, vector) in vectors.iter().enumerate() {
+        candidates.clear();
+
+        for (j, other) in vectors.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+
+            let Some(s) = similarity(options.metric, vector, other, (norms[i], norms[j])) else {
+                continue;
+            };
+
+            if matches!(options.min_similarity, Some(min_similarity) if s < min_similarity) {
+                continue;
+            }
+
+            candidates.push((j, s));
+        }
+
+        if candidates.len() > options.k {
+            candidates.select_nth_unstable_by(options.k, compare);
+            candidates.truncate(options.k);
+        }
+
+        candidates.sort_unstable_by(compare);
+
+        edges.extend(candidates.iter().map(|(j, s)| (i, *j, *s)));
+    }
+
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(metric: KnnMetric, k: usize) -> KnnOptions {
+        KnnOptions {
+            metric,
+            k,
+            min_similarity: None,
+        }
+    }
+
+    #[test]
+    fn test_knn() {
+        let vectors = vec![
+            vec![1.0, 0.0],
+            vec![2.0, 0.1],
+            vec![0.0, 1.0],
+            vec![0.0, 0.0],
+        ];
+
+        let edges = knn(&vectors, &options(KnnMetric::Cosine, 1));
+
+        assert_eq!(
+            edges.iter().map(|(i, j, _)| (*i, *j)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0), (2, 1)]
+        );
+        assert!((edges[0].2 - 2.0 / 4.01f64.sqrt()).abs() < 1e-12);
+
+        let edges = knn(&vectors, &options(KnnMetric::Euclidean, 2));
+
+        assert_eq!(
+            edges.iter().map(|(i, j, _)| (*i, *j)).collect::<Vec<_>>(),
+            vec![
+                (0, 3),
+                (0, 1),
+                (1, 0),
+                (1, 3),
+                (2, 3),
+                (2, 0),
+                (3, 0),
+                (3, 2)
+            ]
+        );
+        assert_eq!(edges[0].2, 0.5);
+
+        let mut thresholded = options(KnnMetric::Cosine, 10);
+        thresholded.min_similarity = Some(0.5);
+
+        assert_eq!(
+            knn(&vectors, &thresholded)
+                .iter()
+                .map(|(i, j, _)| (*i, *j))
+                .collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+    }
+}
@@ -0,0 +1,521 @@
+// Force-directed layouts. ForceAtlas2 follows the description found in:
+// Jacomy, M., Venturini, T., Heymann, S., & Bastian, M. (2014). ForceAtlas2,
+// a Continuous Graph Layout Algorithm for Handy Network Visualization.
+// Fruchterman-Reingold follows networkx's implementation.
+//
+// NOTE: both algorithms can approximate repulsion using a Barnes-Hut
+// quadtree, which is necessary to scale to large graphs.
+use std::convert::TryFrom;
+
+use rand::{Rng, RngExt};
+
+use super::Adjacency;
+
+const MAX_QUADTREE_DEPTH: usize = 32;
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(try_from = "String")]
+pub enum LayoutAlgorithm {
+    ForceAtlas2,
+    FruchtermanReingold,
+}
+
+impl TryFrom<String> for LayoutAlgorithm {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(match value.as_str() {
+            "forceatlas2" => Self::ForceAtlas2,
+            "fruchterman-reingold" => Self::FruchtermanReingold,
+            _ => return Err(format!("unknown layout \"{}\"!", value)),
+        })
+    }
+}
+
+pub struct LayoutOptions {
+    pub algorithm: LayoutAlgorithm,
+    pub iterations: usize,
+    pub barnes_hut_theta: Option<f64>,
+}
+
+#[derive(Default)]
+struct Cell {
+    x: f64,
+    y: f64,
+    size: f64,
+    mass: f64,
+    mass_x: f64,
+    mass_y: f64,
+    body: Option<usize>,
+    children: Option<[usize; 4]>,
+}
+
+impl Cell {
+    fn new(x: f64, y: f64, size: f64) -> Self {
+        Self {
+            x,
+            y,
+            size,
+            ..Self::default()
+        }
+    }
+
+    fn center_of_mass(&self) -> (f64, f64) {
+        (self.mass_x / self.mass, self.mass_y / self.mass)
+    }
+}
+
+struct QuadTree {
+    cells: Vec<Cell>,
+}
+
+impl QuadTree {
+    fn new(positions: &[(f64, f64)], masses: &[f64]) -> Self {
+        let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+        let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+
+        for (x, y) in positions.iter().copied() {
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+
+        let size = (max_x - min_x).max(max_y - min_y).max(f64::EPSILON);
+
+        let mut tree = Self {
+            cells: vec![Cell::new(min_x, min_y, size)],
+        };
+
+        for (body, (position, mass)) in positions.iter().zip(masses.iter()).enumerate() {
+            tree.insert(0, body, *position, *mass, positions, 0);
+        }
+
+        tree
+    }
+
+    fn quadrant(&self, cell: usize, (x, y): (f64, f64)) -> usize {
+        let cell = &self.cells[cell];
+        let half = cell.size / 2.0;
+
+        (x >= cell.x + half) as usize + 2 * (y >= cell.y + half) as usize
+    }
+
+    fn subdivide(&mut self, cell: usize) -> [usize; 4] {
+        let (x, y, half) = {
+            let cell = &self.cells[cell];
+            (cell.x, cell.y, cell.size / 2.0)
+        };
+
+        let first = self.cells.len();
+
+        for i in 0..4 {
+            self.cells.push(Cell::new(
+                x + half * (i % 2) as f64,
+                y + half * (i / 2) as f64,
+                half,
+            ));
+        }
+
+        let children = [first, first + 1, first + 2, first + 3];
+        self.cells[cell].children = Some(children);
+
+        children
+    }
+
+    fn insert(
+        &mut self,
+        cell: usize,
+        body: usize,
+        position: (f64, f64),
+        mass: f64,
+        positions: &[(f64, f64)],
+        depth: usize,
+    ) {
+        let was_empty = self.cells[cell].mass == 0.0;
+        let previous_body = self.cells[cell].body;
+
+        {
+            let cell = &mut self.cells[cell];
+            cell.mass += mass;
+            cell.mass_x += position.0 * mass;
+            cell.mass_y += position.1 * mass;
+        }
+
+        if let Some(children) = self.cells[cell].children {
+            let child = children[self.quadrant(cell, position)];
+            self.insert(child, body, position, mass, positions, depth + 1);
+            return;
+        }
+
+        if was_empty {
+            self.cells[cell].body = Some(body);
+            return;
+        }
+
+        // NOTE: past some depth, bodies, which are probably overlapping, are
+        // aggregated in the same leaf
+        if depth >= MAX_QUADTREE_DEPTH {
+            self.cells[cell].body = None;
+            return;
+        }
+
+        let children = self.subdivide(cell);
+        self.cells[cell].body = None;
+
+        if let Some(previous_body) = previous_body {
+            let previous_position = positions[previous_body];
+            let previous_mass = {
+                let cell = &self.cells[cell];
+                cell.mass - mass
+            };
+
+            let child = children[self.quadrant(cell, previous_position)];
+            self.insert(
+                child,
+                previous_body,
+                previous_position,
+                previous_mass,
+                positions,
+                depth + 1,
+            );
+        }
+
+        let child = children[self.quadrant(cell, position)];
+        self.insert(child, body, position, mass, positions, depth + 1);
+    }
+
+    // NOTE: accumulates sum(mass_j * (p - p_j) / d²) over the other bodies,
+    // approximating far away cells by their center of mass.
+    fn repulsion(&self, body: usize, position: (f64, f64), theta: f64) -> (f64, f64) {
+        let mut force = (0.0, 0.0);
+        let mut stack = vec![0];
+
+        while let Some(index) = stack.pop() {
+            let cell = &self.cells[index];
+
+            if cell.mass == 0.0 || cell.body == Some(body) {
+                continue;
+            }
+
+            let (cx, cy) = cell.center_of_mass();
+            let (dx, dy) = (position.0 - cx, position.1 - cy);
+            let distance_squared = dx * dx + dy * dy;
+
+            match cell.children {
+                Some(children) if cell.size * cell.size >= theta * theta * distance_squared => {
+                    stack.extend(children);
+                }
+                _ => {
+                    if distance_squared > f64::EPSILON {
+                        force.0 += cell.mass * dx / distance_squared;
+                        force.1 += cell.mass * dy / distance_squared;
+                    }
+                }
+            }
+        }
+
+        force
+    }
+}
+
+// NOTE: same quantity as `QuadTree::repulsion`, computed exactly
+fn exact_repulsion(body: usize, positions: &[(f64, f64)], masses: &[f64]) -> (f64, f64) {
+    let mut force = (0.0, 0.0);
+    let (x, y) = positions[body];
+
+    for (other, ((ox, oy), mass)) in positions.iter().zip(masses.iter()).enumerate() {
+        if other == body {
+            continue;
+        }
+
+        let (dx, dy) = (x - ox, y - oy);
+        let distance_squared = dx * dx + dy * dy;
+
+        if distance_squared > f64::EPSILON {
+            force.0 += mass * dx / distance_squared;
+            force.1 += mass * dy / distance_squared;
+        }
+    }
+
+    force
+}
+
+fn compute_repulsions(
+    positions: &[(f64, f64)],
+    masses: &[f64],
+    theta: Option<f64>,
+) -> Vec<(f64, f64)> {
+    match theta {
+        Some(theta) => {
+            let tree = QuadTree::new(positions, masses);
+
+            positions
+                .iter()
+                .enumerate()
+                .map(|(body, position)| tree.repulsion(body, *position, theta))
+                .collect()
+        }
+        None => (0..positions.len())
+            .map(|body| exact_repulsion(body, positions, masses))
+            .collect(),
+    }
+}
+
+fn forceatlas2(graph: &Adjacency, positions: &mut [(f64, f64)], options: &LayoutOptions) {
+    let n = graph.order();
+
+    // NOTE: settings inferred like graphology does
+    let scaling_ratio = if n > 100 { 2.0 } else { 10.0 };
+    let gravity = 1.0;
+    let jitter_tolerance = 1.0;
+
+    let masses = (0..n)
+        .map(|node| graph.out_neighbors(node).len() as f64 + 1.0)
+        .collect::<Vec<_>>();
+
+    let mut forces = vec![(0.0, 0.0); n];
+    let mut previous_forces = vec![(0.0, 0.0); n];
+
+    let mut speed = 1.0;
+    let mut speed_efficiency = 1.0;
+
+    for _ in 0..options.iterations {
+        std::mem::swap(&mut forces, &mut previous_forces);
+
+        let repulsions = compute_repulsions(positions, &masses, options.barnes_hut_theta);
+
+        for (node, force) in forces.iter_mut().enumerate() {
+            let (x, y) = positions[node];
+            let mass = masses[node];
+
+            // Repulsion
+            let (rx, ry) = repulsions[node];
+            *force = (scaling_ratio * mass * rx, scaling_ratio * mass * ry);
+
+            // Gravity
+            let distance = (x * x + y * y).sqrt();
+
+            if distance > 0.0 {
+                force.0 -= gravity * mass * x / distance;
+                force.1 -= gravity * mass * y / distance;
+            }
+
+            // Attraction
+            for (neighbor, weight) in graph.out_neighbors(node) {
+                let (nx, ny) = positions[*neighbor];
+
+                force.0 -= weight * (x - nx);
+                force.1 -= weight * (y - ny);
+            }
+        }
+
+        // Adaptive speed, as implemented by Gephi
+        let mut total_swinging = 0.0;
+        let mut total_traction = 0.0;
+
+        for ((force, previous), mass) in forces.iter().zip(previous_forces.iter()).zip(&masses) {
+            let (sx, sy) = (force.0 - previous.0, force.1 - previous.1);
+            let (tx, ty) = (force.0 + previous.0, force.1 + previous.1);
+
+            total_swinging += mass * (sx * sx + sy * sy).sqrt();
+            total_traction += mass * (tx * tx + ty * ty).sqrt() / 2.0;
+        }
+
+        let nf = n as f64;
+        let estimated_optimal_jitter_tolerance = 0.05 * nf.sqrt();
+        let min_jitter = estimated_optimal_jitter_tolerance.sqrt();
+        let max_jitter: f64 = 10.0;
+
+        let mut jitter = jitter_tolerance
+            * min_jitter.max(
+                max_jitter.min(estimated_optimal_jitter_tolerance * total_traction / (nf * nf)),
+            );
+
+        let min_speed_efficiency = 0.05;
+
+        if total_traction > 0.0 && total_swinging / total_traction > 2.0 {
+            if speed_efficiency > min_speed_efficiency {
+                speed_efficiency *= 0.5;
+            }
+
+            jitter = jitter.max(jitter_tolerance);
+        }
+
+        let target_speed = if total_swinging > 0.0 {
+            jitter * speed_efficiency * total_traction / total_swinging
+        } else {
+            speed
+        };
+
+        if total_swinging > jitter * total_traction {
+            if speed_efficiency > min_speed_efficiency {
+                speed_efficiency *= 0.7;
+            }
+        } else if speed < 1000.0 {
+            speed_efficiency *= 1.3;
+        }
+
+        let max_rise = 0.5;
+        speed += (target_speed - speed).min(max_rise * speed);
+
+        for (node, position) in positions.iter_mut().enumerate() {
+            let (force, previous) = (forces[node], previous_forces[node]);
+            let (sx, sy) = (force.0 - previous.0, force.1 - previous.1);
+            let swinging = masses[node] * (sx * sx + sy * sy).sqrt();
+
+            let factor = speed / (1.0 + (speed * swinging).sqrt());
+
+            position.0 += force.0 * factor;
+            position.1 += force.1 * factor;
+        }
+    }
+}
+
+fn fruchterman_reingold(graph: &Adjacency, positions: &mut [(f64, f64)], options: &LayoutOptions) {
+    let n = graph.order();
+
+    let k = (1.0 / n as f64).sqrt();
+    let masses = vec![1.0; n];
+
+    // NOTE: the temperature decreases linearly until the last iteration
+    let mut temperature = 0.1;
+    let cooling = temperature / (options.iterations + 1) as f64;
+
+    for _ in 0..options.iterations {
+        let repulsions = compute_repulsions(positions, &masses, options.barnes_hut_theta);
+
+        let displacements = (0..n)
+            .map(|node| {
+                let (x, y) = positions[node];
+                let (rx, ry) = repulsions[node];
+
+                let mut displacement = (k * k * rx, k * k * ry);
+
+                for (neighbor, weight) in graph.out_neighbors(node) {
+                    let (nx, ny) = positions[*neighbor];
+                    let (dx, dy) = (x - nx, y - ny);
+                    let distance = (dx * dx + dy * dy).sqrt();
+
+                    displacement.0 -= weight * dx * distance / k;
+                    displacement.1 -= weight * dy * distance / k;
+                }
+
+                displacement
+            })
+            .collect::<Vec<_>>();
+
+        for (position, (dx, dy)) in positions.iter_mut().zip(displacements) {
+            let length = (dx * dx + dy * dy).sqrt();
+
+            if length > 0.0 {
+                let factor = length.min(temperature) / length;
+
+                position.0 += dx * factor;
+                position.1 += dy * factor;
+            }
+        }
+
+        temperature -= cooling;
+    }
+}
+
+// NOTE: the graph is considered as undirected & positions are initialized
+// at random in the unit square.
+pub fn layout<R: Rng + ?Sized>(
+    graph: &Adjacency,
+    options: &LayoutOptions,
+    rng: &mut R,
+) -> Vec<(f64, f64)> {
+    let mut positions = (0..graph.order())
+        .map(|_| (rng.random::<f64>(), rng.random::<f64>()))
+        .collect::<Vec<_>>();
+
+    if positions.len() < 2 {
+        return positions;
+    }
+
+    match options.algorithm {
+        LayoutAlgorithm::ForceAtlas2 => forceatlas2(graph, &mut positions, options),
+        LayoutAlgorithm::FruchtermanReingold => {
+            fruchterman_reingold(graph, &mut positions, options)
+        }
+    }
+
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::util;
+
+    fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+        ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+    }
+
+    #[test]
+    fn test_quadtree() {
+        let positions = vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (5.0, 5.0), (5.0, 5.0)];
+        let masses = vec![1.0, 2.0, 1.0, 1.0, 3.0];
+
+        // With theta = 0, Barnes-Hut must be exact
+        let approximated = compute_repulsions(&positions, &masses, Some(0.0));
+        let exact = compute_repulsions(&positions, &masses, None);
+
+        for (a, b) in approximated.iter().zip(exact.iter()) {
+            assert!(distance(*a, *b) < 1e-9, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_layout() {
+        // Two triangles linked by a single edge
+        let graph = Adjacency::new(
+            6,
+            false,
+            [
+                (0, 1, 1.0),
+                (1, 2, 1.0),
+                (0, 2, 1.0),
+                (3, 4, 1.0),
+                (4, 5, 1.0),
+                (3, 5, 1.0),
+                (2, 3, 1.0),
+            ]
+            .into_iter(),
+        );
+
+        for algorithm in [
+            LayoutAlgorithm::ForceAtlas2,
+            LayoutAlgorithm::FruchtermanReingold,
+        ] {
+            for barnes_hut_theta in [None, Some(0.5)] {
+                let options = LayoutOptions {
+                    algorithm,
+                    iterations: 200,
+                    barnes_hut_theta,
+                };
+
+                let mut rng = util::acquire_rng(Some(1));
+                let positions = layout(&graph, &options, &mut rng);
+
+                assert!(
+                    positions
+                        .iter()
+                        .all(|(x, y)| x.is_finite() && y.is_finite())
+                );
+
+                // Nodes of a same triangle should be closer than nodes of
+                // different triangles
+                assert!(
+                    distance(positions[0], positions[1]) < distance(positions[0], positions[5])
+                );
+                assert!(
+                    distance(positions[4], positions[5]) < distance(positions[1], positions[4])
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,265 @@
+// Louvain community detection, as described in:
+// Blondel, V. D., Guillaume, J.-L., Lambiotte, R., & Lefebvre, E. (2008).
+// Fast unfolding of communities in large networks.
+//
+// NOTE: the graph is considered as undirected and self loops are counted
+// twice in node degrees, like networkx does.
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use super::Adjacency;
+
+struct LevelGraph {
+    neighbors: Vec<Vec<(usize, f64)>>,
+    self_loops: Vec<f64>,
+    degrees: Vec<f64>,
+}
+
+impl LevelGraph {
+    fn from_adjacency(graph: &Adjacency) -> Self {
+        let n = graph.order();
+
+        let mut neighbors = vec![Vec::new(); n];
+        let mut self_loops = vec![0.0; n];
+
+        for (node, node_neighbors) in neighbors.iter_mut().enumerate() {
+            for (neighbor, weight) in graph.out_neighbors(node) {
+                if *neighbor == node {
+                    self_loops[node] += 2.0 * weight;
+                } else {
+                    node_neighbors.push((*neighbor, *weight));
+                }
+            }
+        }
+
+        Self::new(neighbors, self_loops)
+    }
+
+    fn new(neighbors: Vec<Vec<(usize, f64)>>, self_loops: Vec<f64>) -> Self {
+        let degrees = neighbors
+            .iter()
+            .zip(self_loops.iter())
+            .map(|(node_neighbors, self_loop)| {
+                node_neighbors.iter().map(|(_, w)| w).sum::<f64>() + self_loop
+            })
+            .collect();
+
+        Self {
+            neighbors,
+            self_loops,
+            degrees,
+        }
+    }
+
+    fn order(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    // NOTE: returns the community of each node, numbered from 0 in order of
+    // first appearance, and whether some node was moved
+    fn move_nodes<R: Rng + ?Sized>(
+        &self,
+        resolution: f64,
+        total_weight: f64,
+        rng: &mut R,
+    ) -> (Vec<usize>, bool) {
+        let n = self.order();
+
+        let mut communities = (0..n).collect::<Vec<_>>();
+        let mut totals = self.degrees.clone();
+
+        let mut order = (0..n).collect::<Vec<_>>();
+        order.shuffle(rng);
+
+        let mut neighbor_weights = vec![0.0; n];
+        let mut touched: Vec<usize> = Vec::new();
+        let mut is_touched = vec![false; n];
+
+        let mut moved = false;
+
+        loop {
+            let mut moves: usize = 0;
+
+            for node in order.iter().copied() {
+                let current = communities[node];
+                let degree = self.degrees[node];
+
+                for (neighbor, weight) in self.neighbors[node].iter() {
+                    let community = communities[*neighbor];
+
+                    if !is_touched[community] {
+                        is_touched[community] = true;
+                        touched.push(community);
+                    }
+
+                    neighbor_weights[community] += weight;
+                }
+
+                totals[current] -= degree;
+
+                let gain = |community: usize| {
+                    neighbor_weights[community]
+                        - resolution * totals[community] * degree / total_weight
+                };
+
+                let mut best = current;
+                let mut best_gain = gain(current);
+
+                for community in touched.iter().copied() {
+                    let community_gain = gain(community);
+
+                    if community_gain > best_gain + 1e-12 {
+                        best = community;
+                        best_gain = community_gain;
+                    }
+                }
+
+                totals[best] += degree;
+                communities[node] = best;
+
+                if best != current {
+                    moves += 1;
+                }
+
+                for community in touched.drain(..) {
+                    is_touched[community] = false;
+                    neighbor_weights[community] = 0.0;
+                }
+            }
+
+            if moves == 0 {
+                break;
+            }
+
+            moved = true;
+        }
+
+        let mut labels = vec![usize::MAX; n];
+        let mut next_label: usize = 0;
+
+        for community in communities.iter_mut() {
+            if labels[*community] == usize::MAX {
+                labels[*community] = next_label;
+                next_label += 1;
+            }
+
+            *community = labels[*community];
+        }
+
+        (communities, moved)
+    }
+
+    fn aggregate(&self, communities: &[usize]) -> Self {
+        let k = communities
+            .iter()
+            .copied()
+            .max()
+            .map(|m| m + 1)
+            .unwrap_or(0);
+
+        let mut neighbors = vec![Vec::new(); k];
+        let mut self_loops = vec![0.0; k];
+
+        for (node, node_neighbors) in self.neighbors.iter().enumerate() {
+            let community = communities[node];
+
+            self_loops[community] += self.self_loops[node];
+
+            for (neighbor, weight) in node_neighbors {
+                let neighbor_community = communities[*neighbor];
+
+                if neighbor_community == community {
+                    self_loops[community] += weight;
+                } else {
+                    neighbors[community].push((neighbor_community, *weight));
+                }
+            }
+        }
+
+        for community_neighbors in neighbors.iter_mut() {
+            community_neighbors.sort_by_key(|(neighbor, _)| *neighbor);
+            community_neighbors.dedup_by(|current, previous| {
+                if current.0 == previous.0 {
+                    previous.1 += current.1;
+                    true
+                } else {
+                    false
+                }
+            });
+        }
+
+        Self::new(neighbors, self_loops)
+    }
+}
+
+pub fn louvain<R: Rng + ?Sized>(graph: &Adjacency, resolution: f64, rng: &mut R) -> Vec<usize> {
+    let mut level = LevelGraph::from_adjacency(graph);
+    let mut memberships = (0..graph.order()).collect::<Vec<_>>();
+
+    let total_weight = level.degrees.iter().sum::<f64>();
+
+    if total_weight == 0.0 {
+        return memberships;
+    }
+
+    loop {
+        let (communities, moved) = level.move_nodes(resolution, total_weight, rng);
+
+        for membership in memberships.iter_mut() {
+            *membership = communities[*membership];
+        }
+
+        if !moved {
+            break;
+        }
+
+        level = level.aggregate(&communities);
+    }
+
+    memberships
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::util;
+
+    #[test]
+    fn test_louvain() {
+        // Two triangles linked by a single edge
+        let graph = Adjacency::new(
+            6,
+            false,
+            [
+                (0, 1, 1.0),
+                (1, 2, 1.0),
+                (0, 2, 1.0),
+                (3, 4, 1.0),
+                (4, 5, 1.0),
+                (3, 5, 1.0),
+                (2, 3, 1.0),
+            ]
+            .into_iter(),
+        );
+
+        let mut rng = util::acquire_rng(Some(123));
+        let communities = louvain(&graph, 1.0, &mut rng);
+
+        assert_eq!(communities[0], communities[1]);
+        assert_eq!(communities[1], communities[2]);
+        assert_eq!(communities[3], communities[4]);
+        assert_eq!(communities[4], communities[5]);
+        assert_ne!(communities[0], communities[3]);
+
+        // Very low resolution should merge everything
+        let communities = louvain(&graph, 0.01, &mut rng);
+
+        assert!(communities.iter().all(|c| *c == communities[0]));
+
+        // Edgeless graph
+        let graph = Adjacency::new(3, false, std::iter::empty());
+
+        assert_eq!(louvain(&graph, 1.0, &mut rng), vec![0, 1, 2]);
+    }
+}
@@ -1,3 +1,13 @@
+mod adjacency;
+mod centrality;
+mod knn;
+mod layout;
+mod louvain;
+mod paths;
+mod projection;
+mod readers;
+mod view;
+
 use std::borrow::Cow;
 use std::cell::RefCell;
 use std::io::Write;
@@ -5,6 +15,7 @@ use std::ops::Not;
 
 use jiff::Zoned;
 use phylacter::UnionFind;
+use rand::seq::SliceRandom;
 use serde::ser::{SerializeMap, SerializeSeq, Serializer as _};
 use serde_json::{
     Value,
@@ -17,8 +28,15 @@ use crate::collections::{
 };
 use crate::config::Config;
 use crate::json::{Attributes, INTERNER, JSONType};
+use crate::util;
 use crate::xml::XMLWriter;
 
+pub use adjacency::Adjacency;
+pub use knn::{KnnMetric, KnnOptions, knn};
+pub use layout::{LayoutAlgorithm, LayoutOptions};
+pub use projection::{ProjectionOptions, ProjectionWeighting, project};
+pub use readers::{GraphTables, read_gexf, read_graphml, read_graphology};
+
 fn density(graph_type: GraphType, order: usize, size: usize) -> f64 {
     match graph_type {
         GraphType::Directed => size as f64 / (order * order.saturating_sub(1)) as f64,
@@ -45,11 +63,45 @@ impl JSONType {
             Self::Null => "string",
         }
     }
+
+    fn as_graphml_type(&self) -> &str {
+        match self {
+            Self::Float => "double",
+            Self::Integer => "long",
+            Self::String => "string",
+            Self::Null => "string",
+        }
+    }
+}
+
+fn escape_dot_id(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len() + 2);
+
+    escaped.push('"');
+
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+
+    escaped
+}
+
+// NOTE: pajek has no way to escape double quotes in labels
+fn escape_pajek_label(string: &str) -> String {
+    format!("\"{}\"", string.replace('"', "'"))
 }
 
 struct GexfNamespace {
     version: &'static str,
     xmlns: &'static str,
+    viz: &'static str,
     schema_location: &'static str,
 }
 
@@ -58,6 +110,7 @@ impl GexfNamespace {
         Self {
             version: "1.2",
             xmlns: "http://www.gexf.net/1.2draft",
+            viz: "http://www.gexf.net/1.2draft/viz",
             schema_location: "http://www.gexf.net/1.2draft http://www.gexf.net/1.2draft/gexf.xsd",
         }
     }
@@ -66,6 +119,7 @@ impl GexfNamespace {
         Self {
             version: "1.3",
             xmlns: "http://gexf.net/1.3",
+            viz: "http://gexf.net/1.3/viz",
             schema_location: "http://gexf.net/1.3 http://gexf.net/1.3/gexf.xsd",
         }
     }
@@ -308,6 +362,90 @@ impl EdgeStore {
     }
 }
 
+#[derive(Default)]
+pub struct CentralityOptions {
+    pub weight: Option<String>,
+    pub pagerank: bool,
+    pub damping: f64,
+    pub betweenness: bool,
+    pub betweenness_samples: Option<usize>,
+    pub seed: Option<usize>,
+    pub closeness: bool,
+    pub eigenvector: bool,
+    pub hits: bool,
+}
+
+impl CentralityOptions {
+    pub fn is_empty(&self) -> bool {
+        !(self.pagerank || self.betweenness || self.closeness || self.eigenvector || self.hits)
+    }
+}
+
+#[derive(Default)]
+struct Centralities {
+    columns: Vec<(&'static str, Vec<f64>)>,
+}
+
+impl Centralities {
+    fn compute(adjacency: &Adjacency, options: &CentralityOptions) -> CliResult<Self> {
+        let has_invalid_weights = (0..adjacency.order()).any(|node| {
+            adjacency
+                .out_neighbors(node)
+                .iter()
+                .any(|(_, weight)| !weight.is_finite() || *weight < 0.0)
+        });
+
+        if has_invalid_weights {
+            Err("edge weights must be finite & non-negative to compute centrality metrics!")?;
+        }
+
+        let mut columns = Vec::new();
+
+        if options.pagerank {
+            columns.push((
+                "pagerank",
+                centrality::pagerank(adjacency, options.damping)?,
+            ));
+        }
+
+        if options.betweenness {
+            let sources = options
+                .betweenness_samples
+                .filter(|k| *k < adjacency.order())
+                .map(|k| {
+                    let mut rng = util::acquire_rng(options.seed);
+                    let mut nodes = (0..adjacency.order()).collect::<Vec<_>>();
+
+                    nodes.shuffle(&mut rng);
+                    nodes.truncate(k);
+                    nodes
+                });
+
+            columns.push((
+                "betweenness",
+                centrality::betweenness(adjacency, sources.as_deref()),
+            ));
+        }
+
+        if options.closeness {
+            columns.push(("closeness", centrality::closeness(adjacency)));
+        }
+
+        if options.eigenvector {
+            columns.push(("eigenvector", centrality::eigenvector(adjacency)?));
+        }
+
+        if options.hits {
+            let (hubs, authorities) = centrality::hits(adjacency)?;
+
+            columns.push(("hub", hubs));
+            columns.push(("authority", authorities));
+        }
+
+        Ok(Self { columns })
+    }
+}
+
 enum NodeExtremityType {
     None,
     Source,
@@ -330,6 +468,8 @@ pub struct GraphBuilder {
     edge_model: Vec<ModelAttribute>,
     nodes: NodeStore,
     edges: EdgeStore,
+    // NOTE: interner ids of the "x" & "y" attributes added by a layout
+    layout_attributes: Option<(usize, usize)>,
 }
 
 impl GraphBuilder {
@@ -364,6 +504,7 @@ impl GraphBuilder {
             } else {
                 EdgeStore::Hash(HashMap::new(), Vec::new())
             },
+            layout_attributes: None,
         }
     }
 
@@ -546,6 +687,163 @@ impl GraphBuilder {
         degree_map
     }
 
+    fn edge_attribute_id(&self, name: &str) -> CliResult<usize> {
+        match self.edge_model.iter().find(|attr| attr.name == name) {
+            Some(attr) => Ok(attr.interner_id),
+            None => Err(format!("unknown edge attribute \"{}\"!", name))?,
+        }
+    }
+
+    fn compute_weights(&self, weight: Option<&str>) -> CliResult<Vec<f64>> {
+        Ok(match weight {
+            None => vec![1.0; self.edges.len()],
+            Some(name) => {
+                let id = self.edge_attribute_id(name)?;
+
+                self.edges
+                    .iter()
+                    .map(|(_, attributes)| {
+                        let weight = match attributes.get(id) {
+                            Some(Value::Number(number)) => number.as_f64(),
+                            Some(Value::String(string)) => string.parse::<f64>().ok(),
+                            _ => None,
+                        };
+
+                        weight.ok_or_else(|| {
+                            format!("could not parse edge weight from \"{}\" attribute!", name)
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+        })
+    }
+
+    fn weighted_edges(&self, weights: Vec<f64>) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+        self.edges
+            .pairs()
+            .zip(weights)
+            .map(|((source, target), weight)| (source, target, weight))
+    }
+
+    pub fn compute_adjacency(&self, weight: Option<&str>) -> CliResult<Adjacency> {
+        let weights = self.compute_weights(weight)?;
+
+        Ok(Adjacency::new(
+            self.nodes.len(),
+            !self.is_undirected(),
+            self.weighted_edges(weights),
+        ))
+    }
+
+    // NOTE: ignores the direction of edges
+    pub fn compute_undirected_adjacency(&self, weight: Option<&str>) -> CliResult<Adjacency> {
+        let weights = self.compute_weights(weight)?;
+
+        Ok(Adjacency::new(
+            self.nodes.len(),
+            false,
+            self.weighted_edges(weights),
+        ))
+    }
+
+    pub fn compute_distance_adjacency(&self, weight: Option<&str>) -> CliResult<Adjacency> {
+        let weights = self.compute_weights(weight)?;
+
+        Ok(Adjacency::new_distances(
+            self.nodes.len(),
+            !self.is_undirected(),
+            self.weighted_edges(weights),
+        ))
+    }
+
+    // NOTE: nodes lacking attributes are filled with nulls so that all nodes
+    // remain aligned with the node model. An attribute already existing in the
+    // node model is overwritten in place.
+    fn push_node_attribute(
+        &mut self,
+        name: &str,
+        json_type: JSONType,
+        values: Vec<Value>,
+    ) -> usize {
+        let model_names = self
+            .node_model
+            .iter()
+            .map(|attr| attr.name.clone())
+            .collect::<Vec<_>>();
+
+        let nodes: Box<dyn Iterator<Item = &mut Attributes>> = match &mut self.nodes {
+            NodeStore::Hash(map) => Box::new(map.values_mut()),
+            NodeStore::Range(list) => Box::new(list.iter_mut().map(|(_, attributes)| attributes)),
+        };
+
+        for (attributes, value) in nodes.zip(values) {
+            if attributes.is_empty() {
+                for model_name in model_names.iter() {
+                    attributes.insert(model_name, Value::Null);
+                }
+            }
+
+            attributes.set(name, value);
+        }
+
+        if let Some(attr) = self.node_model.iter_mut().find(|attr| attr.name == name) {
+            attr.json_type = json_type;
+            return attr.interner_id;
+        }
+
+        let interner_id = INTERNER.with_borrow_mut(|interner| interner.register(name.to_string()));
+
+        self.node_model.push(ModelAttribute {
+            name: name.to_string(),
+            interner_id,
+            json_type,
+        });
+
+        interner_id
+    }
+
+    pub fn add_louvain_communities(
+        &mut self,
+        weight: Option<&str>,
+        resolution: f64,
+        seed: Option<usize>,
+    ) -> CliResult<()> {
+        let adjacency = self.compute_undirected_adjacency(weight)?;
+        let mut rng = util::acquire_rng(seed);
+
+        let communities = louvain::louvain(&adjacency, resolution, &mut rng);
+
+        self.push_node_attribute(
+            "community",
+            JSONType::Integer,
+            communities.into_iter().map(Value::from).collect(),
+        );
+
+        Ok(())
+    }
+
+    pub fn add_layout(
+        &mut self,
+        weight: Option<&str>,
+        options: &LayoutOptions,
+        seed: Option<usize>,
+    ) -> CliResult<()> {
+        let adjacency = self.compute_undirected_adjacency(weight)?;
+        let mut rng = util::acquire_rng(seed);
+
+        let (xs, ys): (Vec<_>, Vec<_>) = layout::layout(&adjacency, options, &mut rng)
+            .into_iter()
+            .map(|(x, y)| (Value::from(x), Value::from(y)))
+            .unzip();
+
+        let x_id = self.push_node_attribute("x", JSONType::Float, xs);
+        let y_id = self.push_node_attribute("y", JSONType::Float, ys);
+
+        self.layout_attributes = Some((x_id, y_id));
+
+        Ok(())
+    }
+
     pub fn compute_union_find(&self) -> UnionFind {
         let mut sets = UnionFind::with_capacity(self.nodes.len());
 
@@ -560,9 +858,14 @@ impl GraphBuilder {
         sets
     }
 
+    // NOTE: an empty graph has no largest component, but then there is no
+    // node to compare to it either.
     pub fn compute_union_find_with_largest(&self) -> (UnionFind, usize) {
         let sets = self.compute_union_find();
-        let largest = sets.largest_entry().unwrap().size();
+        let largest = sets
+            .largest_entry()
+            .map(|entry| entry.parent())
+            .unwrap_or(0);
         (sets, largest)
     }
 
@@ -665,12 +968,20 @@ impl GraphBuilder {
         only_largest_component: bool,
         compute_degrees: bool,
         union_find: bool,
+        centrality_options: &CentralityOptions,
     ) -> CliResult<()> {
         let sets_opt =
             (only_largest_component || union_find).then(|| self.compute_union_find_with_largest());
 
         let degree_map_opt = compute_degrees.then(|| self.compute_degrees());
 
+        let centralities = if centrality_options.is_empty() {
+            Centralities::default()
+        } else {
+            let adjacency = self.compute_adjacency(centrality_options.weight.as_deref())?;
+            Centralities::compute(&adjacency, centrality_options)?
+        };
+
         let mut writer = writer_config.simd_writer()?;
 
         let mut record = simd_csv::ByteRecord::new();
@@ -693,6 +1004,10 @@ impl GraphBuilder {
             record.push_field(b"component");
         }
 
+        for (name, _) in centralities.columns.iter() {
+            record.push_field(name.as_bytes());
+        }
+
         writer.write_byte_record(&record)?;
 
         for (i, (key, attributes)) in self.nodes.iter().enumerate() {
@@ -736,6 +1051,261 @@ impl GraphBuilder {
                 record.push_field(sets.find(i).to_string().as_bytes());
             }
 
+            for (_, values) in centralities.columns.iter() {
+                record.push_field(values[i].to_string().as_bytes());
+            }
+
+            writer.write_byte_record(&record)?;
+        }
+
+        Ok(writer.flush()?)
+    }
+
+    // NOTE: nodes are colored by connected component, unless a categorical
+    // node attribute is given.
+    pub fn print_view(
+        &self,
+        color_by: Option<&str>,
+        only_largest_component: bool,
+        cols: usize,
+        rows: usize,
+    ) -> CliResult<()> {
+        let (x_id, y_id) = self
+            .layout_attributes
+            .expect("graph must be laid out before being viewed");
+
+        let color_by_id = color_by
+            .map(
+                |name| match self.node_model.iter().find(|attr| attr.name == name) {
+                    Some(attr) => Ok(attr.interner_id),
+                    None => Err(format!("unknown node attribute \"{}\"!", name)),
+                },
+            )
+            .transpose()?;
+
+        let (sets, largest) = if only_largest_component {
+            let (sets, largest) = self.compute_union_find_with_largest();
+            (sets, Some(largest))
+        } else {
+            (self.compute_union_find(), None)
+        };
+
+        // NOTE: components are named after their first node
+        let mut component_names: HashMap<usize, String> = HashMap::new();
+        let mut ids = vec![None; self.nodes.len()];
+        let mut view = view::GraphView {
+            positions: Vec::new(),
+            edges: Vec::new(),
+            categories: Vec::new(),
+        };
+
+        for (i, (key, attributes)) in self.nodes.iter().enumerate() {
+            let component = sets.find(i);
+
+            if largest.is_some_and(|largest| component != largest) {
+                continue;
+            }
+
+            let coordinate = |id: usize| attributes.get(id).and_then(|value| value.as_f64());
+
+            ids[i] = Some(view.positions.len());
+            view.positions
+                .push((coordinate(x_id).unwrap(), coordinate(y_id).unwrap()));
+
+            view.categories.push(match color_by_id {
+                Some(id) => attributes
+                    .get(id)
+                    .map(|value| serialize_value_to_csv(value).into_owned())
+                    .unwrap_or_default(),
+                None => component_names
+                    .entry(component)
+                    .or_insert_with(|| format!("component of {}", key.as_str()))
+                    .clone(),
+            });
+        }
+
+        for (source, target) in self.edges.pairs() {
+            if let (Some(source), Some(target)) = (ids[source], ids[target]) {
+                view.edges.push((source, target));
+            }
+        }
+
+        // NOTE: there is nothing to draw for an empty graph
+        if view.positions.is_empty() {
+            return Ok(());
+        }
+
+        view::print_graph_view(&view, cols, rows)
+    }
+
+    fn is_layout_attribute(&self, interner_id: usize) -> bool {
+        matches!(self.layout_attributes, Some((x_id, y_id)) if interner_id == x_id || interner_id == y_id)
+    }
+
+    fn node_id(&self, key: &str) -> CliResult<usize> {
+        let id = match &self.nodes {
+            NodeStore::Hash(map) => map.get_index_of(key),
+            NodeStore::Range(list) => key.parse::<usize>().ok().filter(|id| *id < list.len()),
+        };
+
+        id.ok_or_else(|| format!("unknown node \"{}\"!", key).into())
+    }
+
+    fn node_ids(&self, keys: &[String]) -> CliResult<Vec<usize>> {
+        keys.iter().map(|key| self.node_id(key)).collect()
+    }
+
+    pub fn write_csv_edgelist(
+        &self,
+        writer_config: &Config,
+        only_largest_component: bool,
+    ) -> CliResult<()> {
+        let sets_opt = only_largest_component.then(|| self.compute_union_find_with_largest());
+
+        self.write_csv_edgelist_impl(writer_config, |source, _| match &sets_opt {
+            Some((sets, largest)) => sets.find(source) == *largest,
+            None => true,
+        })
+    }
+
+    // NOTE: the edgelist of the subgraph induced by the nodes found at most
+    // `hops` away from the seeds, ignoring the direction of edges.
+    pub fn write_csv_ego(
+        &self,
+        writer_config: &Config,
+        seeds: &[String],
+        hops: usize,
+    ) -> CliResult<()> {
+        let seeds = self.node_ids(seeds)?;
+        let adjacency = self.compute_undirected_adjacency(None)?;
+        let distances = paths::neighborhood(&adjacency, &seeds, hops);
+
+        self.write_csv_edgelist_impl(writer_config, |source, target| {
+            distances[source].is_some() && distances[target].is_some()
+        })
+    }
+
+    pub fn write_csv_paths(
+        &self,
+        writer_config: &Config,
+        sources: &[String],
+        targets: &[String],
+        weight: Option<&str>,
+        separator: &str,
+    ) -> CliResult<()> {
+        let source_ids = self.node_ids(sources)?;
+        let target_ids = self.node_ids(targets)?;
+        let adjacency = self.compute_distance_adjacency(weight)?;
+
+        // NOTE: weights are validated before writing anything
+        let has_invalid_weights = (0..adjacency.order()).any(|node| {
+            adjacency
+                .out_neighbors(node)
+                .iter()
+                .any(|(_, weight)| !weight.is_finite() || *weight < 0.0)
+        });
+
+        if has_invalid_weights {
+            Err("edge weights must be finite & non-negative to find shortest paths!")?;
+        }
+
+        let mut writer = writer_config.simd_writer()?;
+
+        let mut record = simd_csv::ByteRecord::new();
+        record.push_field(b"source");
+        record.push_field(b"target");
+        record.push_field(b"hops");
+
+        if weight.is_some() {
+            record.push_field(b"distance");
+        }
+
+        record.push_field(b"path");
+
+        writer.write_byte_record(&record)?;
+
+        for (source, source_id) in sources.iter().zip(source_ids) {
+            let tree = if weight.is_some() {
+                paths::dijkstra(&adjacency, source_id)?
+            } else {
+                paths::bfs(&adjacency, source_id)
+            };
+
+            for (target, target_id) in targets.iter().zip(target_ids.iter().copied()) {
+                record.clear();
+                record.push_field(source.as_bytes());
+                record.push_field(target.as_bytes());
+
+                match tree.path(target_id) {
+                    None => {
+                        record.push_field(b"");
+
+                        if weight.is_some() {
+                            record.push_field(b"");
+                        }
+
+                        record.push_field(b"");
+                    }
+                    Some(path) => {
+                        record.push_field((path.len() - 1).to_string().as_bytes());
+
+                        if weight.is_some() {
+                            record.push_field(
+                                tree.distance(target_id).unwrap().to_string().as_bytes(),
+                            );
+                        }
+
+                        let keys = path
+                            .into_iter()
+                            .map(|node| self.nodes.get_index(node).unwrap().0.as_str().into_owned())
+                            .collect::<Vec<_>>();
+
+                        record.push_field(keys.join(separator).as_bytes());
+                    }
+                }
+
+                writer.write_byte_record(&record)?;
+            }
+        }
+
+        Ok(writer.flush()?)
+    }
+
+    fn write_csv_edgelist_impl<F>(&self, writer_config: &Config, keep: F) -> CliResult<()>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let mut writer = writer_config.simd_writer()?;
+
+        let mut record = simd_csv::ByteRecord::new();
+        record.push_field(b"source");
+        record.push_field(b"target");
+
+        for attr in self.edge_model.iter() {
+            record.push_field(attr.name.as_bytes());
+        }
+
+        writer.write_byte_record(&record)?;
+
+        for ((source, target), attributes) in self.edges.iter() {
+            if !keep(source, target) {
+                continue;
+            }
+
+            record.clear();
+            record.push_field(&self.nodes.get_index(source).unwrap().0.as_bytes());
+            record.push_field(&self.nodes.get_index(target).unwrap().0.as_bytes());
+
+            if !attributes.is_empty() {
+                for (_, attr_value) in attributes.iter() {
+                    record.push_field(serialize_value_to_csv(attr_value).as_bytes());
+                }
+            } else {
+                for _ in self.edge_model.iter() {
+                    record.push_field(b"");
+                }
+            }
+
             writer.write_byte_record(&record)?;
         }
 
@@ -771,15 +1341,19 @@ impl GraphBuilder {
         let node_model = &self.node_model;
         let edge_model = &self.edge_model;
 
-        xml_writer.open(
-            "gexf",
-            [
-                ("xmlns", gexf_namespace.xmlns),
-                ("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"),
-                ("xsi:schemaLocation", gexf_namespace.schema_location),
-                ("version", gexf_namespace.version),
-            ],
-        )?;
+        let mut root_attributes = vec![("xmlns", gexf_namespace.xmlns)];
+
+        if self.layout_attributes.is_some() {
+            root_attributes.push(("xmlns:viz", gexf_namespace.viz));
+        }
+
+        root_attributes.extend([
+            ("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"),
+            ("xsi:schemaLocation", gexf_namespace.schema_location),
+            ("version", gexf_namespace.version),
+        ]);
+
+        xml_writer.open("gexf", root_attributes)?;
 
         // Meta
         xml_writer.open("meta", [("lastmodifieddate", today.as_str())])?;
@@ -806,6 +1380,10 @@ impl GraphBuilder {
                 continue;
             }
 
+            if self.is_layout_attribute(model_attr.interner_id) {
+                continue;
+            }
+
             xml_writer.open_empty(
                 "attribute",
                 [
@@ -852,7 +1430,7 @@ impl GraphBuilder {
 
             let node_label = if let Some(id) = node_label_attr {
                 match attributes.get(id) {
-                    None => key.as_str(),
+                    None | Some(Value::Null) => key.as_str(),
                     Some(v) => Cow::Owned(serialize_value(v)),
                 }
             } else {
@@ -864,12 +1442,26 @@ impl GraphBuilder {
             } else {
                 xml_writer.open("node", [("id", key.as_str()), ("label", node_label)])?;
 
+                let mut position: (Option<&Value>, Option<&Value>) = (None, None);
+
                 xml_writer.open_no_attributes("attvalues")?;
                 for (i, (interner_id, value)) in attributes.iter().enumerate() {
                     if matches!(node_label_attr, Some(id) if id == *interner_id) {
                         continue;
                     }
 
+                    match self.layout_attributes {
+                        Some((x_id, _)) if x_id == *interner_id => {
+                            position.0 = Some(value);
+                            continue;
+                        }
+                        Some((_, y_id)) if y_id == *interner_id => {
+                            position.1 = Some(value);
+                            continue;
+                        }
+                        _ => (),
+                    }
+
                     xml_writer.open_empty(
                         "attvalue",
                         [
@@ -880,6 +1472,16 @@ impl GraphBuilder {
                 }
                 xml_writer.close("attvalues")?;
 
+                if let (Some(x), Some(y)) = position {
+                    xml_writer.open_empty(
+                        "viz:position",
+                        [
+                            ("x", serialize_value(x).as_str()),
+                            ("y", serialize_value(y).as_str()),
+                        ],
+                    )?;
+                }
+
                 xml_writer.close("node")?;
             }
         }
@@ -939,6 +1541,269 @@ impl GraphBuilder {
         Ok(())
     }
 
+    pub fn write_graphml(
+        &self,
+        writer_config: &Config,
+        minify: bool,
+        only_largest_component: bool,
+    ) -> CliResult<()> {
+        let sets_opt = only_largest_component.then(|| self.compute_union_find_with_largest());
+
+        let writer = writer_config.buf_io_writer()?;
+
+        let mut xml_writer = if minify {
+            XMLWriter::new_minified(writer)
+        } else {
+            XMLWriter::new(writer)
+        };
+
+        xml_writer.write_declaration()?;
+
+        xml_writer.open(
+            "graphml",
+            [
+                ("xmlns", "http://graphml.graphdrawing.org/xmlns"),
+                ("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"),
+                (
+                    "xsi:schemaLocation",
+                    "http://graphml.graphdrawing.org/xmlns http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd",
+                ),
+            ],
+        )?;
+
+        // Node & edge models
+        for (prefix, class, model) in [
+            ("n", "node", &self.node_model),
+            ("e", "edge", &self.edge_model),
+        ] {
+            for (i, model_attr) in model.iter().enumerate() {
+                xml_writer.open_empty(
+                    "key",
+                    [
+                        ("id", format!("{}{}", prefix, i).as_str()),
+                        ("for", class),
+                        ("attr.name", model_attr.name.as_str()),
+                        ("attr.type", model_attr.json_type.as_graphml_type()),
+                    ],
+                )?;
+            }
+        }
+
+        xml_writer.open("graph", [("edgedefault", self.options.graph_type.as_str())])?;
+
+        fn write_data<W: Write>(
+            xml_writer: &mut XMLWriter<W>,
+            prefix: &str,
+            attributes: &Attributes,
+        ) -> CliResult<()> {
+            for (i, (_, value)) in attributes.iter().enumerate() {
+                if value.is_null() {
+                    continue;
+                }
+
+                xml_writer.open("data", [("key", format!("{}{}", prefix, i).as_str())])?;
+                xml_writer.write_text(&serialize_value_to_csv(value))?;
+                xml_writer.close("data")?;
+            }
+
+            Ok(())
+        }
+
+        // Node data
+        for (i, (key, attributes)) in self.nodes.iter().enumerate() {
+            if let Some((sets, largest)) = &sets_opt {
+                if sets.find(i) != *largest {
+                    continue;
+                }
+            }
+
+            if attributes.is_empty() {
+                xml_writer.open_empty("node", [("id", key.as_str())])?;
+            } else {
+                xml_writer.open("node", [("id", key.as_str())])?;
+                write_data(&mut xml_writer, "n", attributes)?;
+                xml_writer.close("node")?;
+            }
+        }
+
+        // Edge data
+        for ((source, target), attributes) in self.edges.iter() {
+            if let Some((sets, largest)) = &sets_opt {
+                if sets.find(source) != *largest {
+                    continue;
+                }
+            }
+
+            let source_key = self.nodes.get_index(source).unwrap().0;
+            let target_key = self.nodes.get_index(target).unwrap().0;
+
+            let edge_attributes = [
+                ("source", source_key.as_str()),
+                ("target", target_key.as_str()),
+            ];
+
+            if attributes.is_empty() {
+                xml_writer.open_empty("edge", edge_attributes)?;
+            } else {
+                xml_writer.open("edge", edge_attributes)?;
+                write_data(&mut xml_writer, "e", attributes)?;
+                xml_writer.close("edge")?;
+            }
+        }
+
+        xml_writer.close("graph")?;
+
+        xml_writer.close("graphml")?;
+        xml_writer.finish()?;
+
+        Ok(())
+    }
+
+    pub fn write_dot(&self, writer_config: &Config, only_largest_component: bool) -> CliResult<()> {
+        let sets_opt = only_largest_component.then(|| self.compute_union_find_with_largest());
+
+        let mut writer = writer_config.buf_io_writer()?;
+
+        let (graph_keyword, edge_operator) = match self.options.graph_type {
+            GraphType::Directed => ("digraph", "->"),
+            GraphType::Undirected => ("graph", "--"),
+        };
+
+        fn format_attributes(model: &[ModelAttribute], attributes: &Attributes) -> String {
+            let pairs = model
+                .iter()
+                .zip(attributes.iter())
+                .filter(|(_, (_, value))| !value.is_null())
+                .map(|(model_attr, (_, value))| {
+                    format!(
+                        "{}={}",
+                        escape_dot_id(&model_attr.name),
+                        escape_dot_id(&serialize_value_to_csv(value))
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            if pairs.is_empty() {
+                "".to_string()
+            } else {
+                format!(" [{}]", pairs.join(", "))
+            }
+        }
+
+        writeln!(&mut writer, "{} {{", graph_keyword)?;
+
+        for (i, (key, attributes)) in self.nodes.iter().enumerate() {
+            if let Some((sets, largest)) = &sets_opt {
+                if sets.find(i) != *largest {
+                    continue;
+                }
+            }
+
+            writeln!(
+                &mut writer,
+                "  {}{};",
+                escape_dot_id(&key.as_str()),
+                format_attributes(&self.node_model, attributes)
+            )?;
+        }
+
+        for ((source, target), attributes) in self.edges.iter() {
+            if let Some((sets, largest)) = &sets_opt {
+                if sets.find(source) != *largest {
+                    continue;
+                }
+            }
+
+            let source_key = self.nodes.get_index(source).unwrap().0;
+            let target_key = self.nodes.get_index(target).unwrap().0;
+
+            writeln!(
+                &mut writer,
+                "  {} {} {}{};",
+                escape_dot_id(&source_key.as_str()),
+                edge_operator,
+                escape_dot_id(&target_key.as_str()),
+                format_attributes(&self.edge_model, attributes)
+            )?;
+        }
+
+        writeln!(&mut writer, "}}")?;
+
+        Ok(writer.flush()?)
+    }
+
+    pub fn write_pajek(
+        &self,
+        writer_config: &Config,
+        weight: Option<&str>,
+        only_largest_component: bool,
+    ) -> CliResult<()> {
+        let sets_opt = only_largest_component.then(|| self.compute_union_find_with_largest());
+        let weight_id = weight
+            .map(|name| self.edge_attribute_id(name))
+            .transpose()?;
+
+        let mut writer = writer_config.buf_io_writer()?;
+
+        // NOTE: pajek vertices are numbered from 1, so we need to renumber
+        // them when only keeping the largest component
+        let mut vertices: Vec<Option<usize>> = Vec::with_capacity(self.nodes.len());
+        let mut labels: Vec<String> = Vec::new();
+
+        for (i, (key, _)) in self.nodes.iter().enumerate() {
+            if let Some((sets, largest)) = &sets_opt {
+                if sets.find(i) != *largest {
+                    vertices.push(None);
+                    continue;
+                }
+            }
+
+            labels.push(escape_pajek_label(&key.as_str()));
+            vertices.push(Some(labels.len()));
+        }
+
+        writeln!(&mut writer, "*Vertices {}", labels.len())?;
+
+        for (i, label) in labels.iter().enumerate() {
+            writeln!(&mut writer, "{} {}", i + 1, label)?;
+        }
+
+        writeln!(
+            &mut writer,
+            "{}",
+            match self.options.graph_type {
+                GraphType::Directed => "*Arcs",
+                GraphType::Undirected => "*Edges",
+            }
+        )?;
+
+        for ((source, target), attributes) in self.edges.iter() {
+            let (Some(source_vertex), Some(target_vertex)) = (vertices[source], vertices[target])
+            else {
+                continue;
+            };
+
+            write!(&mut writer, "{} {}", source_vertex, target_vertex)?;
+
+            if let Some(id) = weight_id {
+                match attributes.get(id) {
+                    Some(value @ Value::Number(_)) => write!(&mut writer, " {}", value)?,
+                    Some(Value::String(string)) if string.parse::<f64>().is_ok() => {
+                        write!(&mut writer, " {}", string)?
+                    }
+                    _ => Err(format!(
+                        "could not parse edge weight from \"{}\" attribute!",
+                        weight.unwrap()
+                    ))?,
+                }
+            }
+
+            writeln!(&mut writer)?;
+        }
+
+        Ok(writer.flush()?)
+    }
+
     fn write_json_impl<W: Write, F: Formatter>(
         &self,
         mut serializer: Serializer<W, F>,
@@ -0,0 +1,169 @@
+// Shortest paths & neighborhoods. Unweighted graphs are traversed using
+// BFS, weighted ones using Dijkstra's algorithm, in which case weights are
+// considered as distances and must therefore be positive.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use ordered_float::OrderedFloat;
+
+use super::Adjacency;
+
+pub struct ShortestPathTree {
+    predecessors: Vec<Option<usize>>,
+    distances: Vec<Option<f64>>,
+}
+
+impl ShortestPathTree {
+    fn new(order: usize, source: usize) -> Self {
+        let mut distances = vec![None; order];
+        distances[source] = Some(0.0);
+
+        Self {
+            predecessors: vec![None; order],
+            distances,
+        }
+    }
+
+    pub fn distance(&self, target: usize) -> Option<f64> {
+        self.distances[target]
+    }
+
+    // NOTE: returns the nodes of the path, source & target included
+    pub fn path(&self, target: usize) -> Option<Vec<usize>> {
+        self.distances[target]?;
+
+        let mut path = vec![target];
+        let mut node = target;
+
+        while let Some(predecessor) = self.predecessors[node] {
+            path.push(predecessor);
+            node = predecessor;
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+pub fn bfs(graph: &Adjacency, source: usize) -> ShortestPathTree {
+    let mut tree = ShortestPathTree::new(graph.order(), source);
+    let mut queue = VecDeque::from([source]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = tree.distances[node].unwrap();
+
+        for (neighbor, _) in graph.out_neighbors(node) {
+            if tree.distances[*neighbor].is_none() {
+                tree.distances[*neighbor] = Some(distance + 1.0);
+                tree.predecessors[*neighbor] = Some(node);
+                queue.push_back(*neighbor);
+            }
+        }
+    }
+
+    tree
+}
+
+pub fn dijkstra(graph: &Adjacency, source: usize) -> Result<ShortestPathTree, String> {
+    let mut tree = ShortestPathTree::new(graph.order(), source);
+    let mut visited = vec![false; graph.order()];
+    let mut heap = BinaryHeap::from([Reverse((OrderedFloat(0.0), source))]);
+
+    while let Some(Reverse((OrderedFloat(distance), node))) = heap.pop() {
+        if visited[node] {
+            continue;
+        }
+
+        visited[node] = true;
+
+        for (neighbor, weight) in graph.out_neighbors(node) {
+            if *weight < 0.0 {
+                return Err("cannot find shortest paths with negative weights!".to_string());
+            }
+
+            let candidate = distance + weight;
+
+            if !matches!(tree.distances[*neighbor], Some(current) if current <= candidate) {
+                tree.distances[*neighbor] = Some(candidate);
+                tree.predecessors[*neighbor] = Some(node);
+                heap.push(Reverse((OrderedFloat(candidate), *neighbor)));
+            }
+        }
+    }
+
+    Ok(tree)
+}
+
+// NOTE: returns, for each node, its distance in hops to the closest seed, if
+// it is at most `hops` away.
+pub fn neighborhood(graph: &Adjacency, seeds: &[usize], hops: usize) -> Vec<Option<usize>> {
+    let mut distances = vec![None; graph.order()];
+    let mut queue = VecDeque::new();
+
+    for seed in seeds.iter().copied() {
+        if distances[seed].is_none() {
+            distances[seed] = Some(0);
+            queue.push_back(seed);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[node].unwrap();
+
+        if distance == hops {
+            continue;
+        }
+
+        for (neighbor, _) in graph.out_neighbors(node) {
+            if distances[*neighbor].is_none() {
+                distances[*neighbor] = Some(distance + 1);
+                queue.push_back(*neighbor);
+            }
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortest_paths() {
+        // Direct edge 0 -> 2 is heavier than going through 1
+        let graph = Adjacency::new(4, true, [(0, 1, 1.0), (1, 2, 1.0), (0, 2, 5.0)].into_iter());
+
+        let tree = bfs(&graph, 0);
+
+        assert_eq!(tree.path(2), Some(vec![0, 2]));
+        assert_eq!(tree.distance(2), Some(1.0));
+        assert_eq!(tree.path(3), None);
+        assert_eq!(bfs(&graph, 2).path(0), None);
+
+        let tree = dijkstra(&graph, 0).unwrap();
+
+        assert_eq!(tree.path(2), Some(vec![0, 1, 2]));
+        assert_eq!(tree.distance(2), Some(2.0));
+        assert_eq!(tree.path(0), Some(vec![0]));
+    }
+
+    #[test]
+    fn test_neighborhood() {
+        let graph = Adjacency::new(
+            5,
+            false,
+            [(0, 1, 1.0), (1, 2, 1.0), (2, 3, 1.0)].into_iter(),
+        );
+
+        assert_eq!(
+            neighborhood(&graph, &[0], 2),
+            vec![Some(0), Some(1), Some(2), None, None]
+        );
+        assert_eq!(
+            neighborhood(&graph, &[0, 3], 1),
+            vec![Some(0), Some(1), Some(1), Some(0), None]
+        );
+    }
+}
@@ -0,0 +1,200 @@
+// One-mode projection of bipartite graphs, where two nodes of the projected
+// part are linked if they share at least one neighbor in the other part.
+use std::convert::TryFrom;
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(try_from = "String")]
+pub enum ProjectionWeighting {
+    Count,
+    Newman,
+    Jaccard,
+    Cosine,
+}
+
+impl ProjectionWeighting {
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Self::Count)
+    }
+}
+
+impl TryFrom<String> for ProjectionWeighting {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(match value.as_str() {
+            "count" => Self::Count,
+            "newman" => Self::Newman,
+            "jaccard" => Self::Jaccard,
+            "cosine" => Self::Cosine,
+            _ => return Err(format!("unknown weighting \"{}\"!", value)),
+        })
+    }
+}
+
+pub struct ProjectionOptions {
+    pub weighting: ProjectionWeighting,
+    pub min_weight: Option<f64>,
+    pub top_k: Option<usize>,
+}
+
+// NOTE: `neighborhoods` lists, for each node of the other part, the sorted &
+// deduplicated ids of its neighbors in the projected part, whose ids must
+// range from 0 to `order` - 1.
+pub fn project(
+    neighborhoods: &[Vec<usize>],
+    order: usize,
+    options: &ProjectionOptions,
+) -> Vec<(usize, usize, f64)> {
+    let mut incidences = vec![Vec::new(); order];
+
+    for (other, neighbors) in neighborhoods.iter().enumerate() {
+        for node in neighbors.iter().copied() {
+            incidences[node].push(other);
+        }
+    }
+
+    let mut edges = Vec::new();
+
+    let mut shared = vec![0.0; order];
+    let mut touched: Vec<usize> = Vec::new();
+    let mut is_touched = vec![false; order];
+
+    for (node, others) in incidences.iter().enumerate() {
+        for other in others.iter().copied() {
+            let neighbors = &neighborhoods[other];
+
+            // NOTE: Newman's collaboration weighting discounts neighbors
+            // shared by many nodes
+            let contribution = match options.weighting {
+                ProjectionWeighting::Newman => 1.0 / (neighbors.len() - 1).max(1) as f64,
+                _ => 1.0,
+            };
+
+            for neighbor in neighbors.iter().copied() {
+                if neighbor <= node {
+                    continue;
+                }
+
+                if !is_touched[neighbor] {
+                    is_touched[neighbor] = true;
+                    touched.push(neighbor);
+                }
+
+                shared[neighbor] += contribution;
+            }
+        }
+
+        touched.sort_unstable();
+
+        for neighbor in touched.drain(..) {
+            let intersection = shared[neighbor];
+            let a = others.len() as f64;
+            let b = incidences[neighbor].len() as f64;
+
+            let weight = match options.weighting {
+                ProjectionWeighting::Count | ProjectionWeighting::Newman => intersection,
+                ProjectionWeighting::Jaccard => intersection / (a + b - intersection),
+                ProjectionWeighting::Cosine => intersection / (a * b).sqrt(),
+            };
+
+            if !matches!(options.min_weight, Some(min_weight) if weight < min_weight) {
+                edges.push((node, neighbor, weight));
+            }
+
+            shared[neighbor] = 0.0;
+            is_touched[neighbor] = false;
+        }
+    }
+
+    if let Some(k) = options.top_k {
+        edges = prune_top_k(edges, order, k);
+    }
+
+    edges
+}
+
+// NOTE: an edge is kept if it is among the k heaviest edges of at least one
+// of its endpoints. Ties are broken using edge order.
+fn prune_top_k(
+    edges: Vec<(usize, usize, f64)>,
+    order: usize,
+    k: usize,
+) -> Vec<(usize, usize, f64)> {
+    let mut incident_edges = vec![Vec::new(); order];
+
+    for (i, (source, target, _)) in edges.iter().enumerate() {
+        incident_edges[*source].push(i);
+        incident_edges[*target].push(i);
+    }
+
+    let mut keep = vec![false; edges.len()];
+
+    for node_edges in incident_edges.iter_mut() {
+        node_edges.sort_by(|a, b| edges[*b].2.total_cmp(&edges[*a].2));
+
+        for i in node_edges.iter().take(k) {
+            keep[*i] = true;
+        }
+    }
+
+    edges
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(edge, kept)| kept.then_some(edge))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(weighting: ProjectionWeighting) -> ProjectionOptions {
+        ProjectionOptions {
+            weighting,
+            min_weight: None,
+            top_k: None,
+        }
+    }
+
+    #[test]
+    fn test_project() {
+        // 0 & 1 share two neighbors, 2 shares one with both of them
+        let neighborhoods = vec![vec![0, 1], vec![0, 1, 2], vec![2]];
+
+        assert_eq!(
+            project(&neighborhoods, 3, &options(ProjectionWeighting::Count)),
+            vec![(0, 1, 2.0), (0, 2, 1.0), (1, 2, 1.0)]
+        );
+
+        assert_eq!(
+            project(&neighborhoods, 3, &options(ProjectionWeighting::Newman)),
+            vec![(0, 1, 1.5), (0, 2, 0.5), (1, 2, 0.5)]
+        );
+
+        assert_eq!(
+            project(&neighborhoods, 3, &options(ProjectionWeighting::Jaccard)),
+            vec![(0, 1, 1.0), (0, 2, 1.0 / 3.0), (1, 2, 1.0 / 3.0)]
+        );
+
+        assert_eq!(
+            project(&neighborhoods, 3, &options(ProjectionWeighting::Cosine)),
+            vec![(0, 1, 1.0), (0, 2, 0.5), (1, 2, 0.5)]
+        );
+
+        let mut pruned = options(ProjectionWeighting::Count);
+        pruned.min_weight = Some(2.0);
+
+        assert_eq!(project(&neighborhoods, 3, &pruned), vec![(0, 1, 2.0)]);
+    }
+
+    #[test]
+    fn test_prune_top_k() {
+        let edges = vec![(0, 1, 3.0), (0, 2, 2.0), (0, 3, 1.0), (2, 3, 0.5)];
+
+        // Edge (0, 3) is kept because it is 3's heaviest edge
+        assert_eq!(
+            prune_top_k(edges, 4, 1),
+            vec![(0, 1, 3.0), (0, 2, 2.0), (0, 3, 1.0)]
+        );
+    }
+}
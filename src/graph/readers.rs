@@ -0,0 +1,586 @@
+// Readers converting graph files back into CSV node & edge tables. Attribute
+// columns are emitted in the order they are declared in the file's models
+// (or, for graphology, in order of first appearance).
+use std::io::BufRead;
+
+use quick_xml::XmlVersion;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::reader::Reader;
+use serde_json::Value;
+
+use crate::CliResult;
+use crate::collections::HashMap;
+
+pub struct GraphTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+pub struct GraphTables {
+    pub nodes: GraphTable,
+    pub edges: GraphTable,
+}
+
+#[derive(Default)]
+struct AttributeModel {
+    indices: HashMap<String, usize>,
+    titles: Vec<String>,
+    defaults: Vec<Option<String>>,
+}
+
+impl AttributeModel {
+    fn declare(&mut self, id: &str, title: &str) -> usize {
+        if let Some(index) = self.indices.get(id) {
+            return *index;
+        }
+
+        let index = self.titles.len();
+
+        self.indices.insert(id.to_string(), index);
+        self.titles.push(title.to_string());
+        self.defaults.push(None);
+
+        index
+    }
+
+    fn get(&self, id: &str) -> Option<usize> {
+        self.indices.get(id).copied()
+    }
+}
+
+struct Row {
+    fixed: Vec<String>,
+    optional: Vec<Option<String>>,
+    values: Vec<Option<String>>,
+}
+
+impl Row {
+    fn set_value(&mut self, index: usize, value: String) {
+        if self.values.len() <= index {
+            self.values.resize(index + 1, None);
+        }
+
+        self.values[index] = Some(value);
+    }
+}
+
+// NOTE: optional columns, e.g. gexf viz data, are only emitted if at least
+// one row has a value for them.
+struct TableBuilder {
+    fixed: &'static [&'static str],
+    optional: &'static [&'static str],
+    model: AttributeModel,
+    rows: Vec<Row>,
+}
+
+impl TableBuilder {
+    fn new(fixed: &'static [&'static str], optional: &'static [&'static str]) -> Self {
+        Self {
+            fixed,
+            optional,
+            model: AttributeModel::default(),
+            rows: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, fixed: Vec<String>) -> &mut Row {
+        self.rows.push(Row {
+            fixed,
+            optional: vec![None; self.optional.len()],
+            values: Vec::new(),
+        });
+
+        self.rows.last_mut().unwrap()
+    }
+
+    fn last_mut(&mut self) -> Option<&mut Row> {
+        self.rows.last_mut()
+    }
+
+    fn finish(self) -> GraphTable {
+        let emitted = (0..self.optional.len())
+            .filter(|i| self.rows.iter().any(|row| row.optional[*i].is_some()))
+            .collect::<Vec<_>>();
+
+        let mut headers = self
+            .fixed
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+
+        headers.extend(emitted.iter().map(|i| self.optional[*i].to_string()));
+        headers.extend(self.model.titles.iter().cloned());
+
+        let defaults = &self.model.defaults;
+
+        let rows = self
+            .rows
+            .into_iter()
+            .map(|row| {
+                let mut record = row.fixed;
+
+                for i in emitted.iter() {
+                    record.push(row.optional[*i].clone().unwrap_or_default());
+                }
+
+                for (i, default) in defaults.iter().enumerate() {
+                    record.push(
+                        row.values
+                            .get(i)
+                            .cloned()
+                            .flatten()
+                            .or_else(|| default.clone())
+                            .unwrap_or_default(),
+                    );
+                }
+
+                record
+            })
+            .collect();
+
+        GraphTable { headers, rows }
+    }
+}
+
+struct XMLElement {
+    name: String,
+    attributes: Vec<(String, String)>,
+}
+
+impl XMLElement {
+    fn parse(start: &BytesStart) -> CliResult<Self> {
+        let mut attributes = Vec::new();
+
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(quick_xml::Error::from)?;
+
+            attributes.push((
+                std::str::from_utf8(attribute.key.local_name().as_ref())?.to_string(),
+                attribute
+                    .normalized_value(XmlVersion::Implicit1_0)?
+                    .into_owned(),
+            ));
+        }
+
+        Ok(Self {
+            name: std::str::from_utf8(start.local_name().as_ref())?.to_string(),
+            attributes,
+        })
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find_map(|(k, v)| (k == name).then_some(v.as_str()))
+    }
+
+    fn get_owned(&self, name: &str) -> Option<String> {
+        self.get(name).map(|v| v.to_string())
+    }
+
+    fn require(&self, name: &str) -> CliResult<&str> {
+        self.get(name).ok_or_else(|| {
+            format!(
+                "<{}> element is missing its \"{}\" attribute!",
+                self.name, name
+            )
+            .into()
+        })
+    }
+}
+
+enum XMLEvent<'a> {
+    Open(&'a XMLElement),
+    Close(&'a str),
+    Text(&'a str),
+}
+
+// NOTE: empty elements are reported as opened then closed right away and
+// entity references are resolved into text events.
+fn read_xml<R: BufRead>(
+    reader: R,
+    mut callback: impl FnMut(XMLEvent) -> CliResult<()>,
+) -> CliResult<()> {
+    let mut reader = Reader::from_reader(reader);
+    let mut buffer = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buffer)? {
+            Event::Start(start) => {
+                callback(XMLEvent::Open(&XMLElement::parse(&start)?))?;
+            }
+            Event::Empty(start) => {
+                let element = XMLElement::parse(&start)?;
+
+                callback(XMLEvent::Open(&element))?;
+                callback(XMLEvent::Close(&element.name))?;
+            }
+            Event::End(end) => {
+                callback(XMLEvent::Close(std::str::from_utf8(
+                    end.local_name().as_ref(),
+                )?))?;
+            }
+            Event::Text(text) => {
+                callback(XMLEvent::Text(&text.xml10_content()?))?;
+            }
+            Event::CData(cdata) => {
+                callback(XMLEvent::Text(&cdata.decode()?))?;
+            }
+            Event::GeneralRef(reference) => {
+                if let Some(c) = reference.resolve_char_ref()? {
+                    callback(XMLEvent::Text(c.encode_utf8(&mut [0; 4])))?;
+                } else if let Some(resolved) = resolve_predefined_entity(&reference.decode()?) {
+                    callback(XMLEvent::Text(resolved))?;
+                }
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+
+        buffer.clear();
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Class {
+    Node,
+    Edge,
+}
+
+pub fn read_gexf<R: BufRead>(reader: R) -> CliResult<GraphTables> {
+    let mut nodes = TableBuilder::new(&["node"], &["label", "x", "y", "z", "size", "color"]);
+    let mut edges = TableBuilder::new(&["source", "target"], &["label", "weight"]);
+
+    let mut attributes_class: Option<Class> = None;
+    let mut current_attribute: Option<usize> = None;
+    let mut current_element: Option<Class> = None;
+    let mut default_text: Option<String> = None;
+
+    read_xml(reader, |event| {
+        match event {
+            XMLEvent::Open(element) => match element.name.as_str() {
+                "attributes" => {
+                    attributes_class = match element.get("class") {
+                        Some("node") => Some(Class::Node),
+                        Some("edge") => Some(Class::Edge),
+                        _ => None,
+                    };
+                }
+                "attribute" => {
+                    let id = element.require("id")?;
+                    let title = element.get("title").unwrap_or(id);
+
+                    current_attribute = match attributes_class {
+                        Some(Class::Node) => Some(nodes.model.declare(id, title)),
+                        Some(Class::Edge) => Some(edges.model.declare(id, title)),
+                        None => None,
+                    };
+                }
+                "default" if current_attribute.is_some() => {
+                    default_text = Some(String::new());
+                }
+                "node" => {
+                    let row = nodes.push(vec![element.require("id")?.to_string()]);
+                    row.optional[0] = element.get_owned("label");
+
+                    current_element = Some(Class::Node);
+                }
+                "edge" => {
+                    let row = edges.push(vec![
+                        element.require("source")?.to_string(),
+                        element.require("target")?.to_string(),
+                    ]);
+                    row.optional[0] = element.get_owned("label");
+                    row.optional[1] = element.get_owned("weight");
+
+                    current_element = Some(Class::Edge);
+                }
+                "attvalue" => {
+                    // NOTE: gexf 1.0 used "id" instead of "for"
+                    let id = element.get("for").or_else(|| element.get("id"));
+                    let value = element.get_owned("value");
+
+                    let table = match current_element {
+                        Some(Class::Node) => &mut nodes,
+                        Some(Class::Edge) => &mut edges,
+                        None => return Ok(()),
+                    };
+
+                    if let (Some(index), Some(value)) =
+                        (id.and_then(|id| table.model.get(id)), value)
+                    {
+                        table.last_mut().unwrap().set_value(index, value);
+                    }
+                }
+                "position" if current_element == Some(Class::Node) => {
+                    let row = nodes.last_mut().unwrap();
+
+                    row.optional[1] = element.get_owned("x");
+                    row.optional[2] = element.get_owned("y");
+                    row.optional[3] = element.get_owned("z");
+                }
+                "size" if current_element == Some(Class::Node) => {
+                    nodes.last_mut().unwrap().optional[4] = element.get_owned("value");
+                }
+                "color" if current_element == Some(Class::Node) => {
+                    let channels = ["r", "g", "b"]
+                        .into_iter()
+                        .map(|channel| element.require(channel))
+                        .collect::<CliResult<Vec<_>>>()?;
+
+                    nodes.last_mut().unwrap().optional[5] = Some(match element.get("a") {
+                        Some(alpha) => format!("rgba({},{})", channels.join(","), alpha),
+                        None => format!("rgb({})", channels.join(",")),
+                    });
+                }
+                _ => (),
+            },
+            XMLEvent::Text(text) => {
+                if let Some(buffer) = default_text.as_mut() {
+                    buffer.push_str(text);
+                }
+            }
+            XMLEvent::Close(name) => match name {
+                "attributes" => {
+                    attributes_class = None;
+                }
+                "attribute" => {
+                    current_attribute = None;
+                }
+                "default" => {
+                    if let (Some(index), Some(text)) = (current_attribute, default_text.take()) {
+                        match attributes_class {
+                            Some(Class::Node) => nodes.model.defaults[index] = Some(text),
+                            Some(Class::Edge) => edges.model.defaults[index] = Some(text),
+                            None => (),
+                        }
+                    }
+                }
+                "node" | "edge" => {
+                    current_element = None;
+                }
+                _ => (),
+            },
+        }
+
+        Ok(())
+    })?;
+
+    Ok(GraphTables {
+        nodes: nodes.finish(),
+        edges: edges.finish(),
+    })
+}
+
+pub fn read_graphml<R: BufRead>(reader: R) -> CliResult<GraphTables> {
+    let mut nodes = TableBuilder::new(&["node"], &[]);
+    let mut edges = TableBuilder::new(&["source", "target"], &[]);
+
+    // NOTE: a key declared "for" "all" is tracked in both models
+    let mut current_key: (Option<usize>, Option<usize>) = (None, None);
+    let mut current_element: Option<Class> = None;
+    let mut current_data: Option<usize> = None;
+    let mut text: Option<String> = None;
+
+    read_xml(reader, |event| {
+        match event {
+            XMLEvent::Open(element) => match element.name.as_str() {
+                "key" => {
+                    let id = element.require("id")?;
+                    let name = element.get("attr.name").unwrap_or(id);
+
+                    current_key = match element.get("for") {
+                        Some("node") => (Some(nodes.model.declare(id, name)), None),
+                        Some("edge") => (None, Some(edges.model.declare(id, name))),
+                        Some("all") => (
+                            Some(nodes.model.declare(id, name)),
+                            Some(edges.model.declare(id, name)),
+                        ),
+                        _ => (None, None),
+                    };
+                }
+                "default" => {
+                    text = Some(String::new());
+                }
+                "node" => {
+                    nodes.push(vec![element.require("id")?.to_string()]);
+                    current_element = Some(Class::Node);
+                }
+                "edge" => {
+                    edges.push(vec![
+                        element.require("source")?.to_string(),
+                        element.require("target")?.to_string(),
+                    ]);
+                    current_element = Some(Class::Edge);
+                }
+                "data" => {
+                    let key = element.require("key")?;
+
+                    current_data = match current_element {
+                        Some(Class::Node) => nodes.model.get(key),
+                        Some(Class::Edge) => edges.model.get(key),
+                        None => None,
+                    };
+
+                    text = Some(String::new());
+                }
+                _ => (),
+            },
+            XMLEvent::Text(string) => {
+                if let Some(buffer) = text.as_mut() {
+                    buffer.push_str(string);
+                }
+            }
+            XMLEvent::Close(name) => match name {
+                "key" => {
+                    current_key = (None, None);
+                }
+                "default" => {
+                    let value = text.take();
+
+                    if let Some(index) = current_key.0 {
+                        nodes.model.defaults[index] = value.clone();
+                    }
+
+                    if let Some(index) = current_key.1 {
+                        edges.model.defaults[index] = value;
+                    }
+                }
+                "node" | "edge" => {
+                    current_element = None;
+                }
+                "data" => {
+                    if let (Some(index), Some(value)) = (current_data.take(), text.take()) {
+                        let table = match current_element {
+                            Some(Class::Node) => &mut nodes,
+                            Some(Class::Edge) => &mut edges,
+                            None => return Ok(()),
+                        };
+
+                        table.last_mut().unwrap().set_value(index, value);
+                    }
+                }
+                _ => (),
+            },
+        }
+
+        Ok(())
+    })?;
+
+    Ok(GraphTables {
+        nodes: nodes.finish(),
+        edges: edges.finish(),
+    })
+}
+
+fn serialize_graphology_value(value: &Value) -> String {
+    match value {
+        Value::Null => "".to_string(),
+        Value::String(string) => string.clone(),
+        Value::Bool(_) | Value::Number(_) => value.to_string(),
+        Value::Array(_) | Value::Object(_) => serde_json::to_string(value).unwrap(),
+    }
+}
+
+fn read_graphology_attributes(table: &mut TableBuilder, item: &Value) {
+    let Some(Value::Object(attributes)) = item.get("attributes") else {
+        return;
+    };
+
+    let indices = attributes
+        .keys()
+        .map(|key| table.model.declare(key, key))
+        .collect::<Vec<_>>();
+
+    let row = table.last_mut().unwrap();
+
+    for (index, value) in indices.into_iter().zip(attributes.values()) {
+        row.set_value(index, serialize_graphology_value(value));
+    }
+}
+
+pub fn read_graphology(value: &Value) -> CliResult<GraphTables> {
+    let mut nodes = TableBuilder::new(&["node"], &[]);
+    let mut edges = TableBuilder::new(&["source", "target"], &[]);
+
+    let get_string = |item: &Value, name: &str| -> CliResult<String> {
+        item.get(name)
+            .filter(|value| value.is_string() || value.is_number())
+            .map(serialize_graphology_value)
+            .ok_or_else(|| format!("graphology item is missing its \"{}\" key!", name).into())
+    };
+
+    if let Some(Value::Array(items)) = value.get("nodes") {
+        for item in items {
+            nodes.push(vec![get_string(item, "key")?]);
+            read_graphology_attributes(&mut nodes, item);
+        }
+    } else {
+        Err("graphology data does not contain a \"nodes\" array!")?;
+    }
+
+    if let Some(Value::Array(items)) = value.get("edges") {
+        for item in items {
+            edges.push(vec![
+                get_string(item, "source")?,
+                get_string(item, "target")?,
+            ]);
+            read_graphology_attributes(&mut edges, item);
+        }
+    }
+
+    Ok(GraphTables {
+        nodes: nodes.finish(),
+        edges: edges.finish(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_gexf() {
+        let gexf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gexf xmlns="http://gexf.net/1.3" xmlns:viz="http://gexf.net/1.3/viz" version="1.3">
+  <graph defaultedgetype="undirected">
+    <attributes class="node">
+      <attribute id="0" title="community" type="integer">
+        <default>-1</default>
+      </attribute>
+    </attributes>
+    <nodes>
+      <node id="a" label="A &amp; co">
+        <attvalues>
+          <attvalue for="0" value="3"/>
+        </attvalues>
+        <viz:position x="1.5" y="-2"/>
+      </node>
+      <node id="b" label="B"/>
+    </nodes>
+    <edges>
+      <edge source="a" target="b" weight="2"/>
+    </edges>
+  </graph>
+</gexf>"#;
+
+        let tables = read_gexf(gexf.as_bytes()).unwrap();
+
+        assert_eq!(
+            tables.nodes.headers,
+            vec!["node", "label", "x", "y", "community"]
+        );
+        assert_eq!(
+            tables.nodes.rows,
+            vec![
+                vec!["a", "A & co", "1.5", "-2", "3"],
+                vec!["b", "B", "", "", "-1"]
+            ]
+        );
+        assert_eq!(tables.edges.headers, vec!["source", "target", "weight"]);
+        assert_eq!(tables.edges.rows, vec![vec!["a", "b", "2"]]);
+    }
+}
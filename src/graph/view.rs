@@ -0,0 +1,239 @@
+// Terminal rendering of laid out graphs, using braille markers so that each
+// character cell holds a 2x4 grid of dots.
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style};
+use ratatui::symbols;
+use ratatui::text::{Line as TextLine, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::canvas::{Canvas, Line, Points};
+
+use crate::CliResult;
+use crate::collections::HashMap;
+use crate::ratatui::print_ratatui_frame_to_stdout;
+
+const PALETTE: [Color; 6] = [
+    Color::Cyan,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+];
+
+const EDGE_COLOR: Color = Color::DarkGray;
+const OTHER_COLOR: Color = Color::White;
+
+pub struct GraphView {
+    pub positions: Vec<(f64, f64)>,
+    pub edges: Vec<(usize, usize)>,
+    pub categories: Vec<String>,
+}
+
+// NOTE: the most frequent categories get a color from the palette, the
+// remaining ones being grouped together.
+fn assign_colors(categories: &[String]) -> (Vec<Color>, Vec<(String, Color, usize)>) {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+
+    for category in categories.iter() {
+        counts
+            .entry(category.as_str())
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+    }
+
+    let mut sorted_counts = counts.into_iter().collect::<Vec<_>>();
+    sorted_counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+    let mut legend = Vec::new();
+    let mut colors_by_category: HashMap<&str, Color> = HashMap::new();
+    let mut other_count: usize = 0;
+
+    for (i, (category, count)) in sorted_counts.into_iter().enumerate() {
+        match PALETTE.get(i) {
+            Some(color) => {
+                colors_by_category.insert(category, *color);
+                legend.push((category.to_string(), *color, count));
+            }
+            None => {
+                other_count += count;
+            }
+        }
+    }
+
+    if other_count > 0 {
+        legend.push(("<other>".to_string(), OTHER_COLOR, other_count));
+    }
+
+    let colors = categories
+        .iter()
+        .map(|category| {
+            colors_by_category
+                .get(category.as_str())
+                .copied()
+                .unwrap_or(OTHER_COLOR)
+        })
+        .collect();
+
+    (colors, legend)
+}
+
+// NOTE: bounds are padded so that the layout keeps its aspect ratio, knowing
+// that a braille dot is roughly as wide as it is high.
+fn compute_bounds(positions: &[(f64, f64)], cols: usize, rows: usize) -> ([f64; 2], [f64; 2]) {
+    let mut x_bounds = [f64::INFINITY, f64::NEG_INFINITY];
+    let mut y_bounds = [f64::INFINITY, f64::NEG_INFINITY];
+
+    for (x, y) in positions.iter().copied() {
+        x_bounds = [x_bounds[0].min(x), x_bounds[1].max(x)];
+        y_bounds = [y_bounds[0].min(y), y_bounds[1].max(y)];
+    }
+
+    if positions.is_empty() {
+        return ([-1.0, 1.0], [-1.0, 1.0]);
+    }
+
+    let width = (x_bounds[1] - x_bounds[0]).max(f64::EPSILON);
+    let height = (y_bounds[1] - y_bounds[0]).max(f64::EPSILON);
+
+    let dots_ratio = (cols * 2) as f64 / (rows * 4) as f64;
+
+    let (width, height) = if width / height > dots_ratio {
+        (width, width / dots_ratio)
+    } else {
+        (height * dots_ratio, height)
+    };
+
+    let center = (
+        (x_bounds[0] + x_bounds[1]) / 2.0,
+        (y_bounds[0] + y_bounds[1]) / 2.0,
+    );
+
+    // Small margin so that nodes are not drawn on the edges of the canvas
+    let (width, height) = (width * 1.05, height * 1.05);
+
+    (
+        [center.0 - width / 2.0, center.0 + width / 2.0],
+        [center.1 - height / 2.0, center.1 + height / 2.0],
+    )
+}
+
+pub fn print_graph_view(view: &GraphView, cols: usize, rows: usize) -> CliResult<()> {
+    let (colors, legend) = assign_colors(&view.categories);
+
+    let legend_rows = legend.len().min(rows.saturating_sub(3));
+    let canvas_rows = rows - legend_rows;
+
+    let (x_bounds, y_bounds) = compute_bounds(&view.positions, cols, canvas_rows);
+
+    // NOTE: nodes are drawn as a small cross of dots, so they stand out
+    // from the edges
+    let dot = (
+        (x_bounds[1] - x_bounds[0]) / (cols * 2) as f64,
+        (y_bounds[1] - y_bounds[0]) / (canvas_rows * 4) as f64,
+    );
+
+    let mut points_by_color: Vec<(Color, Vec<(f64, f64)>)> = Vec::new();
+
+    for ((x, y), color) in view.positions.iter().copied().zip(colors) {
+        let points = match points_by_color.iter_mut().position(|(c, _)| *c == color) {
+            Some(i) => &mut points_by_color[i].1,
+            None => {
+                points_by_color.push((color, Vec::new()));
+                &mut points_by_color.last_mut().unwrap().1
+            }
+        };
+
+        points.extend([
+            (x, y),
+            (x - dot.0, y),
+            (x + dot.0, y),
+            (x, y - dot.1),
+            (x, y + dot.1),
+        ]);
+    }
+
+    print_ratatui_frame_to_stdout(cols, rows, |frame| {
+        let areas = Layout::vertical([
+            Constraint::Length(canvas_rows as u16),
+            Constraint::Length(legend_rows as u16),
+        ])
+        .split(frame.area());
+
+        let canvas = Canvas::default()
+            .marker(symbols::Marker::Braille)
+            .x_bounds(x_bounds)
+            .y_bounds(y_bounds)
+            .paint(|ctx| {
+                for (source, target) in view.edges.iter().copied() {
+                    let (x1, y1) = view.positions[source];
+                    let (x2, y2) = view.positions[target];
+
+                    ctx.draw(&Line {
+                        x1,
+                        y1,
+                        x2,
+                        y2,
+                        color: EDGE_COLOR,
+                    });
+                }
+
+                ctx.layer();
+
+                for (color, coords) in points_by_color.iter() {
+                    ctx.draw(&Points {
+                        coords,
+                        color: *color,
+                    });
+                }
+            });
+
+        frame.render_widget(canvas, areas[0]);
+
+        let lines = legend
+            .iter()
+            .take(legend_rows)
+            .map(|(category, color, count)| {
+                TextLine::from(vec![
+                    Span::styled("■ ", Style::default().fg(*color)),
+                    Span::raw(format!(
+                        "{} ({})",
+                        if category.is_empty() {
+                            "<empty>"
+                        } else {
+                            category
+                        },
+                        count
+                    )),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        frame.render_widget(Paragraph::new(lines), areas[1]);
+    })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assign_colors() {
+        let mut categories = vec!["a", "b", "b", "c", "d", "e", "f", "g", "h"]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        categories.push("b".to_string());
+
+        let (colors, legend) = assign_colors(&categories);
+
+        assert_eq!(colors[1], Color::Cyan);
+        assert_eq!(colors[0], Color::Red);
+        assert_eq!(colors[8], OTHER_COLOR);
+        assert_eq!(legend.len(), 7);
+        assert_eq!(legend[0], ("b".to_string(), Color::Cyan, 3));
+        assert_eq!(legend[6], ("<other>".to_string(), OTHER_COLOR, 2));
+    }
+}
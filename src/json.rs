@@ -213,6 +213,19 @@ impl Attributes {
         self.entries.push((attr_name_id, value));
     }
 
+    pub fn set(&mut self, key: &str, value: Value) {
+        let attr_name_id = INTERNER.with_borrow_mut(|interner| interner.register(key.to_string()));
+
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.0 == attr_name_id)
+        {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((attr_name_id, value)),
+        }
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &(usize, Value)> {
         self.entries.iter()
     }
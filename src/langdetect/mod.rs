@@ -0,0 +1,213 @@
+// Offline language detection, relying on the script detection & trigram
+// profiles of the whatlang crate. Detection is restricted to the languages
+// sharing the script of the text, and its confidence decreases with the
+// length of the text and the gap between the two best candidates, so that
+// short or ambiguous texts get a low score instead of a spurious guess.
+use whatlang::{Detector, Lang};
+
+// NOTE: (ISO 639-1 code, whatlang language), sorted by code
+pub static LANGUAGES: [(&str, Lang); 69] = [
+    ("af", Lang::Afr),
+    ("ak", Lang::Aka),
+    ("am", Lang::Amh),
+    ("ar", Lang::Ara),
+    ("az", Lang::Aze),
+    ("be", Lang::Bel),
+    ("bg", Lang::Bul),
+    ("bn", Lang::Ben),
+    ("ca", Lang::Cat),
+    ("cs", Lang::Ces),
+    ("da", Lang::Dan),
+    ("de", Lang::Deu),
+    ("el", Lang::Ell),
+    ("en", Lang::Eng),
+    ("eo", Lang::Epo),
+    ("es", Lang::Spa),
+    ("et", Lang::Est),
+    ("fa", Lang::Pes),
+    ("fi", Lang::Fin),
+    ("fr", Lang::Fra),
+    ("gu", Lang::Guj),
+    ("he", Lang::Heb),
+    ("hi", Lang::Hin),
+    ("hr", Lang::Hrv),
+    ("hu", Lang::Hun),
+    ("hy", Lang::Hye),
+    ("id", Lang::Ind),
+    ("it", Lang::Ita),
+    ("ja", Lang::Jpn),
+    ("jv", Lang::Jav),
+    ("ka", Lang::Kat),
+    ("km", Lang::Khm),
+    ("kn", Lang::Kan),
+    ("ko", Lang::Kor),
+    ("la", Lang::Lat),
+    ("lt", Lang::Lit),
+    ("lv", Lang::Lav),
+    ("mk", Lang::Mkd),
+    ("ml", Lang::Mal),
+    ("mr", Lang::Mar),
+    ("my", Lang::Mya),
+    ("nb", Lang::Nob),
+    ("ne", Lang::Nep),
+    ("nl", Lang::Nld),
+    ("or", Lang::Ori),
+    ("pa", Lang::Pan),
+    ("pl", Lang::Pol),
+    ("pt", Lang::Por),
+    ("ro", Lang::Ron),
+    ("ru", Lang::Rus),
+    ("si", Lang::Sin),
+    ("sk", Lang::Slk),
+    ("sl", Lang::Slv),
+    ("sn", Lang::Sna),
+    ("sr", Lang::Srp),
+    ("sv", Lang::Swe),
+    ("ta", Lang::Tam),
+    ("te", Lang::Tel),
+    ("th", Lang::Tha),
+    ("tk", Lang::Tuk),
+    ("tl", Lang::Tgl),
+    ("tr", Lang::Tur),
+    ("uk", Lang::Ukr),
+    ("ur", Lang::Urd),
+    ("uz", Lang::Uzb),
+    ("vi", Lang::Vie),
+    ("yi", Lang::Yid),
+    ("zh", Lang::Cmn),
+    ("zu", Lang::Zul),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detection {
+    pub lang: &'static str,
+    pub confidence: f64,
+}
+
+fn resolve_language(code: &str) -> Option<Lang> {
+    LANGUAGES
+        .binary_search_by_key(&code, |(c, _)| c)
+        .ok()
+        .map(|i| LANGUAGES[i].1)
+}
+
+fn language_code(lang: Lang) -> &'static str {
+    LANGUAGES
+        .iter()
+        .find(|(_, l)| *l == lang)
+        .map(|(code, _)| *code)
+        .unwrap()
+}
+
+pub fn is_supported(lang: &str) -> bool {
+    resolve_language(lang).is_some()
+}
+
+// NOTE: `candidates` can be given to restrict the detection to some languages
+pub fn detect(text: &str, candidates: Option<&[&str]>) -> Option<Detection> {
+    let detector = match candidates {
+        Some(langs) => Detector::with_allowlist(
+            langs
+                .iter()
+                .filter_map(|lang| resolve_language(lang))
+                .collect(),
+        ),
+        None => Detector::new(),
+    };
+
+    let info = detector.detect(text)?;
+    let lang = language_code(info.lang());
+
+    // NOTE: whatlang can still fall back to a language outside of the allowlist
+    // for scripts only used by a single language
+    if candidates.is_some_and(|langs| !langs.contains(&lang)) {
+        return None;
+    }
+
+    // NOTE: whatlang returns a null confidence when it has nothing to go on
+    if info.confidence() <= 0.0 {
+        return None;
+    }
+
+    Some(Detection {
+        lang,
+        confidence: info.confidence(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lang(text: &str) -> Option<&'static str> {
+        detect(text, None).map(|detection| detection.lang)
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            lang("The quick brown fox jumps over the lazy dog"),
+            Some("en")
+        );
+        assert_eq!(lang("Le chat est sur le toit de la maison"), Some("fr"));
+        assert_eq!(lang("Der Hund schläft unter dem Tisch"), Some("de"));
+        assert_eq!(lang("El perro duerme debajo de la mesa"), Some("es"));
+        assert_eq!(
+            lang("Il gatto dorme sotto il tavolo della cucina"),
+            Some("it")
+        );
+        assert_eq!(lang("O cão está a dormir debaixo da mesa"), Some("pt"));
+        assert_eq!(
+            lang("Het is vandaag een mooie dag en ik ga met mijn vrienden naar het strand"),
+            Some("nl")
+        );
+        assert_eq!(lang("Hunden sover under bordet i köket"), Some("sv"));
+        assert_eq!(lang("Pies śpi pod stołem w kuchni"), Some("pl"));
+        assert_eq!(lang("Köpek mutfaktaki masanın altında uyuyor"), Some("tr"));
+        assert_eq!(lang("Собака спит под столом на кухне"), Some("ru"));
+        assert_eq!(lang("Собака спить під столом на кухні"), Some("uk"));
+        assert_eq!(lang("Ο σκύλος κοιμάται κάτω από το τραπέζι"), Some("el"));
+        assert_eq!(lang("الكلب نائم تحت الطاولة"), Some("ar"));
+        assert_eq!(lang("狗在桌子下面睡觉"), Some("zh"));
+        assert_eq!(lang("犬はテーブルの下で寝ています"), Some("ja"));
+        assert_eq!(lang("개가 식탁 아래에서 자고 있다"), Some("ko"));
+        assert_eq!(
+            lang("Tôi thích ăn phở vào buổi sáng với gia đình"),
+            Some("vi")
+        );
+        assert_eq!(lang("1234 !!"), None);
+        assert_eq!(lang(""), None);
+    }
+
+    #[test]
+    fn test_detect_candidates() {
+        let text = "Le chat est sur le toit de la maison";
+
+        assert_eq!(
+            detect(text, Some(&["en", "fr"])).map(|detection| detection.lang),
+            Some("fr")
+        );
+        assert_eq!(
+            detect("De hond slaapt", Some(&["nl", "en"])).map(|detection| detection.lang),
+            Some("nl")
+        );
+        assert_eq!(detect("狗在桌子下面睡觉", Some(&["en", "fr"])), None);
+    }
+
+    #[test]
+    fn test_confidence() {
+        let short = detect("le chat", None).unwrap();
+        let long = detect(
+            "Le chat est sur le toit de la maison et il ne veut pas descendre.",
+            None,
+        )
+        .unwrap();
+
+        assert!(short.confidence <= long.confidence);
+        assert!(long.confidence > 0.9);
+
+        for text in ["lol", "Hola amigo", "ok"] {
+            assert!(detect(text, None).is_none_or(|detection| detection.confidence < 0.5));
+        }
+    }
+}
@@ -17,12 +17,17 @@ mod collections;
 mod config;
 mod graph;
 mod json;
+mod langdetect;
 mod moonblade;
 mod processing;
 mod ratatui;
 mod read;
 mod scales;
 mod select;
+mod snowball;
+mod sparse;
+mod statistics;
+mod stopwords;
 mod temporal;
 mod urls;
 mod util;
@@ -56,18 +61,22 @@ macro_rules! command_list {
     tail        Last rows of CSV file
     slice       Slice rows of CSV file
     top         Find top rows of a CSV file according to some column
+    outliers    Find rows containing outlier values
     sample      Randomly sample CSV data
     bisect      Binary search on sorted CSV data
 
 ## Sort & deduplicate
     sort        Sort CSV data
     dedup       Deduplicate a CSV file
+    neardup     Find near-duplicate documents in a CSV file
     shuffle     Shuffle CSV data
 
 ## Aggregate
     frequency (freq) Show frequency tables
     groupby          Aggregate data by groups of a CSV file
     stats            Compute basic statistics
+    test             Run statistical hypothesis tests
+    regress          Fit linear or logistic regression models
     agg              Aggregate data from CSV file
     bins             Dispatch numeric columns into bins
     window           Compute window aggregations (cumsum, rolling mean, lag etc.)
@@ -308,7 +317,9 @@ enum Command {
     Map,
     Matrix,
     Merge,
+    Neardup,
     Network,
+    Outliers,
     P,
     Parallel,
     Partition,
@@ -320,6 +331,7 @@ enum Command {
     Plot,
     Progress,
     Range,
+    Regress,
     Rename,
     Reverse,
     Run,
@@ -335,6 +347,7 @@ enum Command {
     Split,
     Stats,
     Tail,
+    Test,
     To,
     Tokenize,
     Top,
@@ -397,7 +410,9 @@ impl Command {
             Command::Implode => cmd::implode::run(argv),
             Command::Input => cmd::input::run(argv),
             Command::Join => cmd::join::run(argv),
+            Command::Neardup => cmd::neardup::run(argv),
             Command::Network => cmd::network::run(argv),
+            Command::Outliers => cmd::outliers::run(argv),
             Command::Map => cmd::map::run(argv),
             Command::Matrix => cmd::matrix::run(argv),
             Command::Merge => cmd::merge::run(argv),
@@ -407,6 +422,7 @@ impl Command {
             Command::Plot => cmd::plot::run(argv),
             Command::Progress => cmd::progress::run(argv),
             Command::Range => cmd::range::run(argv),
+            Command::Regress => cmd::regress::run(argv),
             Command::Rename => cmd::rename::run(argv),
             Command::Reverse => cmd::reverse::run(argv),
             Command::Run => cmd::run::run(argv),
@@ -422,6 +438,7 @@ impl Command {
             Command::Split => cmd::split::run(argv),
             Command::Stats => cmd::stats::run(argv),
             Command::Tail => cmd::tail::run(argv),
+            Command::Test => cmd::test::run(argv),
             Command::To => cmd::to::run(argv),
             Command::Tokenize => cmd::tokenize::run(argv),
             Command::Top => cmd::top::run(argv),
@@ -585,3 +602,6 @@ impl_from_error!(Utf8Error);
 impl_from_error!(btoi::ParseIntegerError);
 impl_from_error!(fast_float::Error);
 impl_from_error!(std::num::ParseIntError);
+impl_from_error!(zip::result::ZipError);
+impl_from_error!(quick_xml::Error);
+impl_from_error!(quick_xml::encoding::EncodingError);
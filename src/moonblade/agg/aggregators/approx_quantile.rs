@@ -45,6 +45,10 @@ impl ApproxQuantiles {
         }
     }
 
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
     pub fn finalize(&mut self) {
         self.flush();
     }
@@ -53,6 +57,10 @@ impl ApproxQuantiles {
         self.digest.as_ref().unwrap().estimate_quantile(q)
     }
 
+    pub fn digest(&self) -> &TDigest {
+        self.digest.as_ref().unwrap()
+    }
+
     pub fn merge(&mut self, other: Self) {
         self.flush();
         self.buffer = other.buffer;
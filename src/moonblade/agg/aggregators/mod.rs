@@ -6,6 +6,7 @@ mod extent;
 mod first_last;
 mod frequencies;
 mod numbers;
+pub mod sketches;
 mod sum;
 mod temporal;
 mod types;
@@ -19,7 +20,8 @@ pub use count::Count;
 pub use extent::{ArgExtent, ArgTop, Extent, LexicographicExtent, NumericExtent};
 pub use first_last::{First, Last};
 pub use frequencies::Frequencies;
-pub use numbers::{MedianType, Numbers};
+pub use numbers::{MedianType, Numbers, PairedNumbers};
+pub use sketches::{CountMinSketch, HeavyHitters, HyperLogLog, SketchMerger};
 pub use sum::Sum;
 pub use temporal::TemporalExtent;
 pub use types::{Type, Types};
@@ -1,6 +1,7 @@
 use rayon::prelude::*;
 
 use crate::moonblade::types::DynamicNumber;
+use crate::statistics::{kendall_correlation, spearman_correlation};
 
 // NOTE: removing last '█' char so we can stack sparklines easily
 static SPARKLINE_CHARS: [char; 7] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇'];
@@ -202,6 +203,44 @@ impl Numbers {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct PairedNumbers {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+}
+
+impl PairedNumbers {
+    pub fn new() -> Self {
+        Self {
+            xs: Vec::new(),
+            ys: Vec::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.xs.clear();
+        self.ys.clear();
+    }
+
+    pub fn add(&mut self, x: f64, y: f64) {
+        self.xs.push(x);
+        self.ys.push(y);
+    }
+
+    pub fn spearman_correlation(&self) -> Option<f64> {
+        spearman_correlation(&self.xs, &self.ys)
+    }
+
+    pub fn kendall_correlation(&self) -> Option<f64> {
+        kendall_correlation(&self.xs, &self.ys)
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.xs.extend(other.xs);
+        self.ys.extend(other.ys);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,805 @@
+// NOTE: this module implements serializable & mergeable sketches so that one
+// can pre-aggregate partitions of some data (e.g. daily files) and roll them
+// up later (e.g. weekly) without needing to scan the raw data again.
+
+// Sketches are serialized as a small binary header (magic bytes, version and
+// sketch kind), followed by a payload. The whole thing is then deflated and
+// encoded in base64 so it can safely live in a CSV cell.
+use std::cmp::Reverse;
+use std::io::{Read, Write};
+
+use base64::prelude::*;
+use flate2::{Compression, read::DeflateDecoder, write::DeflateEncoder};
+use tdigest::{Centroid, TDigest};
+
+use crate::collections::HashMap;
+
+const MAGIC: &[u8; 3] = b"xsk";
+const VERSION: u8 = 1;
+
+const HLL_PRECISION: u32 = 14;
+const COUNT_MIN_WIDTH: usize = 2048;
+const COUNT_MIN_DEPTH: usize = 4;
+
+// NOTE: sketches must hash values the same way across processes, versions and
+// platforms, which rules out std's RandomState and ahash. This is therefore
+// a FNV-1a hash, finalized using murmur3's fmix64 for better avalanche.
+fn stable_hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+    hash ^= hash >> 33;
+
+    hash
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SketchKind {
+    HyperLogLog,
+    TDigest,
+    CountMin,
+    HeavyHitters,
+}
+
+impl SketchKind {
+    fn as_byte(&self) -> u8 {
+        match self {
+            Self::HyperLogLog => 0,
+            Self::TDigest => 1,
+            Self::CountMin => 2,
+            Self::HeavyHitters => 3,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        Some(match byte {
+            0 => Self::HyperLogLog,
+            1 => Self::TDigest,
+            2 => Self::CountMin,
+            3 => Self::HeavyHitters,
+            _ => return None,
+        })
+    }
+}
+
+fn encode(kind: SketchKind, payload: &[u8]) -> String {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+
+    encoder.write_all(MAGIC).unwrap();
+    encoder.write_all(&[VERSION, kind.as_byte()]).unwrap();
+    encoder.write_all(payload).unwrap();
+
+    BASE64_STANDARD.encode(encoder.finish().unwrap())
+}
+
+fn decode(string: &str) -> Result<(SketchKind, Vec<u8>), String> {
+    let invalid = || "invalid sketch".to_string();
+
+    let compressed = BASE64_STANDARD
+        .decode(string.trim().as_bytes())
+        .map_err(|_| invalid())?;
+
+    let mut bytes = Vec::new();
+
+    DeflateDecoder::new(compressed.as_slice())
+        .read_to_end(&mut bytes)
+        .map_err(|_| invalid())?;
+
+    if bytes.len() < 5 || &bytes[..3] != MAGIC {
+        return Err(invalid());
+    }
+
+    if bytes[3] != VERSION {
+        return Err(format!("unsupported sketch version {}", bytes[3]));
+    }
+
+    let kind = SketchKind::from_byte(bytes[4]).ok_or_else(invalid)?;
+
+    bytes.drain(..5);
+
+    Ok((kind, bytes))
+}
+
+struct PayloadReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> PayloadReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if self.bytes.len() < n {
+            return Err("truncated sketch".to_string());
+        }
+
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+
+        Ok(head)
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn f64(&mut self) -> Result<f64, String> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn usize(&mut self) -> Result<usize, String> {
+        Ok(self.u64()? as usize)
+    }
+
+    // NOTE: lengths are read from untrusted input, so we check them against
+    // the remaining bytes before allocating anything.
+    fn len(&mut self, item_size: usize) -> Result<usize, String> {
+        let len = self.usize()?;
+
+        if len
+            .checked_mul(item_size)
+            .is_none_or(|size| size > self.bytes.len())
+        {
+            return Err("truncated sketch".to_string());
+        }
+
+        Ok(len)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = self.usize()?;
+
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|_| "invalid sketch".to_string())
+    }
+}
+
+// NOTE: registers are lazily allocated so that aggregating per group does not
+// cost 16KB per group until a value is actually seen.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.registers.clear();
+    }
+
+    fn ensure_registers(&mut self) {
+        if self.registers.is_empty() {
+            self.registers = vec![0; 1 << HLL_PRECISION];
+        }
+    }
+
+    pub fn add(&mut self, bytes: &[u8]) {
+        self.ensure_registers();
+
+        let hash = stable_hash(bytes);
+        let index = (hash >> (64 - HLL_PRECISION)) as usize;
+        let rank =
+            (((hash << HLL_PRECISION) | (1 << (HLL_PRECISION - 1))).leading_zeros() + 1) as u8;
+
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        if other.registers.is_empty() {
+            return;
+        }
+
+        self.ensure_registers();
+
+        for (register, other_register) in self.registers.iter_mut().zip(other.registers.iter()) {
+            if *other_register > *register {
+                *register = *other_register;
+            }
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        if self.registers.is_empty() {
+            return 0;
+        }
+
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+
+        let mut sum = 0.0;
+        let mut zeros: usize = 0;
+
+        for register in self.registers.iter().copied() {
+            sum += 2.0_f64.powi(-(register as i32));
+
+            if register == 0 {
+                zeros += 1;
+            }
+        }
+
+        let estimate = alpha * m * m / sum;
+
+        // NOTE: small range correction using linear counting
+        if estimate <= 2.5 * m && zeros > 0 {
+            return (m * (m / zeros as f64).ln()).round() as usize;
+        }
+
+        estimate.round() as usize
+    }
+
+    pub fn to_base64(&self) -> String {
+        encode(SketchKind::HyperLogLog, &self.registers)
+    }
+
+    fn from_payload(payload: &[u8]) -> Result<Self, String> {
+        if !payload.is_empty() && payload.len() != 1 << HLL_PRECISION {
+            return Err("invalid hll sketch".to_string());
+        }
+
+        Ok(Self {
+            registers: payload.to_vec(),
+        })
+    }
+}
+
+pub fn tdigest_to_base64(digest: &TDigest) -> String {
+    // NOTE: the tdigest crate does not expose its centroids, except through
+    // its serde implementation...
+    let value = serde_json::to_value(digest).unwrap();
+
+    let mut payload = Vec::new();
+
+    for key in ["sum", "count", "max", "min"] {
+        let f = value[key].as_f64().unwrap_or(f64::NAN);
+        payload.extend_from_slice(&f.to_le_bytes());
+    }
+
+    payload.extend_from_slice(&(digest.max_size() as u64).to_le_bytes());
+
+    let centroids = value["centroids"].as_array().unwrap();
+    payload.extend_from_slice(&(centroids.len() as u64).to_le_bytes());
+
+    for centroid in centroids {
+        payload.extend_from_slice(&centroid["mean"].as_f64().unwrap().to_le_bytes());
+        payload.extend_from_slice(&centroid["weight"].as_f64().unwrap().to_le_bytes());
+    }
+
+    encode(SketchKind::TDigest, &payload)
+}
+
+fn tdigest_from_payload(payload: &[u8]) -> Result<TDigest, String> {
+    let mut reader = PayloadReader::new(payload);
+
+    let sum = reader.f64()?;
+    let count = reader.f64()?;
+    let max = reader.f64()?;
+    let min = reader.f64()?;
+    let max_size = reader.usize()?;
+    let len = reader.len(16)?;
+
+    let mut centroids = Vec::with_capacity(len);
+
+    for _ in 0..len {
+        let mean = reader.f64()?;
+        let weight = reader.f64()?;
+
+        centroids.push(Centroid::new(mean, weight));
+    }
+
+    Ok(TDigest::new(centroids, sum, count, max, min, max_size))
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CountMinSketch {
+    total: u64,
+    table: Vec<u64>,
+}
+
+impl CountMinSketch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.total = 0;
+        self.table.clear();
+    }
+
+    fn cells(bytes: &[u8]) -> impl Iterator<Item = usize> {
+        let hash = stable_hash(bytes);
+        let (h1, h2) = (hash & 0xffffffff, hash >> 32);
+
+        (0..COUNT_MIN_DEPTH).map(move |i| {
+            i * COUNT_MIN_WIDTH
+                + (h1.wrapping_add((i as u64).wrapping_mul(h2)) % COUNT_MIN_WIDTH as u64) as usize
+        })
+    }
+
+    pub fn add(&mut self, bytes: &[u8]) {
+        if self.table.is_empty() {
+            self.table = vec![0; COUNT_MIN_WIDTH * COUNT_MIN_DEPTH];
+        }
+
+        self.total += 1;
+
+        for cell in Self::cells(bytes) {
+            self.table[cell] += 1;
+        }
+    }
+
+    pub fn estimate(&self, bytes: &[u8]) -> u64 {
+        if self.table.is_empty() {
+            return 0;
+        }
+
+        Self::cells(bytes)
+            .map(|cell| self.table[cell])
+            .min()
+            .unwrap()
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        if other.table.is_empty() {
+            return;
+        }
+
+        if self.table.is_empty() {
+            self.table = vec![0; COUNT_MIN_WIDTH * COUNT_MIN_DEPTH];
+        }
+
+        self.total += other.total;
+
+        for (cell, other_cell) in self.table.iter_mut().zip(other.table.iter()) {
+            *cell += *other_cell;
+        }
+    }
+
+    pub fn to_base64(&self) -> String {
+        let mut payload = Vec::with_capacity(8 + self.table.len() * 8);
+
+        payload.extend_from_slice(&self.total.to_le_bytes());
+
+        for cell in self.table.iter() {
+            payload.extend_from_slice(&cell.to_le_bytes());
+        }
+
+        encode(SketchKind::CountMin, &payload)
+    }
+
+    fn from_payload(payload: &[u8]) -> Result<Self, String> {
+        let mut reader = PayloadReader::new(payload);
+        let total = reader.u64()?;
+
+        let cells = reader.bytes.len() / 8;
+
+        if reader.bytes.len() % 8 != 0 || (cells != 0 && cells != COUNT_MIN_WIDTH * COUNT_MIN_DEPTH)
+        {
+            return Err("invalid count_min sketch".to_string());
+        }
+
+        let mut table = Vec::with_capacity(cells);
+
+        for _ in 0..cells {
+            table.push(reader.u64()?);
+        }
+
+        Ok(Self { total, table })
+    }
+}
+
+// NOTE: this is the Misra-Gries summary, which is known to be mergeable.
+// Counts are underestimated by at most n / (k + 1).
+// Ref: https://www.cs.utah.edu/~jeffp/papers/merge-summ.pdf
+#[derive(Debug, Clone)]
+pub struct HeavyHitters {
+    k: usize,
+    total: u64,
+    counters: HashMap<String, u64>,
+}
+
+impl HeavyHitters {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            total: 0,
+            counters: HashMap::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.k
+    }
+
+    pub fn clear(&mut self) {
+        self.total = 0;
+        self.counters.clear();
+    }
+
+    // NOTE: we let the counters grow up to 2k before pruning them, so that
+    // decrementing is amortized.
+    fn prune(&mut self, capacity: usize) {
+        if self.counters.len() <= capacity {
+            return;
+        }
+
+        let mut counts = self.counters.values().copied().collect::<Vec<_>>();
+        counts.select_nth_unstable_by_key(capacity, |count| Reverse(*count));
+        let threshold = counts[capacity];
+
+        self.counters.retain(|_, count| {
+            *count = count.saturating_sub(threshold);
+            *count > 0
+        });
+    }
+
+    pub fn add_count(&mut self, value: &str, count: u64) {
+        self.total += count;
+
+        match self.counters.get_mut(value) {
+            Some(current) => *current += count,
+            None => {
+                self.counters.insert(value.to_string(), count);
+
+                if self.counters.len() > self.k * 2 {
+                    self.prune(self.k);
+                }
+            }
+        }
+    }
+
+    pub fn add(&mut self, value: &str) {
+        self.add_count(value, 1);
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.k = self.k.max(other.k);
+        self.total += other.total;
+
+        for (value, count) in other.counters {
+            self.counters
+                .entry(value)
+                .and_modify(|current| *current += count)
+                .or_insert(count);
+        }
+
+        self.prune(self.k * 2);
+    }
+
+    pub fn estimate(&self, value: &str) -> u64 {
+        self.counters.get(value).copied().unwrap_or(0)
+    }
+
+    pub fn top(&self) -> Vec<(&String, u64)> {
+        let mut items = self
+            .counters
+            .iter()
+            .map(|(value, count)| (value, *count))
+            .collect::<Vec<_>>();
+
+        items.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        items.truncate(self.k);
+
+        items
+    }
+
+    pub fn to_base64(&self) -> String {
+        let mut payload = Vec::new();
+
+        payload.extend_from_slice(&(self.k as u64).to_le_bytes());
+        payload.extend_from_slice(&self.total.to_le_bytes());
+
+        // NOTE: we serialize every counter, not only the top k ones, so that
+        // merging serialized sketches keeps the error bound.
+        let mut items = self.counters.iter().collect::<Vec<_>>();
+        items.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+        payload.extend_from_slice(&(items.len() as u64).to_le_bytes());
+
+        for (value, count) in items {
+            payload.extend_from_slice(&(value.len() as u64).to_le_bytes());
+            payload.extend_from_slice(value.as_bytes());
+            payload.extend_from_slice(&count.to_le_bytes());
+        }
+
+        encode(SketchKind::HeavyHitters, &payload)
+    }
+
+    fn from_payload(payload: &[u8]) -> Result<Self, String> {
+        let mut reader = PayloadReader::new(payload);
+
+        let mut sketch = Self::new(reader.usize()?);
+        sketch.total = reader.u64()?;
+
+        let len = reader.len(16)?;
+
+        sketch.counters.reserve(len);
+
+        for _ in 0..len {
+            let value = reader.string()?;
+            let count = reader.u64()?;
+
+            sketch.counters.insert(value, count);
+        }
+
+        Ok(sketch)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Sketch {
+    HyperLogLog(HyperLogLog),
+    TDigest(TDigest),
+    CountMin(CountMinSketch),
+    HeavyHitters(HeavyHitters),
+}
+
+impl Sketch {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::HyperLogLog(_) => "hll",
+            Self::TDigest(_) => "tdigest",
+            Self::CountMin(_) => "count_min",
+            Self::HeavyHitters(_) => "heavy_hitters",
+        }
+    }
+
+    pub fn parse(string: &str) -> Result<Self, String> {
+        let (kind, payload) = decode(string)?;
+
+        Ok(match kind {
+            SketchKind::HyperLogLog => Self::HyperLogLog(HyperLogLog::from_payload(&payload)?),
+            SketchKind::TDigest => Self::TDigest(tdigest_from_payload(&payload)?),
+            SketchKind::CountMin => Self::CountMin(CountMinSketch::from_payload(&payload)?),
+            SketchKind::HeavyHitters => Self::HeavyHitters(HeavyHitters::from_payload(&payload)?),
+        })
+    }
+
+    pub fn to_base64(&self) -> String {
+        match self {
+            Self::HyperLogLog(inner) => inner.to_base64(),
+            Self::TDigest(inner) => tdigest_to_base64(inner),
+            Self::CountMin(inner) => inner.to_base64(),
+            Self::HeavyHitters(inner) => inner.to_base64(),
+        }
+    }
+
+    pub fn merge(&mut self, other: Self) -> Result<(), String> {
+        match (self, other) {
+            (Self::HyperLogLog(inner), Self::HyperLogLog(other_inner)) => inner.merge(&other_inner),
+            (Self::TDigest(inner), Self::TDigest(other_inner)) => {
+                *inner = TDigest::merge_digests(vec![std::mem::take(inner), other_inner]);
+            }
+            (Self::CountMin(inner), Self::CountMin(other_inner)) => inner.merge(&other_inner),
+            (Self::HeavyHitters(inner), Self::HeavyHitters(other_inner)) => {
+                inner.merge(other_inner)
+            }
+            (this, other) => {
+                return Err(format!(
+                    "cannot merge a \"{}\" sketch with a \"{}\" one",
+                    this.kind(),
+                    other.kind()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SketchMerger {
+    sketch: Option<Sketch>,
+    error: Option<String>,
+}
+
+impl SketchMerger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.sketch = None;
+        self.error = None;
+    }
+
+    pub fn add(&mut self, string: &str) -> Result<(), String> {
+        let sketch = Sketch::parse(string)?;
+
+        match self.sketch.as_mut() {
+            None => {
+                self.sketch = Some(sketch);
+            }
+            Some(current) => current.merge(sketch)?,
+        };
+
+        Ok(())
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        if self.error.is_some() {
+            return;
+        }
+
+        if other.error.is_some() {
+            self.error = other.error;
+            return;
+        }
+
+        match (self.sketch.as_mut(), other.sketch) {
+            (_, None) => (),
+            (None, Some(other_sketch)) => {
+                self.sketch = Some(other_sketch);
+            }
+            (Some(sketch), Some(other_sketch)) => {
+                if let Err(msg) = sketch.merge(other_sketch) {
+                    self.error = Some(msg);
+                }
+            }
+        }
+    }
+
+    pub fn get(&self) -> Result<Option<String>, String> {
+        if let Some(msg) = &self.error {
+            return Err(msg.clone());
+        }
+
+        Ok(self.sketch.as_ref().map(|sketch| sketch.to_base64()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hyperloglog() {
+        let mut left = HyperLogLog::new();
+        let mut right = HyperLogLog::new();
+
+        assert_eq!(left.count(), 0);
+
+        for i in 0..5000 {
+            left.add(i.to_string().as_bytes());
+        }
+
+        for i in 2500..10000 {
+            right.add(i.to_string().as_bytes());
+        }
+
+        let count = left.count() as f64;
+        assert!((count - 5000.0).abs() / 5000.0 < 0.02);
+
+        let mut decoded = match Sketch::parse(&left.to_base64()).unwrap() {
+            Sketch::HyperLogLog(inner) => inner,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(decoded, left);
+
+        decoded.merge(&right);
+
+        let count = decoded.count() as f64;
+        assert!((count - 10000.0).abs() / 10000.0 < 0.02);
+    }
+
+    #[test]
+    fn test_tdigest() {
+        let digest =
+            TDigest::new_with_size(100).merge_unsorted((1..=1000).map(f64::from).collect());
+
+        let decoded = match Sketch::parse(&tdigest_to_base64(&digest)).unwrap() {
+            Sketch::TDigest(inner) => inner,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(decoded.count(), 1000.0);
+        assert_eq!(
+            decoded.estimate_quantile(0.5),
+            digest.estimate_quantile(0.5)
+        );
+    }
+
+    #[test]
+    fn test_count_min() {
+        let mut sketch = CountMinSketch::new();
+
+        for _ in 0..10 {
+            sketch.add(b"john");
+        }
+
+        sketch.add(b"mary");
+
+        let mut other = CountMinSketch::new();
+        other.add(b"mary");
+
+        sketch.merge(&other);
+
+        let decoded =
+            CountMinSketch::from_payload(&decode(&sketch.to_base64()).unwrap().1).unwrap();
+
+        assert_eq!(decoded, sketch);
+        assert_eq!(decoded.estimate(b"john"), 10);
+        assert_eq!(decoded.estimate(b"mary"), 2);
+        assert_eq!(decoded.estimate(b"lucy"), 0);
+    }
+
+    #[test]
+    fn test_heavy_hitters() {
+        let mut left = HeavyHitters::new(2);
+        let mut right = HeavyHitters::new(2);
+
+        for value in ["a", "a", "a", "b", "b", "c", "d", "a"] {
+            left.add(value);
+        }
+
+        for value in ["b", "b", "e", "a"] {
+            right.add(value);
+        }
+
+        left.merge(right);
+
+        let top = left
+            .top()
+            .into_iter()
+            .map(|(value, _)| value.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(top, vec!["a", "b"]);
+
+        let mut merger = SketchMerger::new();
+        merger.add(&left.to_base64()).unwrap();
+
+        assert!(merger.add(&HyperLogLog::new().to_base64()).is_err());
+    }
+
+    #[test]
+    fn test_heavy_hitters_serialization() {
+        let mut sketch = HeavyHitters::new(2);
+
+        for value in ["a", "a", "b", "c"] {
+            sketch.add(value);
+        }
+
+        let decoded = match Sketch::parse(&sketch.to_base64()).unwrap() {
+            Sketch::HeavyHitters(inner) => inner,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(decoded.counters, sketch.counters);
+        assert_eq!(decoded.estimate("c"), 1);
+    }
+
+    #[test]
+    fn test_malformed_lengths() {
+        let mut payload = Vec::new();
+
+        for f in [0.0f64; 4] {
+            payload.extend_from_slice(&f.to_le_bytes());
+        }
+
+        payload.extend_from_slice(&100u64.to_le_bytes());
+        payload.extend_from_slice(&u64::MAX.to_le_bytes());
+
+        assert!(Sketch::parse(&encode(SketchKind::TDigest, &payload)).is_err());
+
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&2u64.to_le_bytes());
+        payload.extend_from_slice(&0u64.to_le_bytes());
+        payload.extend_from_slice(&(1u64 << 60).to_le_bytes());
+
+        assert!(Sketch::parse(&encode(SketchKind::HeavyHitters, &payload)).is_err());
+    }
+}
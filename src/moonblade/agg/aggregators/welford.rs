@@ -62,9 +62,9 @@ impl Welford {
         self.m2 = m2;
     }
 
-    // pub fn count(&self) -> usize {
-    //     self.count
-    // }
+    pub fn count(&self) -> usize {
+        self.count
+    }
 
     pub fn mean(&self) -> Option<f64> {
         if self.count == 0 {
@@ -201,6 +201,10 @@ impl CovarianceWelford {
         self.c += delta_x * (y - mean_y);
     }
 
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
     pub fn covariance(&self) -> Option<f64> {
         if self.count < 1 {
             return None;
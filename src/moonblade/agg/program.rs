@@ -7,9 +7,10 @@ use jiff::Unit;
 use simd_csv::ByteRecord;
 
 use super::aggregators::{
-    AllAny, ApproxCardinality, ApproxQuantiles, ArgExtent, ArgTop, Count, CovarianceWelford, First,
-    Frequencies, Last, LexicographicExtent, MedianType, Numbers, NumericExtent, RMSWelford, Sum,
-    TemporalExtent, Type, Types, Values, Welford,
+    AllAny, ApproxCardinality, ApproxQuantiles, ArgExtent, ArgTop, Count, CountMinSketch,
+    CovarianceWelford, First, Frequencies, HeavyHitters, HyperLogLog, Last, LexicographicExtent,
+    MedianType, Numbers, NumericExtent, PairedNumbers, RMSWelford, SketchMerger, Sum,
+    TemporalExtent, Type, Types, Values, Welford, sketches::tdigest_to_base64,
 };
 use crate::collections::{ClusteredInsertHashmap, IndexMap, new_index_map};
 use crate::moonblade::error::{ConcretizationError, EvaluationError, SpecifiedEvaluationError};
@@ -26,6 +27,7 @@ enum Aggregator {
     ArgExtent(Box<ArgExtent>),
     ArgTop(ArgTop),
     Count(Count),
+    CountMinSketch(CountMinSketch),
     CovarianceWelford(CovarianceWelford),
     NumericExtent(NumericExtent),
     First(First),
@@ -33,8 +35,12 @@ enum Aggregator {
     Values(Values),
     LexicographicExtent(LexicographicExtent),
     Frequencies(Box<Frequencies>),
+    HeavyHitters(Box<HeavyHitters>),
+    HyperLogLog(HyperLogLog),
     Numbers(Numbers),
+    PairedNumbers(PairedNumbers),
     RMSWelford(RMSWelford),
+    SketchMerger(Box<SketchMerger>),
     Sum(Sum),
     Types(Types),
     Welford(Welford),
@@ -52,6 +58,7 @@ impl Aggregator {
             ArgExtent(inner) => inner.clear(),
             ArgTop(inner) => inner.clear(),
             Count(inner) => inner.clear(),
+            CountMinSketch(inner) => inner.clear(),
             CovarianceWelford(inner) => inner.clear(),
             NumericExtent(inner) => inner.clear(),
             First(inner) => inner.clear(),
@@ -59,8 +66,12 @@ impl Aggregator {
             Values(inner) => inner.clear(),
             LexicographicExtent(inner) => inner.clear(),
             Frequencies(inner) => inner.clear(),
+            HeavyHitters(inner) => inner.clear(),
+            HyperLogLog(inner) => inner.clear(),
             Numbers(inner) => inner.clear(),
+            PairedNumbers(inner) => inner.clear(),
             RMSWelford(inner) => inner.clear(),
+            SketchMerger(inner) => inner.clear(),
             Sum(inner) => inner.clear(),
             Types(inner) => inner.clear(),
             Welford(inner) => inner.clear(),
@@ -78,6 +89,7 @@ impl Aggregator {
             (ArgExtent(inner), ArgExtent(other_inner)) => inner.merge(*other_inner),
             (ArgTop(inner), ArgTop(other_inner)) => inner.merge(other_inner),
             (Count(inner), Count(other_inner)) => inner.merge(other_inner),
+            (CountMinSketch(inner), CountMinSketch(other_inner)) => inner.merge(&other_inner),
             (CovarianceWelford(inner), CovarianceWelford(other_inner)) => inner.merge(other_inner),
             (NumericExtent(inner), NumericExtent(other_inner)) => inner.merge(other_inner),
             (First(inner), First(other_inner)) => inner.merge(other_inner),
@@ -87,8 +99,12 @@ impl Aggregator {
                 inner.merge(other_inner)
             }
             (Frequencies(inner), Frequencies(other_inner)) => inner.merge(*other_inner),
+            (HeavyHitters(inner), HeavyHitters(other_inner)) => inner.merge(*other_inner),
+            (HyperLogLog(inner), HyperLogLog(other_inner)) => inner.merge(&other_inner),
             (Numbers(inner), Numbers(other_inner)) => inner.merge(other_inner),
+            (PairedNumbers(inner), PairedNumbers(other_inner)) => inner.merge(other_inner),
             (RMSWelford(inner), RMSWelford(other_inner)) => inner.merge(other_inner),
+            (SketchMerger(inner), SketchMerger(other_inner)) => inner.merge(*other_inner),
             (Sum(inner), Sum(other_inner)) => inner.merge(other_inner),
             (Types(inner), Types(other_inner)) => inner.merge(other_inner),
             (Welford(inner), Welford(other_inner)) => inner.merge(other_inner),
@@ -176,6 +192,12 @@ impl Aggregator {
             (ConcreteAggregationMethod::CovarianceSample, Self::CovarianceWelford(inner)) => {
                 DynamicValue::from(inner.sample_covariance())
             }
+            (ConcreteAggregationMethod::KendallCorrelation, Self::PairedNumbers(inner)) => {
+                DynamicValue::from(inner.kendall_correlation())
+            }
+            (ConcreteAggregationMethod::SpearmanCorrelation, Self::PairedNumbers(inner)) => {
+                DynamicValue::from(inner.spearman_correlation())
+            }
             (ConcreteAggregationMethod::Ratio(decimals), Self::Count(inner)) => match decimals {
                 None => DynamicValue::from(inner.ratio()),
                 Some(p) => DynamicValue::from(format!("{:.p$}", inner.ratio(), p = p)),
@@ -329,6 +351,25 @@ impl Aggregator {
             (ConcreteAggregationMethod::Values(separator), Self::Values(inner)) => {
                 DynamicValue::from(inner.join(separator))
             }
+            (ConcreteAggregationMethod::SketchHll, Self::HyperLogLog(inner)) => {
+                DynamicValue::from(inner.to_base64())
+            }
+            (ConcreteAggregationMethod::SketchTDigest, Self::ApproxQuantiles(inner)) => {
+                DynamicValue::from(tdigest_to_base64(inner.digest()))
+            }
+            (ConcreteAggregationMethod::SketchCountMin, Self::CountMinSketch(inner)) => {
+                DynamicValue::from(inner.to_base64())
+            }
+            (ConcreteAggregationMethod::SketchHeavyHitters(_), Self::HeavyHitters(inner)) => {
+                DynamicValue::from(inner.to_base64())
+            }
+            (ConcreteAggregationMethod::MergeSketches, Self::SketchMerger(inner)) => {
+                DynamicValue::from(
+                    inner
+                        .get()
+                        .map_err(|msg| EvaluationError::Custom(msg).specify("merge_sketches"))?,
+                )
+            }
             _ => unreachable!(),
         })
     }
@@ -429,7 +470,8 @@ impl CompositeAggregator {
             ConcreteAggregationMethod::ApproxCardinality => {
                 upsert_boxed_aggregator!(ApproxCardinality)
             }
-            ConcreteAggregationMethod::ApproxQuantile(_) => {
+            ConcreteAggregationMethod::ApproxQuantile(_)
+            | ConcreteAggregationMethod::SketchTDigest => {
                 upsert_boxed_aggregator!(ApproxQuantiles)
             }
             ConcreteAggregationMethod::Count
@@ -442,6 +484,10 @@ impl CompositeAggregator {
             | ConcreteAggregationMethod::Correlation => {
                 upsert_aggregator!(CovarianceWelford)
             }
+            ConcreteAggregationMethod::KendallCorrelation
+            | ConcreteAggregationMethod::SpearmanCorrelation => {
+                upsert_aggregator!(PairedNumbers)
+            }
             ConcreteAggregationMethod::Min | ConcreteAggregationMethod::Max => {
                 // NOTE: if some ArgExtent already exists, we merge into it.
                 match self
@@ -526,6 +572,26 @@ impl CompositeAggregator {
             ConcreteAggregationMethod::Values(_) => {
                 upsert_aggregator!(Values)
             }
+            ConcreteAggregationMethod::SketchHll => {
+                upsert_aggregator!(HyperLogLog)
+            }
+            ConcreteAggregationMethod::SketchCountMin => {
+                upsert_aggregator!(CountMinSketch)
+            }
+            ConcreteAggregationMethod::SketchHeavyHitters(k) => match self.methods.iter().position(
+                |item| matches!(item, Aggregator::HeavyHitters(inner) if inner.capacity() == *k),
+            ) {
+                None => {
+                    let idx = self.methods.len();
+                    self.methods
+                        .push(Aggregator::HeavyHitters(Box::new(HeavyHitters::new(*k))));
+                    idx
+                }
+                Some(idx) => idx,
+            },
+            ConcreteAggregationMethod::MergeSketches => {
+                upsert_boxed_aggregator!(SketchMerger)
+            }
         }
     }
 
@@ -555,7 +621,14 @@ impl CompositeAggregator {
                     Aggregator::Count(count) => {
                         count.add(value.is_truthy());
                     }
-                    Aggregator::CovarianceWelford(_) => unreachable!(),
+                    Aggregator::CountMinSketch(sketch) => {
+                        if !value.is_nullish() {
+                            sketch.add(value.try_as_str()?.as_bytes());
+                        }
+                    }
+                    Aggregator::CovarianceWelford(_) | Aggregator::PairedNumbers(_) => {
+                        unreachable!()
+                    }
                     Aggregator::NumericExtent(extent) => {
                         if !value.is_nullish() {
                             extent.add(value.try_as_number()?);
@@ -598,6 +671,16 @@ impl CompositeAggregator {
                             frequencies.add(value.try_as_str()?.into_owned());
                         }
                     }
+                    Aggregator::HeavyHitters(sketch) => {
+                        if !value.is_nullish() {
+                            sketch.add(&value.try_as_str()?);
+                        }
+                    }
+                    Aggregator::HyperLogLog(sketch) => {
+                        if !value.is_nullish() {
+                            sketch.add(value.try_as_str()?.as_bytes());
+                        }
+                    }
                     Aggregator::Numbers(numbers) => {
                         if !value.is_nullish() {
                             numbers.add(value.try_as_number()?);
@@ -608,6 +691,13 @@ impl CompositeAggregator {
                             inner.add(value.try_as_f64()?);
                         }
                     }
+                    Aggregator::SketchMerger(merger) => {
+                        if !value.is_nullish() {
+                            merger
+                                .add(&value.try_as_str()?)
+                                .map_err(EvaluationError::Custom)?;
+                        }
+                    }
                     Aggregator::Sum(sum) => {
                         if !value.is_nullish() {
                             sum.add(value.try_as_number()?);
@@ -663,16 +753,19 @@ impl CompositeAggregator {
         first: DynamicValue,
         second: DynamicValue,
     ) -> Result<(), EvaluationError> {
+        let (x, y) = match (first.is_nullish(), second.is_nullish()) {
+            (true, false) | (false, true) => return Err(EvaluationError::Custom("unaligned series where given to covariance or correlation functions (both series must have the same number of data points)".to_string())),
+            (false, false) => (first.try_as_f64()?, second.try_as_f64()?),
+            _ => return Ok(()),
+        };
+
         for method in self.methods.iter_mut() {
             match method {
                 Aggregator::CovarianceWelford(covariance_welford) => {
-                    match (first.is_nullish(), second.is_nullish()) {
-                        (true, false) | (false, true) => return Err(EvaluationError::Custom("unaligned series where given to covariance or correlation functions (both series must have the same number of data points)".to_string())),
-                        (false, false) => {
-                            covariance_welford.add(first.try_as_f64()?, second.try_as_f64()?);
-                        }
-                        _ => ()
-                    }
+                    covariance_welford.add(x, y);
+                }
+                Aggregator::PairedNumbers(paired_numbers) => {
+                    paired_numbers.add(x, y);
                 }
                 _ => unreachable!(),
             }
@@ -756,12 +849,14 @@ fn get_function_arguments_parser(name: &str) -> Option<(FunctionArguments, Argum
         "cardinality" => (FunctionArguments::unary(), |_| Ok(Cardinality)),
         "correlation" => (FunctionArguments::binary(), |_| Ok(Correlation)),
         "count" => (FunctionArguments::with_range(0..=1), |_| Ok(Count)),
+        "count_min_sketch" => (FunctionArguments::unary(), |_| Ok(SketchCountMin)),
         "count_seconds" => (FunctionArguments::unary(), |_| Ok(CountTime(Unit::Second))),
         "count_hours" => (FunctionArguments::unary(), |_| Ok(CountTime(Unit::Hour))),
         "count_days" => (FunctionArguments::unary(), |_| Ok(CountTime(Unit::Day))),
         "count_years" => (FunctionArguments::unary(), |_| Ok(CountTime(Unit::Year))),
         "covariance" | "covariance_pop" => (FunctionArguments::binary(), |_| Ok(CovariancePop)),
         "covariance_sample" => (FunctionArguments::binary(), |_| Ok(CovarianceSample)),
+        "kendall_correlation" => (FunctionArguments::binary(), |_| Ok(KendallCorrelation)),
         "dist" => (FunctionArguments::with_range(1..=2), |args| {
             Ok(ConcreteAggregationMethod::Distribution(
                 if args.len() == 1 {
@@ -786,6 +881,16 @@ fn get_function_arguments_parser(name: &str) -> Option<(FunctionArguments, Argum
         }),
         "earliest" => (FunctionArguments::unary(), |_| Ok(Earliest)),
         "first" => (FunctionArguments::unary(), |_| Ok(First)),
+        "heavy_hitters_sketch" => (FunctionArguments::binary(), |args| {
+            let k = cast_as_static_value(args.first().unwrap(), DynamicValue::try_as_usize)?;
+
+            if k == 0 {
+                return Err(ConcretizationError::Custom("expecting k > 0".to_string()));
+            }
+
+            Ok(SketchHeavyHitters(k))
+        }),
+        "hll_sketch" => (FunctionArguments::unary(), |_| Ok(SketchHll)),
         "latest" => (FunctionArguments::unary(), |_| Ok(Latest)),
         "last" => (FunctionArguments::unary(), |_| Ok(Last)),
         "lex_first" => (FunctionArguments::unary(), |_| Ok(LexFirst)),
@@ -809,6 +914,7 @@ fn get_function_arguments_parser(name: &str) -> Option<(FunctionArguments, Argum
                 true,
             ))
         }),
+        "merge_sketches" => (FunctionArguments::unary(), |_| Ok(MergeSketches)),
         "min" => (FunctionArguments::unary(), |_| Ok(Min)),
         "max" => (FunctionArguments::unary(), |_| Ok(Max)),
         "avg" | "mean" => (FunctionArguments::unary(), |_| Ok(Mean)),
@@ -870,9 +976,11 @@ fn get_function_arguments_parser(name: &str) -> Option<(FunctionArguments, Argum
             Ok(Ratio(decimals))
         }),
         "rms" => (FunctionArguments::unary(), |_| Ok(Rms)),
+        "spearman_correlation" => (FunctionArguments::binary(), |_| Ok(SpearmanCorrelation)),
         "stddev" | "stddev_pop" => (FunctionArguments::unary(), |_| Ok(StddevPop)),
         "stddev_sample" => (FunctionArguments::unary(), |_| Ok(StddevSample)),
         "sum" => (FunctionArguments::unary(), |_| Ok(Sum)),
+        "tdigest_sketch" => (FunctionArguments::unary(), |_| Ok(SketchTDigest)),
         "top" => (FunctionArguments::with_range(2..=3), |args| {
             Ok(Top(
                 cast_as_static_value(args.first().unwrap(), DynamicValue::try_as_usize)?,
@@ -908,6 +1016,7 @@ enum ConcreteAggregationMethod {
     Distribution(usize, bool),
     Earliest,
     First,
+    KendallCorrelation,
     Latest,
     Last,
     LexFirst,
@@ -916,6 +1025,7 @@ enum ConcreteAggregationMethod {
     Max,
     Mean,
     Median(MedianType),
+    MergeSketches,
     Mode,
     Modes(String),
     MostCommonValues(usize, String),
@@ -925,6 +1035,11 @@ enum ConcreteAggregationMethod {
     Quantile(f64),
     Ratio(Option<usize>),
     Rms,
+    SketchCountMin,
+    SketchHeavyHitters(usize),
+    SketchHll,
+    SketchTDigest,
+    SpearmanCorrelation,
     Sum,
     Values(String),
     VarPop,
@@ -983,8 +1098,14 @@ pub fn concretize_aggregations(
         let args_count = aggregation.args.len();
 
         if aggregation.args.len() >= 2
-            && ["most_common", "most_common_counts", "top", "argtop"]
-                .contains(&aggregation.func_name.as_str())
+            && [
+                "most_common",
+                "most_common_counts",
+                "top",
+                "argtop",
+                "heavy_hitters_sketch",
+            ]
+            .contains(&aggregation.func_name.as_str())
         {
             aggregation.args.swap(0, 1);
         }
@@ -1001,6 +1122,8 @@ pub fn concretize_aggregations(
                 "covariance_pop",
                 "covariance_sample",
                 "correlation",
+                "kendall_correlation",
+                "spearman_correlation",
             ]
             .contains(&aggregation.func_name.as_str())
         {
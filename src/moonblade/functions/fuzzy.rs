@@ -1,7 +1,11 @@
 use lazy_static::lazy_static;
 use paltoquet::tokenizers::FingerprintTokenizer;
 
+use crate::langdetect;
+use crate::moonblade::error::EvaluationError;
 use crate::moonblade::types::{BoundArguments, DynamicValue};
+use crate::snowball::Language;
+use crate::stopwords;
 
 use super::FunctionResult;
 
@@ -22,3 +26,33 @@ pub fn apply_unidecode(args: BoundArguments) -> FunctionResult {
 
     Ok(DynamicValue::from(unidecode::unidecode(&arg)))
 }
+
+pub fn stem(args: BoundArguments) -> FunctionResult {
+    let (token, language) = args.get2_str()?;
+    let language = Language::try_from(language.as_ref()).map_err(EvaluationError::Custom)?;
+
+    Ok(DynamicValue::from(language.stem(&token)))
+}
+
+pub fn detect_lang(args: BoundArguments) -> FunctionResult {
+    let text = args.get1_str()?;
+    let min_confidence = args
+        .get_not_none(1)
+        .map(|arg| arg.try_as_f64())
+        .transpose()?;
+
+    Ok(match langdetect::detect(&text, None) {
+        Some(detection) if min_confidence.is_none_or(|min| detection.confidence >= min) => {
+            DynamicValue::from(detection.lang)
+        }
+        _ => DynamicValue::None,
+    })
+}
+
+pub fn is_stopword(args: BoundArguments) -> FunctionResult {
+    let (token, langs) = args.get2_str()?;
+
+    Ok(DynamicValue::from(
+        stopwords::is_stopword(&token, &langs).map_err(EvaluationError::Custom)?,
+    ))
+}
@@ -16,6 +16,7 @@ mod io;
 mod maps;
 mod ops;
 mod sequences;
+mod sketches;
 pub mod special;
 mod strings;
 mod temporal;
@@ -269,6 +270,10 @@ pub fn get_function(name: &str) -> Option<(Function, FunctionArguments)> {
         ),
         "shell" => (io::shell, FunctionArguments::unary()),
         "shlex_split" => (io::shlex_split, FunctionArguments::unary()),
+        "sketch_cardinality" => (sketches::sketch_cardinality, FunctionArguments::unary()),
+        "sketch_count" => (sketches::sketch_count, FunctionArguments::binary()),
+        "sketch_quantile" => (sketches::sketch_quantile, FunctionArguments::binary()),
+        "sketch_top" => (sketches::sketch_top, FunctionArguments::unary()),
         "slice" => (sequences::slice, FunctionArguments::with_range(2..=3)),
         "sort" => (
             sequences::sort,
@@ -289,6 +294,9 @@ pub fn get_function(name: &str) -> Option<(Function, FunctionArguments)> {
             FunctionArguments::unary(),
         ),
         "startswith" => (strings::startswith, FunctionArguments::binary()),
+        "stem" => (fuzzy::stem, FunctionArguments::binary()),
+        "is_stopword" => (fuzzy::is_stopword, FunctionArguments::binary()),
+        "detect_lang" => (fuzzy::detect_lang, FunctionArguments::with_range(1..=2)),
         "strftime" => (temporal::strftime, FunctionArguments::binary()),
         "sub" => (ops::sub, FunctionArguments::variadic(2)),
         "sum" => (ops::sum, FunctionArguments::unary()),
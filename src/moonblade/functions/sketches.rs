@@ -0,0 +1,65 @@
+use crate::moonblade::agg::aggregators::sketches::Sketch;
+use crate::moonblade::error::EvaluationError;
+use crate::moonblade::types::{BoundArgument, BoundArguments, DynamicValue};
+
+use super::FunctionResult;
+
+fn parse_sketch(arg: &BoundArgument) -> Result<Sketch, EvaluationError> {
+    Sketch::parse(&arg.try_as_str()?).map_err(EvaluationError::Custom)
+}
+
+fn unexpected_kind(sketch: &Sketch, expected: &str) -> EvaluationError {
+    EvaluationError::Custom(format!(
+        "expected a {} sketch but got a \"{}\" one",
+        expected,
+        sketch.kind()
+    ))
+}
+
+pub fn sketch_cardinality(args: BoundArguments) -> FunctionResult {
+    match parse_sketch(args.get1())? {
+        Sketch::HyperLogLog(inner) => Ok(DynamicValue::from(inner.count())),
+        sketch => Err(unexpected_kind(&sketch, "\"hll\"")),
+    }
+}
+
+pub fn sketch_quantile(args: BoundArguments) -> FunctionResult {
+    let (sketch_arg, q_arg) = args.get2();
+    let q = q_arg.try_as_f64()?;
+
+    match parse_sketch(sketch_arg)? {
+        Sketch::TDigest(inner) => Ok(if inner.is_empty() {
+            DynamicValue::None
+        } else {
+            DynamicValue::from(inner.estimate_quantile(q))
+        }),
+        sketch => Err(unexpected_kind(&sketch, "\"tdigest\"")),
+    }
+}
+
+pub fn sketch_count(args: BoundArguments) -> FunctionResult {
+    let (sketch_arg, value_arg) = args.get2();
+    let value = value_arg.try_as_str()?;
+
+    match parse_sketch(sketch_arg)? {
+        Sketch::CountMin(inner) => Ok(DynamicValue::from(inner.estimate(value.as_bytes()) as i64)),
+        Sketch::HeavyHitters(inner) => Ok(DynamicValue::from(inner.estimate(&value) as i64)),
+        sketch => Err(unexpected_kind(
+            &sketch,
+            "\"count_min\" or \"heavy_hitters\"",
+        )),
+    }
+}
+
+pub fn sketch_top(args: BoundArguments) -> FunctionResult {
+    match parse_sketch(args.get1())? {
+        Sketch::HeavyHitters(inner) => Ok(DynamicValue::from(
+            inner
+                .top()
+                .into_iter()
+                .map(|(value, _)| DynamicValue::from(value.as_str()))
+                .collect::<Vec<_>>(),
+        )),
+        sketch => Err(unexpected_kind(&sketch, "\"heavy_hitters\"")),
+    }
+}
@@ -0,0 +1,180 @@
+// https://snowballstem.org/algorithms/dutch/stemmer.html
+use super::{Word, standard_regions};
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'è')
+}
+
+fn is_valid_s_ending(c: char) -> bool {
+    !is_vowel(c) && c != 'j'
+}
+
+fn undouble(word: &mut Word) {
+    if word.ends_with("kk") || word.ends_with("dd") || word.ends_with("tt") {
+        word.chars.pop();
+    }
+}
+
+// NOTE: deletes given suffix if in R1 and preceded by a valid en-ending,
+// i.e. a non-vowel, not part of "gem".
+fn remove_en_ending(word: &mut Word, suffix: &str, r1: usize) -> bool {
+    if word.suffix_start(suffix) < r1 {
+        return false;
+    }
+
+    let start = word.suffix_start(suffix);
+
+    if start == 0
+        || is_vowel(word.chars[start - 1])
+        || word.chars[..start].ends_with(&['g', 'e', 'm'])
+    {
+        return false;
+    }
+
+    word.remove(suffix);
+    undouble(word);
+
+    true
+}
+
+fn step2(word: &mut Word, r1: usize) -> bool {
+    if word.ends_with_in("e", r1) && word.char_before("e").is_some_and(|c| !is_vowel(c)) {
+        word.remove("e");
+        undouble(word);
+
+        return true;
+    }
+
+    false
+}
+
+pub fn stem(string: &str) -> String {
+    let mut word = Word::new(string);
+
+    word.map_chars(|c| match c {
+        'ä' | 'á' => 'a',
+        'ë' | 'é' => 'e',
+        'ï' | 'í' => 'i',
+        'ö' | 'ó' => 'o',
+        'ü' | 'ú' => 'u',
+        _ => c,
+    });
+
+    // Marking vowels as consonants
+    let n = word.len();
+
+    for i in 0..n {
+        let c = word.chars[i];
+
+        if c == 'y' && (i == 0 || is_vowel(word.chars[i - 1])) {
+            word.chars[i] = 'Y';
+        } else if c == 'i'
+            && i > 0
+            && i + 1 < n
+            && is_vowel(word.chars[i - 1])
+            && is_vowel(word.chars[i + 1])
+        {
+            word.chars[i] = 'I';
+        }
+    }
+
+    let (r1, r2) = standard_regions(&word.chars, is_vowel);
+    let r1 = r1.max(3);
+
+    // Step 1
+    match word.longest_suffix(&["heden", "en", "ene", "s", "se"]) {
+        Some("heden") => {
+            if word.ends_with_in("heden", r1) {
+                word.replace("heden", "heid");
+            }
+        }
+        Some(suffix @ ("en" | "ene")) => {
+            remove_en_ending(&mut word, suffix, r1);
+        }
+        Some(suffix @ ("s" | "se")) => {
+            if word.ends_with_in(suffix, r1)
+                && word.char_before(suffix).is_some_and(is_valid_s_ending)
+            {
+                word.remove(suffix);
+            }
+        }
+        _ => (),
+    };
+
+    let e_removed = step2(&mut word, r1);
+
+    // Step 3a
+    if word.ends_with_in("heid", r2) && word.char_before("heid") != Some('c') {
+        word.remove("heid");
+
+        if word.ends_with("en") {
+            remove_en_ending(&mut word, "en", r1);
+        }
+    }
+
+    // Step 3b
+    match word.longest_suffix(&["end", "ing", "ig", "lijk", "baar", "bar"]) {
+        Some(suffix @ ("end" | "ing")) => {
+            if word.ends_with_in(suffix, r2) {
+                word.remove(suffix);
+
+                if word.ends_with_in("ig", r2) && word.char_before("ig") != Some('e') {
+                    word.remove("ig");
+                } else {
+                    undouble(&mut word);
+                }
+            }
+        }
+        Some("ig") => {
+            if word.ends_with_in("ig", r2) && word.char_before("ig") != Some('e') {
+                word.remove("ig");
+            }
+        }
+        Some("lijk") => {
+            if word.ends_with_in("lijk", r2) {
+                word.remove("lijk");
+                step2(&mut word, r1);
+            }
+        }
+        Some("baar") => {
+            if word.ends_with_in("baar", r2) {
+                word.remove("baar");
+            }
+        }
+        Some("bar") => {
+            if word.ends_with_in("bar", r2) && e_removed {
+                word.remove("bar");
+            }
+        }
+        _ => (),
+    };
+
+    // Step 4
+    let n = word.len();
+
+    if n >= 4 {
+        let (c, v1, v2, d) = (
+            word.chars[n - 4],
+            word.chars[n - 3],
+            word.chars[n - 2],
+            word.chars[n - 1],
+        );
+
+        if !is_vowel(c)
+            && v1 == v2
+            && matches!(v1, 'a' | 'e' | 'o' | 'u')
+            && !is_vowel(d)
+            && d != 'I'
+        {
+            word.chars.remove(n - 2);
+        }
+    }
+
+    word.map_chars(|c| match c {
+        'I' => 'i',
+        'Y' => 'y',
+        _ => c,
+    });
+
+    word.to_string()
+}
@@ -0,0 +1,264 @@
+// Porter2 stemmer: https://snowballstem.org/algorithms/english/stemmer.html
+use super::{Word, region_after};
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y')
+}
+
+fn is_double(chars: &[char]) -> bool {
+    let n = chars.len();
+
+    n >= 2
+        && chars[n - 1] == chars[n - 2]
+        && matches!(
+            chars[n - 1],
+            'b' | 'd' | 'f' | 'g' | 'm' | 'n' | 'p' | 'r' | 't'
+        )
+}
+
+fn is_valid_li_ending(c: char) -> bool {
+    matches!(c, 'c' | 'd' | 'e' | 'g' | 'h' | 'k' | 'm' | 'n' | 'r' | 't')
+}
+
+fn ends_with_short_syllable(chars: &[char]) -> bool {
+    let n = chars.len();
+
+    if n == 2 {
+        return is_vowel(chars[0]) && !is_vowel(chars[1]);
+    }
+
+    n >= 3
+        && !is_vowel(chars[n - 3])
+        && is_vowel(chars[n - 2])
+        && !is_vowel(chars[n - 1])
+        && !matches!(chars[n - 1], 'w' | 'x' | 'Y')
+}
+
+fn exception(word: &str) -> Option<&'static str> {
+    Some(match word {
+        "skis" => "ski",
+        "skies" => "sky",
+        "dying" => "die",
+        "lying" => "lie",
+        "tying" => "tie",
+        "idly" => "idl",
+        "gently" => "gentl",
+        "ugly" => "ugli",
+        "early" => "earli",
+        "only" => "onli",
+        "singly" => "singl",
+        "sky" => "sky",
+        "news" => "news",
+        "howe" => "howe",
+        "atlas" => "atlas",
+        "cosmos" => "cosmos",
+        "bias" => "bias",
+        "andes" => "andes",
+        _ => return None,
+    })
+}
+
+fn contains_vowel(chars: &[char]) -> bool {
+    chars.iter().any(|c| is_vowel(*c))
+}
+
+pub fn stem(string: &str) -> String {
+    if let Some(stem) = exception(string) {
+        return stem.to_string();
+    }
+
+    if string.chars().count() <= 2 {
+        return string.to_string();
+    }
+
+    let mut word = Word::new(string.strip_prefix('\'').unwrap_or(string));
+
+    // Marking consonant y
+    for i in 0..word.len() {
+        if word.chars[i] == 'y' && (i == 0 || is_vowel(word.chars[i - 1])) {
+            word.chars[i] = 'Y';
+        }
+    }
+
+    let r1 = if word.chars.starts_with(&['g', 'e', 'n', 'e', 'r'])
+        || word.chars.starts_with(&['a', 'r', 's', 'e', 'n'])
+    {
+        5
+    } else if word.chars.starts_with(&['c', 'o', 'm', 'm', 'u', 'n']) {
+        6
+    } else {
+        region_after(&word.chars, 0, is_vowel)
+    };
+
+    let r2 = region_after(&word.chars, r1, is_vowel);
+
+    // Step 0
+    if let Some(suffix) = word.longest_suffix(&["'s'", "'s", "'"]) {
+        word.remove(suffix);
+    }
+
+    // Step 1a
+    match word.longest_suffix(&["sses", "ied", "ies", "us", "ss", "s"]) {
+        Some("sses") => word.replace("sses", "ss"),
+        Some(suffix @ ("ied" | "ies")) => {
+            if word.suffix_start(suffix) > 1 {
+                word.replace(suffix, "i");
+            } else {
+                word.replace(suffix, "ie");
+            }
+        }
+        Some("s") => {
+            let start = word.suffix_start("s");
+
+            if start >= 2 && contains_vowel(&word.chars[..start - 1]) {
+                word.remove("s");
+            }
+        }
+        _ => (),
+    };
+
+    let current = word.to_string();
+
+    if matches!(
+        current.as_str(),
+        "inning" | "outing" | "canning" | "herring" | "earring" | "proceed" | "exceed" | "succeed"
+    ) {
+        return current;
+    }
+
+    // Step 1b
+    match word.longest_suffix(&["eed", "eedly", "ed", "edly", "ing", "ingly"]) {
+        Some(suffix @ ("eed" | "eedly")) => {
+            if word.suffix_start(suffix) >= r1 {
+                word.replace(suffix, "ee");
+            }
+        }
+        Some(suffix) => {
+            let start = word.suffix_start(suffix);
+
+            if contains_vowel(&word.chars[..start]) {
+                word.remove(suffix);
+
+                if word.ends_with("at") || word.ends_with("bl") || word.ends_with("iz") {
+                    word.chars.push('e');
+                } else if is_double(&word.chars) {
+                    word.chars.pop();
+                } else if r1 >= word.len() && ends_with_short_syllable(&word.chars) {
+                    word.chars.push('e');
+                }
+            }
+        }
+        None => (),
+    };
+
+    // Step 1c
+    if matches!(word.last(), Some('y' | 'Y')) && word.len() > 2 {
+        let before = word.chars[word.len() - 2];
+
+        if !is_vowel(before) {
+            let n = word.len();
+            word.chars[n - 1] = 'i';
+        }
+    }
+
+    // Step 2
+    let step2 = [
+        ("tional", "tion"),
+        ("enci", "ence"),
+        ("anci", "ance"),
+        ("abli", "able"),
+        ("entli", "ent"),
+        ("izer", "ize"),
+        ("ization", "ize"),
+        ("ational", "ate"),
+        ("ation", "ate"),
+        ("ator", "ate"),
+        ("alism", "al"),
+        ("aliti", "al"),
+        ("alli", "al"),
+        ("fulness", "ful"),
+        ("ousli", "ous"),
+        ("ousness", "ous"),
+        ("iveness", "ive"),
+        ("iviti", "ive"),
+        ("biliti", "ble"),
+        ("bli", "ble"),
+        ("ogi", "og"),
+        ("fulli", "ful"),
+        ("lessli", "less"),
+        ("li", ""),
+    ];
+
+    let suffixes = step2.iter().map(|(s, _)| *s).collect::<Vec<_>>();
+
+    if let Some(suffix) = word.longest_suffix(&suffixes) {
+        if word.suffix_start(suffix) >= r1 {
+            let replacement = step2.iter().find(|(s, _)| *s == suffix).unwrap().1;
+
+            match suffix {
+                "ogi" => {
+                    if word.char_before(suffix) == Some('l') {
+                        word.replace(suffix, replacement);
+                    }
+                }
+                "li" => {
+                    if word.char_before(suffix).is_some_and(is_valid_li_ending) {
+                        word.remove(suffix);
+                    }
+                }
+                _ => word.replace(suffix, replacement),
+            }
+        }
+    }
+
+    // Step 3
+    let step3 = [
+        ("tional", "tion"),
+        ("ational", "ate"),
+        ("alize", "al"),
+        ("icate", "ic"),
+        ("iciti", "ic"),
+        ("ical", "ic"),
+        ("ful", ""),
+        ("ness", ""),
+        ("ative", ""),
+    ];
+
+    let suffixes = step3.iter().map(|(s, _)| *s).collect::<Vec<_>>();
+
+    if let Some(suffix) = word.longest_suffix(&suffixes) {
+        let start = word.suffix_start(suffix);
+
+        if start >= r1 && (suffix != "ative" || start >= r2) {
+            let replacement = step3.iter().find(|(s, _)| *s == suffix).unwrap().1;
+            word.replace(suffix, replacement);
+        }
+    }
+
+    // Step 4
+    if let Some(suffix) = word.longest_suffix(&[
+        "al", "ance", "ence", "er", "ic", "able", "ible", "ant", "ement", "ment", "ent", "ism",
+        "ate", "iti", "ous", "ive", "ize", "ion",
+    ]) {
+        if word.suffix_start(suffix) >= r2
+            && (suffix != "ion" || matches!(word.char_before(suffix), Some('s' | 't')))
+        {
+            word.remove(suffix);
+        }
+    }
+
+    // Step 5
+    if word.ends_with("e") {
+        let start = word.len() - 1;
+
+        if start >= r2 || (start >= r1 && !ends_with_short_syllable(&word.chars[..start])) {
+            word.chars.pop();
+        }
+    } else if word.ends_with("ll") && word.len() > r2 {
+        word.chars.pop();
+    }
+
+    word.map_chars(|c| if c == 'Y' { 'y' } else { c });
+
+    word.to_string()
+}
@@ -0,0 +1,416 @@
+// https://snowballstem.org/algorithms/french/stemmer.html
+use super::{Word, standard_regions};
+
+fn is_vowel(c: char) -> bool {
+    matches!(
+        c,
+        'a' | 'e'
+            | 'i'
+            | 'o'
+            | 'u'
+            | 'y'
+            | 'â'
+            | 'à'
+            | 'ë'
+            | 'é'
+            | 'ê'
+            | 'è'
+            | 'ï'
+            | 'î'
+            | 'ô'
+            | 'û'
+            | 'ù'
+    )
+}
+
+fn rv(chars: &[char]) -> usize {
+    let n = chars.len();
+
+    if n >= 2 && is_vowel(chars[0]) && is_vowel(chars[1]) {
+        return 3.min(n);
+    }
+
+    for prefix in [['p', 'a', 'r'], ['c', 'o', 'l'], ['t', 'a', 'p']] {
+        if chars.starts_with(&prefix) {
+            return 3;
+        }
+    }
+
+    (1..n)
+        .find(|i| is_vowel(chars[*i]))
+        .map(|i| i + 1)
+        .unwrap_or(n)
+}
+
+fn step1(word: &mut Word, rv: usize, r1: usize, r2: usize) -> (bool, bool) {
+    let suffix = match word.longest_suffix(&[
+        "ance",
+        "iqUe",
+        "isme",
+        "able",
+        "iste",
+        "eux",
+        "ances",
+        "iqUes",
+        "ismes",
+        "ables",
+        "istes",
+        "atrice",
+        "ateur",
+        "ation",
+        "atrices",
+        "ateurs",
+        "ations",
+        "logie",
+        "logies",
+        "usion",
+        "ution",
+        "usions",
+        "utions",
+        "ence",
+        "ences",
+        "ement",
+        "ements",
+        "ité",
+        "ités",
+        "if",
+        "ive",
+        "ifs",
+        "ives",
+        "eaux",
+        "aux",
+        "euse",
+        "euses",
+        "issement",
+        "issements",
+        "amment",
+        "emment",
+        "ment",
+        "ments",
+    ]) {
+        None => return (false, false),
+        Some(suffix) => suffix,
+    };
+
+    let start = word.suffix_start(suffix);
+    let in_r2 = start >= r2;
+
+    let before = word.chars.clone();
+
+    match suffix {
+        "ance" | "iqUe" | "isme" | "able" | "iste" | "eux" | "ances" | "iqUes" | "ismes"
+        | "ables" | "istes" => {
+            if in_r2 {
+                word.remove(suffix);
+            }
+        }
+        "atrice" | "ateur" | "ation" | "atrices" | "ateurs" | "ations" => {
+            if in_r2 {
+                word.remove(suffix);
+
+                if word.ends_with("ic") {
+                    if word.ends_with_in("ic", r2) {
+                        word.remove("ic");
+                    } else {
+                        word.replace("ic", "iqU");
+                    }
+                }
+            }
+        }
+        "logie" | "logies" => {
+            if in_r2 {
+                word.replace(suffix, "log");
+            }
+        }
+        "usion" | "ution" | "usions" | "utions" => {
+            if in_r2 {
+                word.replace(suffix, "u");
+            }
+        }
+        "ence" | "ences" => {
+            if in_r2 {
+                word.replace(suffix, "ent");
+            }
+        }
+        "ement" | "ements" => {
+            if start >= rv {
+                word.remove(suffix);
+
+                if word.ends_with_in("iv", r2) {
+                    word.remove("iv");
+
+                    if word.ends_with_in("at", r2) {
+                        word.remove("at");
+                    }
+                } else if word.ends_with("eus") {
+                    if word.ends_with_in("eus", r2) {
+                        word.remove("eus");
+                    } else if word.ends_with_in("eus", r1) {
+                        word.replace("eus", "eux");
+                    }
+                } else if word.ends_with_in("abl", r2) {
+                    word.remove("abl");
+                } else if word.ends_with_in("iqU", r2) {
+                    word.remove("iqU");
+                } else if word.ends_with_in("ièr", rv) {
+                    word.replace("ièr", "i");
+                } else if word.ends_with_in("Ièr", rv) {
+                    word.replace("Ièr", "i");
+                }
+            }
+        }
+        "ité" | "ités" => {
+            if in_r2 {
+                word.remove(suffix);
+
+                if word.ends_with("abil") {
+                    if word.ends_with_in("abil", r2) {
+                        word.remove("abil");
+                    } else {
+                        word.replace("abil", "abl");
+                    }
+                } else if word.ends_with("ic") {
+                    if word.ends_with_in("ic", r2) {
+                        word.remove("ic");
+                    } else {
+                        word.replace("ic", "iqU");
+                    }
+                } else if word.ends_with_in("iv", r2) {
+                    word.remove("iv");
+                }
+            }
+        }
+        "if" | "ive" | "ifs" | "ives" => {
+            if in_r2 {
+                word.remove(suffix);
+
+                if word.ends_with_in("at", r2) {
+                    word.remove("at");
+
+                    if word.ends_with("ic") {
+                        if word.ends_with_in("ic", r2) {
+                            word.remove("ic");
+                        } else {
+                            word.replace("ic", "iqU");
+                        }
+                    }
+                }
+            }
+        }
+        "eaux" => word.replace(suffix, "eau"),
+        "aux" => {
+            if start >= r1 {
+                word.replace(suffix, "al");
+            }
+        }
+        "euse" | "euses" => {
+            if in_r2 {
+                word.remove(suffix);
+            } else if start >= r1 {
+                word.replace(suffix, "eux");
+            }
+        }
+        "issement" | "issements" => {
+            if start >= r1 && word.char_before(suffix).is_some_and(|c| !is_vowel(c)) {
+                word.remove(suffix);
+            }
+        }
+        "amment" => {
+            if start >= rv {
+                word.replace(suffix, "ant");
+            }
+
+            return (word.chars != before, true);
+        }
+        "emment" => {
+            if start >= rv {
+                word.replace(suffix, "ent");
+            }
+
+            return (word.chars != before, true);
+        }
+        "ment" | "ments" => {
+            if start > rv && is_vowel(word.chars[start - 1]) {
+                word.remove(suffix);
+            }
+
+            return (word.chars != before, true);
+        }
+        _ => unreachable!(),
+    };
+
+    (word.chars != before, false)
+}
+
+fn step2a(word: &mut Word, rv: usize) -> bool {
+    let suffix = word.longest_suffix_in(
+        &[
+            "îmes", "ît", "îtes", "i", "ie", "ies", "ir", "ira", "irai", "iraIent", "irais",
+            "irait", "iras", "irent", "irez", "iriez", "irions", "irons", "iront", "is",
+            "issaIent", "issais", "issait", "issant", "issante", "issantes", "issants", "isse",
+            "issent", "isses", "issez", "issiez", "issions", "issons", "it",
+        ],
+        rv,
+    );
+
+    if let Some(suffix) = suffix {
+        let start = word.suffix_start(suffix);
+
+        if start > rv && !is_vowel(word.chars[start - 1]) {
+            word.remove(suffix);
+            return true;
+        }
+    }
+
+    false
+}
+
+fn step2b(word: &mut Word, rv: usize, r2: usize) -> bool {
+    let suffix = word.longest_suffix_in(
+        &[
+            "ions", "é", "ée", "ées", "és", "èrent", "er", "era", "erai", "eraIent", "erais",
+            "erait", "eras", "erez", "eriez", "erions", "erons", "eront", "ez", "iez", "âmes",
+            "ât", "âtes", "a", "ai", "aIent", "ais", "ait", "ant", "ante", "antes", "ants", "as",
+            "asse", "assent", "asses", "assiez", "assions",
+        ],
+        rv,
+    );
+
+    let suffix = match suffix {
+        Some(suffix) => suffix,
+        None => return false,
+    };
+
+    match suffix {
+        "ions" => {
+            if word.suffix_start(suffix) >= r2 {
+                word.remove(suffix);
+                return true;
+            }
+
+            false
+        }
+        "âmes" | "ât" | "âtes" | "a" | "ai" | "aIent" | "ais" | "ait" | "ant" | "ante"
+        | "antes" | "ants" | "as" | "asse" | "assent" | "asses" | "assiez" | "assions" => {
+            word.remove(suffix);
+
+            if word.ends_with_in("e", rv) {
+                word.remove("e");
+            }
+
+            true
+        }
+        _ => {
+            word.remove(suffix);
+            true
+        }
+    }
+}
+
+fn step4(word: &mut Word, rv: usize, r2: usize) {
+    if word.ends_with("s")
+        && word
+            .char_before("s")
+            .is_some_and(|c| !matches!(c, 'a' | 'i' | 'o' | 'u' | 'è' | 's'))
+    {
+        word.remove("s");
+    }
+
+    match word.longest_suffix_in(&["ion", "ier", "ière", "Ier", "Ière", "e", "ë"], rv) {
+        Some("ion") => {
+            let start = word.suffix_start("ion");
+
+            if start > rv && start >= r2 && matches!(word.char_before("ion"), Some('s' | 't')) {
+                word.remove("ion");
+            }
+        }
+        Some(suffix @ ("ier" | "ière" | "Ier" | "Ière")) => word.replace(suffix, "i"),
+        Some("e") => word.remove("e"),
+        Some("ë") => {
+            if word.ends_with("guë") {
+                word.remove("ë");
+            }
+        }
+        _ => (),
+    }
+}
+
+pub fn stem(string: &str) -> String {
+    let mut word = Word::new(string);
+
+    // Marking vowels as consonants
+    let n = word.len();
+
+    for i in 0..n {
+        let c = word.chars[i];
+        let prev_vowel = i > 0 && is_vowel(word.chars[i - 1]);
+        let next_vowel = i + 1 < n && is_vowel(word.chars[i + 1]);
+
+        match c {
+            'u' | 'i' if prev_vowel && next_vowel => {
+                word.chars[i] = c.to_ascii_uppercase();
+            }
+            'y' if prev_vowel || next_vowel => {
+                word.chars[i] = 'Y';
+            }
+            'u' if i > 0 && word.chars[i - 1] == 'q' => {
+                word.chars[i] = 'U';
+            }
+            _ => (),
+        }
+    }
+
+    let rv = rv(&word.chars);
+    let (r1, r2) = standard_regions(&word.chars, is_vowel);
+
+    // NOTE: step 2 is also done when one of the "ment" suffixes was found in
+    // step 1, in which case only step 2 decides whether step 3 or 4 is done.
+    let (step1_altered, found_ment) = step1(&mut word, rv, r1, r2);
+
+    let altered = if step1_altered && !found_ment {
+        true
+    } else {
+        step2a(&mut word, rv) || step2b(&mut word, rv, r2)
+    };
+
+    if altered {
+        // Step 3
+        match word.last() {
+            Some('Y') => word.replace("Y", "i"),
+            Some('ç') => word.replace("ç", "c"),
+            _ => (),
+        }
+    } else {
+        step4(&mut word, rv, r2);
+    }
+
+    // Step 5
+    if word.ends_with("enn")
+        || word.ends_with("onn")
+        || word.ends_with("ett")
+        || word.ends_with("ell")
+        || word.ends_with("eill")
+    {
+        word.chars.pop();
+    }
+
+    // Step 6
+    let mut i = word.len();
+
+    while i > 0 && !is_vowel(word.chars[i - 1]) {
+        i -= 1;
+    }
+
+    if i > 0 && i < word.len() && matches!(word.chars[i - 1], 'é' | 'è') {
+        word.chars[i - 1] = 'e';
+    }
+
+    word.map_chars(|c| match c {
+        'I' => 'i',
+        'U' => 'u',
+        'Y' => 'y',
+        _ => c,
+    });
+
+    word.to_string()
+}
@@ -0,0 +1,130 @@
+// https://snowballstem.org/algorithms/german/stemmer.html
+use super::{Word, standard_regions};
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'ä' | 'ö' | 'ü')
+}
+
+fn is_valid_s_ending(c: char) -> bool {
+    matches!(
+        c,
+        'b' | 'd' | 'f' | 'g' | 'h' | 'k' | 'l' | 'm' | 'n' | 'r' | 't'
+    )
+}
+
+fn is_valid_st_ending(c: char) -> bool {
+    c != 'r' && is_valid_s_ending(c)
+}
+
+pub fn stem(string: &str) -> String {
+    let mut word = Word::new(&string.replace('ß', "ss"));
+
+    // Marking vowels as consonants
+    let n = word.len();
+
+    for i in 1..n.saturating_sub(1) {
+        if matches!(word.chars[i], 'u' | 'y')
+            && is_vowel(word.chars[i - 1])
+            && is_vowel(word.chars[i + 1])
+        {
+            word.chars[i] = word.chars[i].to_ascii_uppercase();
+        }
+    }
+
+    let (r1, r2) = standard_regions(&word.chars, is_vowel);
+    let r1 = r1.max(3);
+
+    // Step 1
+    match word.longest_suffix(&["em", "ern", "er", "e", "en", "es", "s"]) {
+        Some(suffix @ ("em" | "ern" | "er")) => {
+            if word.suffix_start(suffix) >= r1 {
+                word.remove(suffix);
+            }
+        }
+        Some(suffix @ ("e" | "en" | "es")) => {
+            if word.suffix_start(suffix) >= r1 {
+                word.remove(suffix);
+
+                if word.ends_with("niss") {
+                    word.chars.pop();
+                }
+            }
+        }
+        Some("s") => {
+            if word.suffix_start("s") >= r1 && word.char_before("s").is_some_and(is_valid_s_ending)
+            {
+                word.remove("s");
+            }
+        }
+        _ => (),
+    };
+
+    // Step 2
+    match word.longest_suffix(&["en", "er", "est", "st"]) {
+        Some("st") => {
+            let start = word.suffix_start("st");
+
+            if start >= r1 && start >= 4 && is_valid_st_ending(word.chars[start - 1]) {
+                word.remove("st");
+            }
+        }
+        Some(suffix) => {
+            if word.suffix_start(suffix) >= r1 {
+                word.remove(suffix);
+            }
+        }
+        None => (),
+    };
+
+    // Step 3
+    match word.longest_suffix(&["end", "ung", "ig", "ik", "isch", "lich", "heit", "keit"]) {
+        Some(suffix @ ("end" | "ung")) => {
+            if word.suffix_start(suffix) >= r2 {
+                word.remove(suffix);
+
+                if word.ends_with_in("ig", r2) && word.char_before("ig") != Some('e') {
+                    word.remove("ig");
+                }
+            }
+        }
+        Some(suffix @ ("ig" | "ik" | "isch")) => {
+            if word.suffix_start(suffix) >= r2 && word.char_before(suffix) != Some('e') {
+                word.remove(suffix);
+            }
+        }
+        Some(suffix @ ("lich" | "heit")) => {
+            if word.suffix_start(suffix) >= r2 {
+                word.remove(suffix);
+
+                if word.ends_with_in("er", r1) {
+                    word.remove("er");
+                } else if word.ends_with_in("en", r1) {
+                    word.remove("en");
+                }
+            }
+        }
+        Some("keit") => {
+            if word.suffix_start("keit") >= r2 {
+                word.remove("keit");
+
+                if word.ends_with_in("lich", r2) {
+                    word.remove("lich");
+                } else if word.ends_with_in("ig", r2) {
+                    word.remove("ig");
+                }
+            }
+        }
+        _ => (),
+    };
+
+    word.map_chars(|c| match c {
+        'U' => 'u',
+        'Y' => 'y',
+        'ä' => 'a',
+        'ö' => 'o',
+        'ü' => 'u',
+        _ => c,
+    });
+
+    word.to_string()
+}
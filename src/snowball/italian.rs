@@ -0,0 +1,207 @@
+// https://snowballstem.org/algorithms/italian/stemmer.html
+use super::{Word, romance_rv, standard_regions};
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'à' | 'è' | 'ì' | 'ò' | 'ù')
+}
+
+fn step0(word: &mut Word, rv: usize) {
+    let pronoun = match word.longest_suffix(&[
+        "ci", "gli", "la", "le", "li", "lo", "mi", "ne", "si", "ti", "vi", "sene", "gliela",
+        "gliele", "glieli", "glielo", "gliene", "mela", "mele", "meli", "melo", "mene", "tela",
+        "tele", "teli", "telo", "tene", "cela", "cele", "celi", "celo", "cene", "vela", "vele",
+        "veli", "velo", "vene",
+    ]) {
+        Some(pronoun) => pronoun,
+        None => return,
+    };
+
+    let start = word.suffix_start(pronoun);
+    let before = Word {
+        chars: word.chars[..start].to_vec(),
+    };
+
+    match before.longest_suffix(&["ando", "endo", "ar", "er", "ir"]) {
+        Some(ending) if before.suffix_start(ending) >= rv => {
+            word.chars.truncate(start);
+
+            if matches!(ending, "ar" | "er" | "ir") {
+                word.chars.push('e');
+            }
+        }
+        _ => (),
+    }
+}
+
+fn step1(word: &mut Word, rv: usize, r1: usize, r2: usize) -> bool {
+    let suffix = match word.longest_suffix(&[
+        "anza", "anze", "ico", "ici", "ica", "ice", "iche", "ichi", "ismo", "ismi", "abile",
+        "abili", "ibile", "ibili", "ista", "iste", "isti", "istà", "istè", "istì", "oso", "osi",
+        "osa", "ose", "mente", "atrice", "atrici", "ante", "anti", "azione", "azioni", "atore",
+        "atori", "logia", "logie", "uzione", "uzioni", "usione", "usioni", "enza", "enze",
+        "amento", "amenti", "imento", "imenti", "amente", "ità", "ivo", "ivi", "iva", "ive",
+    ]) {
+        None => return false,
+        Some(suffix) => suffix,
+    };
+
+    let start = word.suffix_start(suffix);
+    let before = word.len();
+
+    match suffix {
+        "azione" | "azioni" | "atore" | "atori" => {
+            if start >= r2 {
+                word.remove(suffix);
+
+                if word.ends_with_in("ic", r2) {
+                    word.remove("ic");
+                }
+            }
+        }
+        "logia" | "logie" => {
+            if start >= r2 {
+                word.replace(suffix, "log");
+            }
+        }
+        "uzione" | "uzioni" | "usione" | "usioni" => {
+            if start >= r2 {
+                word.replace(suffix, "u");
+            }
+        }
+        "enza" | "enze" => {
+            if start >= r2 {
+                word.replace(suffix, "ente");
+            }
+        }
+        "amento" | "amenti" | "imento" | "imenti" => {
+            if start >= rv {
+                word.remove(suffix);
+            }
+        }
+        "amente" => {
+            if start >= r1 {
+                word.remove(suffix);
+
+                if word.ends_with_in("iv", r2) {
+                    word.remove("iv");
+
+                    if word.ends_with_in("at", r2) {
+                        word.remove("at");
+                    }
+                } else if let Some(ending) = word.longest_suffix_in(&["os", "ic", "abil"], r2) {
+                    word.remove(ending);
+                }
+            }
+        }
+        "ità" => {
+            if start >= r2 {
+                word.remove(suffix);
+
+                if let Some(ending) = word.longest_suffix_in(&["abil", "ic", "iv"], r2) {
+                    word.remove(ending);
+                }
+            }
+        }
+        "ivo" | "ivi" | "iva" | "ive" => {
+            if start >= r2 {
+                word.remove(suffix);
+
+                if word.ends_with_in("at", r2) {
+                    word.remove("at");
+
+                    if word.ends_with_in("ic", r2) {
+                        word.remove("ic");
+                    }
+                }
+            }
+        }
+        _ => {
+            if start >= r2 {
+                word.remove(suffix);
+            }
+        }
+    };
+
+    word.len() != before
+}
+
+fn step2(word: &mut Word, rv: usize) {
+    if let Some(suffix) = word.longest_suffix_in(
+        &[
+            "ammo", "ando", "ano", "are", "arono", "asse", "assero", "assi", "assimo", "ata",
+            "ate", "ati", "ato", "ava", "avamo", "avano", "avate", "avi", "avo", "emmo", "enda",
+            "ende", "endi", "endo", "erà", "erai", "eranno", "ere", "erebbe", "erebbero", "erei",
+            "eremmo", "eremo", "ereste", "eresti", "erete", "erò", "erono", "essero", "ete", "eva",
+            "evamo", "evano", "evate", "evi", "evo", "iamo", "immo", "irà", "irai", "iranno",
+            "ire", "irebbe", "irebbero", "irei", "iremmo", "iremo", "ireste", "iresti", "irete",
+            "irò", "irono", "isca", "iscano", "isce", "isci", "isco", "iscono", "issero", "ita",
+            "ite", "iti", "ito", "iva", "ivamo", "ivano", "ivate", "ivi", "ivo", "ono", "uta",
+            "ute", "uti", "uto", "ar", "ir",
+        ],
+        rv,
+    ) {
+        word.remove(suffix);
+    }
+}
+
+pub fn stem(string: &str) -> String {
+    let mut word = Word::new(string);
+
+    word.map_chars(|c| match c {
+        'á' => 'à',
+        'é' => 'è',
+        'í' => 'ì',
+        'ó' => 'ò',
+        'ú' => 'ù',
+        _ => c,
+    });
+
+    // Marking vowels as consonants
+    let n = word.len();
+
+    for i in 1..n {
+        let c = word.chars[i];
+
+        if c == 'u' && word.chars[i - 1] == 'q' {
+            word.chars[i] = 'U';
+        } else if matches!(c, 'u' | 'i')
+            && i + 1 < n
+            && is_vowel(word.chars[i - 1])
+            && is_vowel(word.chars[i + 1])
+        {
+            word.chars[i] = c.to_ascii_uppercase();
+        }
+    }
+
+    let rv = romance_rv(&word.chars, is_vowel);
+    let (r1, r2) = standard_regions(&word.chars, is_vowel);
+
+    step0(&mut word, rv);
+
+    if !step1(&mut word, rv, r1, r2) {
+        step2(&mut word, rv);
+    }
+
+    // Step 3a
+    if let Some(suffix) = word.longest_suffix_in(&["a", "e", "i", "o", "à", "è", "ì", "ò"], rv)
+    {
+        word.remove(suffix);
+
+        if word.ends_with_in("i", rv) {
+            word.remove("i");
+        }
+    }
+
+    // Step 3b
+    if word.ends_with_in("ch", rv) || word.ends_with_in("gh", rv) {
+        word.chars.pop();
+    }
+
+    word.map_chars(|c| match c {
+        'I' => 'i',
+        'U' => 'u',
+        _ => c,
+    });
+
+    word.to_string()
+}
@@ -0,0 +1,378 @@
+// Pure Rust implementations of some of the Snowball stemmers, as described
+// in https://snowballstem.org/algorithms/
+mod dutch;
+mod english;
+mod french;
+mod german;
+mod italian;
+mod portuguese;
+mod spanish;
+mod swedish;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Language {
+    Dutch,
+    English,
+    French,
+    German,
+    Italian,
+    Portuguese,
+    Spanish,
+    Swedish,
+}
+
+impl Language {
+    pub fn stem(&self, word: &str) -> String {
+        let lowercase = word.to_lowercase();
+
+        match self {
+            Self::Dutch => dutch::stem(&lowercase),
+            Self::English => english::stem(&lowercase),
+            Self::French => french::stem(&lowercase),
+            Self::German => german::stem(&lowercase),
+            Self::Italian => italian::stem(&lowercase),
+            Self::Portuguese => portuguese::stem(&lowercase),
+            Self::Spanish => spanish::stem(&lowercase),
+            Self::Swedish => swedish::stem(&lowercase),
+        }
+    }
+}
+
+impl TryFrom<&str> for Language {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value.to_lowercase().as_str() {
+            "nl" | "dutch" => Self::Dutch,
+            "en" | "english" => Self::English,
+            "fr" | "french" => Self::French,
+            "de" | "german" => Self::German,
+            "it" | "italian" => Self::Italian,
+            "pt" | "portuguese" => Self::Portuguese,
+            "es" | "spanish" => Self::Spanish,
+            "sv" | "swedish" => Self::Swedish,
+            _ => {
+                return Err(format!(
+                    "unsupported snowball stemmer language \"{}\"",
+                    value
+                ));
+            }
+        })
+    }
+}
+
+// NOTE: stemmers work on a vector of chars, since most of them need to
+// handle non-ascii letters and to compute regions in terms of characters.
+struct Word {
+    chars: Vec<char>,
+}
+
+impl Word {
+    fn new(string: &str) -> Self {
+        Self {
+            chars: string.chars().collect(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    fn ends_with(&self, suffix: &str) -> bool {
+        let mut i = self.chars.len();
+
+        for c in suffix.chars().rev() {
+            if i == 0 || self.chars[i - 1] != c {
+                return false;
+            }
+
+            i -= 1;
+        }
+
+        true
+    }
+
+    // NOTE: returns whether the word ends with given suffix, which should
+    // also start at or after given position.
+    fn ends_with_in(&self, suffix: &str, start: usize) -> bool {
+        self.ends_with(suffix) && self.suffix_start(suffix) >= start
+    }
+
+    fn suffix_start(&self, suffix: &str) -> usize {
+        self.chars.len() - suffix.chars().count()
+    }
+
+    fn longest_suffix<'a>(&self, suffixes: &[&'a str]) -> Option<&'a str> {
+        suffixes
+            .iter()
+            .copied()
+            .filter(|suffix| self.ends_with(suffix))
+            .max_by_key(|suffix| suffix.chars().count())
+    }
+
+    // NOTE: same as `longest_suffix` but only considering suffixes starting
+    // at or after given position, e.g. when search is confined to some region.
+    fn longest_suffix_in<'a>(&self, suffixes: &[&'a str], start: usize) -> Option<&'a str> {
+        suffixes
+            .iter()
+            .copied()
+            .filter(|suffix| self.ends_with_in(suffix, start))
+            .max_by_key(|suffix| suffix.chars().count())
+    }
+
+    fn remove(&mut self, suffix: &str) {
+        let start = self.suffix_start(suffix);
+        self.chars.truncate(start);
+    }
+
+    fn replace(&mut self, suffix: &str, replacement: &str) {
+        self.remove(suffix);
+        self.chars.extend(replacement.chars());
+    }
+
+    fn last(&self) -> Option<char> {
+        self.chars.last().copied()
+    }
+
+    fn char_before(&self, suffix: &str) -> Option<char> {
+        let start = self.suffix_start(suffix);
+
+        if start == 0 {
+            None
+        } else {
+            Some(self.chars[start - 1])
+        }
+    }
+
+    fn map_chars<F: Fn(char) -> char>(&mut self, f: F) {
+        for c in self.chars.iter_mut() {
+            *c = f(*c);
+        }
+    }
+}
+
+impl std::fmt::Display for Word {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in self.chars.iter() {
+            write!(f, "{}", c)?;
+        }
+
+        Ok(())
+    }
+}
+
+// NOTE: region after the first non-vowel following a vowel, starting the
+// search from given position.
+fn region_after(chars: &[char], start: usize, is_vowel: fn(char) -> bool) -> usize {
+    for i in (start + 1)..chars.len() {
+        if is_vowel(chars[i - 1]) && !is_vowel(chars[i]) {
+            return i + 1;
+        }
+    }
+
+    chars.len()
+}
+
+fn standard_regions(chars: &[char], is_vowel: fn(char) -> bool) -> (usize, usize) {
+    let r1 = region_after(chars, 0, is_vowel);
+    let r2 = region_after(chars, r1, is_vowel);
+
+    (r1, r2)
+}
+
+// NOTE: RV region as defined by the Spanish, Italian & Portuguese stemmers
+fn romance_rv(chars: &[char], is_vowel: fn(char) -> bool) -> usize {
+    if chars.len() < 2 {
+        return chars.len();
+    }
+
+    if !is_vowel(chars[1]) {
+        (2..chars.len())
+            .find(|i| is_vowel(chars[*i]))
+            .map(|i| i + 1)
+            .unwrap_or(chars.len())
+    } else if is_vowel(chars[0]) {
+        (2..chars.len())
+            .find(|i| !is_vowel(chars[*i]))
+            .map(|i| i + 1)
+            .unwrap_or(chars.len())
+    } else {
+        3.min(chars.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_stems(language: Language, pairs: &[(&str, &str)]) {
+        for (word, expected) in pairs {
+            assert_eq!(&language.stem(word), expected, "stemming \"{}\"", word);
+        }
+    }
+
+    #[test]
+    fn test_regions() {
+        let chars = "beautiful".chars().collect::<Vec<_>>();
+        let is_vowel = |c| "aeiouy".contains(c);
+
+        assert_eq!(standard_regions(&chars, is_vowel), (5, 7));
+
+        let chars = "animadversion".chars().collect::<Vec<_>>();
+        assert_eq!(standard_regions(&chars, is_vowel), (2, 4));
+
+        let chars = "macho".chars().collect::<Vec<_>>();
+        assert_eq!(romance_rv(&chars, is_vowel), 3);
+
+        let chars = "oliva".chars().collect::<Vec<_>>();
+        assert_eq!(romance_rv(&chars, is_vowel), 3);
+
+        let chars = "trabajo".chars().collect::<Vec<_>>();
+        assert_eq!(romance_rv(&chars, is_vowel), 3);
+
+        let chars = "áureo".chars().collect::<Vec<_>>();
+        assert_eq!(romance_rv(&chars, |c| "aeiouáéíóú".contains(c)), 3);
+    }
+
+    #[test]
+    fn test_language() {
+        assert_eq!(Language::try_from("en"), Ok(Language::English));
+        assert_eq!(Language::try_from("French"), Ok(Language::French));
+        assert!(Language::try_from("klingon").is_err());
+    }
+
+    #[test]
+    fn test_english() {
+        assert_stems(
+            Language::English,
+            &[
+                ("caresses", "caress"),
+                ("ponies", "poni"),
+                ("ties", "tie"),
+                ("cats", "cat"),
+                ("running", "run"),
+                ("hopping", "hop"),
+                ("hoped", "hope"),
+                ("happily", "happili"),
+                ("generously", "generous"),
+                ("generations", "generat"),
+                ("connection", "connect"),
+                ("abilities", "abil"),
+                ("hopeful", "hope"),
+                ("knightly", "knight"),
+                ("consolidated", "consolid"),
+                ("sky", "sky"),
+                ("skies", "sky"),
+                ("dying", "die"),
+                ("news", "news"),
+                ("succeeded", "succeed"),
+                ("Replacement", "replac"),
+                ("a", "a"),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_french() {
+        assert_stems(
+            Language::French,
+            &[
+                ("continuellement", "continuel"),
+                ("continuer", "continu"),
+                ("continuité", "continu"),
+                ("chevaux", "cheval"),
+                ("abandonnée", "abandon"),
+                ("majestueusement", "majestu"),
+                ("mangeaient", "mang"),
+                ("finissons", "fin"),
+                ("châteaux", "château"),
+                ("nationales", "national"),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_german() {
+        assert_stems(
+            Language::German,
+            &[
+                ("häuser", "haus"),
+                ("aufeinanderfolgenden", "aufeinanderfolg"),
+                ("kategorischen", "kategor"),
+                ("straße", "strass"),
+                ("freundlichkeit", "freundlich"),
+                ("katzen", "katz"),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_spanish() {
+        assert_stems(
+            Language::Spanish,
+            &[
+                ("chicas", "chic"),
+                ("canciones", "cancion"),
+                ("rápidamente", "rapid"),
+                ("comiéndolo", "com"),
+                ("trabajaban", "trabaj"),
+                ("nacionalidad", "nacional"),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_italian() {
+        assert_stems(
+            Language::Italian,
+            &[
+                ("gattini", "gattin"),
+                ("abbandonata", "abbandon"),
+                ("velocemente", "veloc"),
+                ("parlando", "parl"),
+                ("nazionalità", "nazional"),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_portuguese() {
+        assert_stems(
+            Language::Portuguese,
+            &[
+                ("meninos", "menin"),
+                ("nações", "naçõ"),
+                ("rapidamente", "rapid"),
+                ("falávamos", "fal"),
+                ("felicidade", "felic"),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_dutch() {
+        assert_stems(
+            Language::Dutch,
+            &[
+                ("katten", "kat"),
+                ("huizen", "huiz"),
+                ("mogelijkheden", "mogelijk"),
+                ("lichamelijk", "licham"),
+                ("maan", "man"),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_swedish() {
+        assert_stems(
+            Language::Swedish,
+            &[
+                ("flickorna", "flick"),
+                ("husen", "hus"),
+                ("kärlekens", "kärlek"),
+                ("möjligheterna", "möj"),
+            ],
+        );
+    }
+}
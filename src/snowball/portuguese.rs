@@ -0,0 +1,158 @@
+// https://snowballstem.org/algorithms/portuguese/stemmer.html
+use super::{Word, romance_rv, standard_regions};
+
+fn is_vowel(c: char) -> bool {
+    matches!(
+        c,
+        'a' | 'e' | 'i' | 'o' | 'u' | 'á' | 'é' | 'í' | 'ó' | 'ú' | 'â' | 'ê' | 'ô'
+    )
+}
+
+fn step1(word: &mut Word, rv: usize, r1: usize, r2: usize) -> bool {
+    let suffix = match word.longest_suffix(&[
+        "eza", "ezas", "ico", "ica", "icos", "icas", "ismo", "ismos", "ável", "ível", "ista",
+        "istas", "oso", "osa", "osos", "osas", "amento", "amentos", "imento", "imentos", "adora",
+        "ador", "aça~o", "adoras", "adores", "aço~es", "ante", "antes", "ância", "logia", "logias",
+        "uça~o", "uço~es", "ência", "ências", "amente", "mente", "idade", "idades", "iva", "ivo",
+        "ivas", "ivos", "ira", "iras",
+    ]) {
+        None => return false,
+        Some(suffix) => suffix,
+    };
+
+    let start = word.suffix_start(suffix);
+    let before = word.len();
+
+    match suffix {
+        "logia" | "logias" => {
+            if start >= r2 {
+                word.replace(suffix, "log");
+            }
+        }
+        "uça~o" | "uço~es" => {
+            if start >= r2 {
+                word.replace(suffix, "u");
+            }
+        }
+        "ência" | "ências" => {
+            if start >= r2 {
+                word.replace(suffix, "ente");
+            }
+        }
+        "amente" => {
+            if start >= r1 {
+                word.remove(suffix);
+
+                if word.ends_with_in("iv", r2) {
+                    word.remove("iv");
+
+                    if word.ends_with_in("at", r2) {
+                        word.remove("at");
+                    }
+                } else if let Some(ending) = word.longest_suffix_in(&["os", "ic", "ad"], r2) {
+                    word.remove(ending);
+                }
+            }
+        }
+        "mente" => {
+            if start >= r2 {
+                word.remove(suffix);
+
+                if let Some(ending) = word.longest_suffix_in(&["ante", "avel", "ível"], r2) {
+                    word.remove(ending);
+                }
+            }
+        }
+        "idade" | "idades" => {
+            if start >= r2 {
+                word.remove(suffix);
+
+                if let Some(ending) = word.longest_suffix_in(&["abil", "ic", "iv"], r2) {
+                    word.remove(ending);
+                }
+            }
+        }
+        "iva" | "ivo" | "ivas" | "ivos" => {
+            if start >= r2 {
+                word.remove(suffix);
+
+                if word.ends_with_in("at", r2) {
+                    word.remove("at");
+                }
+            }
+        }
+        "ira" | "iras" => {
+            if start >= rv && word.char_before(suffix) == Some('e') {
+                word.replace(suffix, "ir");
+            }
+        }
+        _ => {
+            if start >= r2 {
+                word.remove(suffix);
+            }
+        }
+    };
+
+    word.len() != before
+}
+
+fn step2(word: &mut Word, rv: usize) -> bool {
+    if let Some(suffix) = word.longest_suffix_in(
+        &[
+            "ada", "ida", "ia", "aria", "eria", "iria", "ará", "ara", "erá", "era", "irá", "ava",
+            "asse", "esse", "isse", "aste", "este", "iste", "ei", "arei", "erei", "irei", "am",
+            "iam", "ariam", "eriam", "iriam", "aram", "eram", "iram", "avam", "em", "arem", "erem",
+            "irem", "assem", "essem", "issem", "ado", "ido", "ando", "endo", "indo", "ara~o",
+            "era~o", "ira~o", "ar", "er", "ir", "as", "adas", "idas", "ias", "arias", "erias",
+            "irias", "arás", "aras", "erás", "eras", "irás", "avas", "es", "ardes", "erdes",
+            "irdes", "ares", "eres", "ires", "asses", "esses", "isses", "astes", "estes", "istes",
+            "is", "ais", "eis", "íeis", "aríeis", "eríeis", "iríeis", "áreis", "éreis", "íreis",
+            "ásseis", "ésseis", "ísseis", "áveis", "ados", "idos", "ámos", "amos", "íamos",
+            "aríamos", "eríamos", "iríamos", "áramos", "éramos", "íramos", "ávamos", "emos",
+            "aremos", "eremos", "iremos", "ássemos", "êssemos", "íssemos", "imos", "armos",
+            "ermos", "irmos", "eu", "iu", "ou", "ira", "iras",
+        ],
+        rv,
+    ) {
+        word.remove(suffix);
+        return true;
+    }
+
+    false
+}
+
+pub fn stem(string: &str) -> String {
+    let mut word = Word::new(&string.replace('ã', "a~").replace('õ', "o~"));
+
+    let rv = romance_rv(&word.chars, is_vowel);
+    let (r1, r2) = standard_regions(&word.chars, is_vowel);
+
+    let altered = step1(&mut word, rv, r1, r2) || step2(&mut word, rv);
+
+    if altered {
+        // Step 3
+        if word.ends_with_in("i", rv) && word.char_before("i") == Some('c') {
+            word.remove("i");
+        }
+    } else {
+        // Step 4
+        if let Some(suffix) = word.longest_suffix_in(&["os", "a", "i", "o", "á", "í", "ó"], rv) {
+            word.remove(suffix);
+        }
+    }
+
+    // Step 5
+    if let Some(suffix) = word.longest_suffix_in(&["e", "é", "ê"], rv) {
+        word.remove(suffix);
+
+        if (word.ends_with("gu") && word.ends_with_in("u", rv))
+            || (word.ends_with("ci") && word.ends_with_in("i", rv))
+        {
+            word.chars.pop();
+        }
+    } else if word.ends_with("ç") {
+        word.replace("ç", "c");
+    }
+
+    word.to_string().replace("a~", "ã").replace("o~", "õ")
+}
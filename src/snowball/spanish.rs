@@ -0,0 +1,232 @@
+// https://snowballstem.org/algorithms/spanish/stemmer.html
+use super::{Word, romance_rv, standard_regions};
+
+fn is_vowel(c: char) -> bool {
+    matches!(
+        c,
+        'a' | 'e' | 'i' | 'o' | 'u' | 'á' | 'é' | 'í' | 'ó' | 'ú' | 'ü'
+    )
+}
+
+fn remove_acute_accents(c: char) -> char {
+    match c {
+        'á' => 'a',
+        'é' => 'e',
+        'í' => 'i',
+        'ó' => 'o',
+        'ú' => 'u',
+        _ => c,
+    }
+}
+
+fn step0(word: &mut Word, rv: usize) {
+    let pronoun = match word.longest_suffix(&[
+        "me", "se", "sela", "selo", "selas", "selos", "la", "le", "lo", "las", "les", "los", "nos",
+    ]) {
+        Some(pronoun) => pronoun,
+        None => return,
+    };
+
+    let start = word.suffix_start(pronoun);
+    let before = Word {
+        chars: word.chars[..start].to_vec(),
+    };
+
+    let ending = match before.longest_suffix(&[
+        "iéndo", "ándo", "ár", "ér", "ír", "ando", "iendo", "ar", "er", "ir", "yendo",
+    ]) {
+        Some(ending) if before.suffix_start(ending) >= rv => ending,
+        _ => return,
+    };
+
+    match ending {
+        "iéndo" | "ándo" | "ár" | "ér" | "ír" => {
+            word.chars.truncate(start);
+
+            let n = word.suffix_start(ending);
+
+            for c in word.chars[n..].iter_mut() {
+                *c = remove_acute_accents(*c);
+            }
+        }
+        "yendo" => {
+            if before.char_before("yendo") == Some('u') {
+                word.chars.truncate(start);
+            }
+        }
+        _ => word.chars.truncate(start),
+    }
+}
+
+fn step1(word: &mut Word, r1: usize, r2: usize) -> bool {
+    let suffix = match word.longest_suffix(&[
+        "anza", "anzas", "ico", "ica", "icos", "icas", "ismo", "ismos", "able", "ables", "ible",
+        "ibles", "ista", "istas", "oso", "osa", "osos", "osas", "amiento", "amientos", "imiento",
+        "imientos", "adora", "ador", "ación", "adoras", "adores", "aciones", "ante", "antes",
+        "ancia", "ancias", "logía", "logías", "ución", "uciones", "encia", "encias", "amente",
+        "mente", "idad", "idades", "iva", "ivo", "ivas", "ivos",
+    ]) {
+        None => return false,
+        Some(suffix) => suffix,
+    };
+
+    let start = word.suffix_start(suffix);
+    let before = word.len();
+
+    match suffix {
+        "adora" | "ador" | "ación" | "adoras" | "adores" | "aciones" | "ante" | "antes"
+        | "ancia" | "ancias" => {
+            if start >= r2 {
+                word.remove(suffix);
+
+                if word.ends_with_in("ic", r2) {
+                    word.remove("ic");
+                }
+            }
+        }
+        "logía" | "logías" => {
+            if start >= r2 {
+                word.replace(suffix, "log");
+            }
+        }
+        "ución" | "uciones" => {
+            if start >= r2 {
+                word.replace(suffix, "u");
+            }
+        }
+        "encia" | "encias" => {
+            if start >= r2 {
+                word.replace(suffix, "ente");
+            }
+        }
+        "amente" => {
+            if start >= r1 {
+                word.remove(suffix);
+
+                if word.ends_with_in("iv", r2) {
+                    word.remove("iv");
+
+                    if word.ends_with_in("at", r2) {
+                        word.remove("at");
+                    }
+                } else if let Some(ending) = word.longest_suffix_in(&["os", "ic", "ad"], r2) {
+                    word.remove(ending);
+                }
+            }
+        }
+        "mente" => {
+            if start >= r2 {
+                word.remove(suffix);
+
+                if let Some(ending) = word.longest_suffix_in(&["ante", "able", "ible"], r2) {
+                    word.remove(ending);
+                }
+            }
+        }
+        "idad" | "idades" => {
+            if start >= r2 {
+                word.remove(suffix);
+
+                if let Some(ending) = word.longest_suffix_in(&["abil", "ic", "iv"], r2) {
+                    word.remove(ending);
+                }
+            }
+        }
+        "iva" | "ivo" | "ivas" | "ivos" => {
+            if start >= r2 {
+                word.remove(suffix);
+
+                if word.ends_with_in("at", r2) {
+                    word.remove("at");
+                }
+            }
+        }
+        _ => {
+            if start >= r2 {
+                word.remove(suffix);
+            }
+        }
+    };
+
+    word.len() != before
+}
+
+fn step2a(word: &mut Word, rv: usize) -> bool {
+    let suffix = word.longest_suffix_in(
+        &[
+            "ya", "ye", "yan", "yen", "yeron", "yendo", "yo", "yó", "yas", "yes", "yais", "yamos",
+        ],
+        rv,
+    );
+
+    match suffix {
+        Some(suffix) if word.char_before(suffix) == Some('u') => {
+            word.remove(suffix);
+            true
+        }
+        _ => false,
+    }
+}
+
+fn step2b(word: &mut Word, rv: usize) {
+    let suffix = word.longest_suffix_in(
+        &[
+            "en", "es", "éis", "emos", "arían", "arías", "arán", "arás", "aríais", "aría", "aréis",
+            "aríamos", "aremos", "ará", "aré", "erían", "erías", "erán", "erás", "eríais", "ería",
+            "eréis", "eríamos", "eremos", "erá", "eré", "irían", "irías", "irán", "irás", "iríais",
+            "iría", "iréis", "iríamos", "iremos", "irá", "iré", "aba", "ada", "ida", "ía", "ara",
+            "iera", "ad", "ed", "id", "ase", "iese", "aste", "iste", "an", "aban", "ían", "aran",
+            "ieran", "asen", "iesen", "aron", "ieron", "ado", "ido", "ando", "iendo", "ió", "ar",
+            "er", "ir", "as", "abas", "adas", "idas", "ías", "aras", "ieras", "ases", "ieses",
+            "ís", "áis", "abais", "íais", "arais", "ierais", "aseis", "ieseis", "asteis", "isteis",
+            "ados", "idos", "amos", "ábamos", "íamos", "imos", "áramos", "iéramos", "iésemos",
+            "ásemos",
+        ],
+        rv,
+    );
+
+    match suffix {
+        Some(suffix @ ("en" | "es" | "éis" | "emos")) => {
+            word.remove(suffix);
+
+            if word.ends_with("gu") {
+                word.chars.pop();
+            }
+        }
+        Some(suffix) => word.remove(suffix),
+        None => (),
+    }
+}
+
+fn step3(word: &mut Word, rv: usize) {
+    match word.longest_suffix_in(&["os", "a", "o", "á", "í", "ó", "e", "é"], rv) {
+        Some(suffix @ ("e" | "é")) => {
+            word.remove(suffix);
+
+            if word.ends_with_in("u", rv) && word.ends_with("gu") {
+                word.chars.pop();
+            }
+        }
+        Some(suffix) => word.remove(suffix),
+        None => (),
+    }
+}
+
+pub fn stem(string: &str) -> String {
+    let mut word = Word::new(string);
+
+    let rv = romance_rv(&word.chars, is_vowel);
+    let (r1, r2) = standard_regions(&word.chars, is_vowel);
+
+    step0(&mut word, rv);
+
+    if !step1(&mut word, r1, r2) && !step2a(&mut word, rv) {
+        step2b(&mut word, rv);
+    }
+
+    step3(&mut word, rv);
+
+    word.map_chars(remove_acute_accents);
+
+    word.to_string()
+}
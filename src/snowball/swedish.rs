@@ -0,0 +1,71 @@
+// https://snowballstem.org/algorithms/swedish/stemmer.html
+use super::{Word, region_after};
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'ä' | 'å' | 'ö')
+}
+
+fn is_valid_s_ending(c: char) -> bool {
+    matches!(
+        c,
+        'b' | 'c'
+            | 'd'
+            | 'f'
+            | 'g'
+            | 'h'
+            | 'j'
+            | 'k'
+            | 'l'
+            | 'm'
+            | 'n'
+            | 'o'
+            | 'p'
+            | 'r'
+            | 't'
+            | 'v'
+            | 'y'
+    )
+}
+
+pub fn stem(string: &str) -> String {
+    let mut word = Word::new(string);
+
+    let r1 = region_after(&word.chars, 0, is_vowel).max(3);
+
+    // Step 1
+    match word.longest_suffix_in(
+        &[
+            "a", "arna", "erna", "heterna", "orna", "ad", "e", "ade", "ande", "arne", "are",
+            "aste", "en", "anden", "aren", "heten", "ern", "ar", "er", "heter", "or", "as",
+            "arnas", "ernas", "ornas", "es", "ades", "andes", "ens", "arens", "hetens", "erns",
+            "at", "andet", "het", "ast", "s",
+        ],
+        r1,
+    ) {
+        Some("s") => {
+            if word.char_before("s").is_some_and(is_valid_s_ending) {
+                word.remove("s");
+            }
+        }
+        Some(suffix) => word.remove(suffix),
+        None => (),
+    };
+
+    // Step 2
+    if word
+        .longest_suffix_in(&["dd", "gd", "nn", "dt", "gt", "kt", "tt"], r1)
+        .is_some()
+    {
+        word.chars.pop();
+    }
+
+    // Step 3
+    match word.longest_suffix_in(&["lig", "ig", "els", "löst", "fullt"], r1) {
+        Some("löst") => word.replace("löst", "lös"),
+        Some("fullt") => word.replace("fullt", "full"),
+        Some(suffix) => word.remove(suffix),
+        None => (),
+    };
+
+    word.to_string()
+}
@@ -0,0 +1,182 @@
+// Sparse matrices, stored row by row, that can be written in the Matrix
+// Market format or as scipy CSR matrices.
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use npyz::WriterBuilder;
+
+use crate::CliResult;
+
+// NOTE: rows are stored as lists of (column, value) pairs
+pub struct SparseMatrix {
+    rows: Vec<Vec<(usize, f64)>>,
+    cols: usize,
+}
+
+impl SparseMatrix {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows: vec![Vec::new(); rows],
+            cols,
+        }
+    }
+
+    pub fn push(&mut self, row: usize, col: usize, value: f64) {
+        self.rows[row].push((col, value));
+    }
+
+    pub fn rows(&self) -> &[Vec<(usize, f64)>] {
+        &self.rows
+    }
+
+    pub fn row_sums(&self) -> Vec<f64> {
+        self.rows
+            .iter()
+            .map(|row| row.iter().map(|(_, value)| value).sum())
+            .collect()
+    }
+
+    pub fn col_sums(&self) -> Vec<f64> {
+        let mut sums = vec![0.0; self.cols];
+
+        for row in self.rows.iter() {
+            for (j, value) in row.iter() {
+                sums[*j] += value;
+            }
+        }
+
+        sums
+    }
+
+    pub fn map_values<F>(&mut self, mut callback: F)
+    where
+        F: FnMut(usize, usize, f64) -> f64,
+    {
+        for (i, row) in self.rows.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut() {
+                *value = callback(i, *j, *value);
+            }
+        }
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.rows.iter().map(|row| row.len()).sum()
+    }
+
+    pub fn sort(&mut self) {
+        for row in self.rows.iter_mut() {
+            row.sort_by_key(|(col, _)| *col);
+        }
+    }
+
+    pub fn write_matrix_market(mut self, path: &Path) -> CliResult<()> {
+        self.sort();
+
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        writeln!(&mut writer, "%%MatrixMarket matrix coordinate real general")?;
+        writeln!(
+            &mut writer,
+            "{} {} {}",
+            self.rows.len(),
+            self.cols,
+            self.nnz()
+        )?;
+
+        for (i, row) in self.rows.iter().enumerate() {
+            for (j, value) in row.iter() {
+                writeln!(&mut writer, "{} {} {}", i + 1, j + 1, value)?;
+            }
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    // NOTE: this mimics what is written by scipy.sparse.save_npz
+    pub fn write_npz(mut self, path: &Path) -> CliResult<()> {
+        fn npy<T: npyz::Serialize + ?Sized>(
+            dtype: &str,
+            shape: &[u64],
+            values: &[&T],
+        ) -> CliResult<Vec<u8>> {
+            let mut bytes = Vec::new();
+
+            let mut writer = npyz::WriteOptions::<T>::new()
+                .dtype(npyz::DType::Plain(dtype.parse::<npyz::TypeStr>().unwrap()))
+                .shape(shape)
+                .writer(&mut bytes)
+                .begin_nd()?;
+
+            for value in values {
+                writer.push(value)?;
+            }
+
+            writer.finish()?;
+
+            Ok(bytes)
+        }
+
+        self.sort();
+
+        let nnz = self.nnz() as u64;
+
+        let mut data: Vec<f64> = Vec::with_capacity(nnz as usize);
+        let mut indices: Vec<i32> = Vec::with_capacity(nnz as usize);
+        let mut indptr: Vec<i32> = Vec::with_capacity(self.rows.len() + 1);
+
+        indptr.push(0);
+
+        for row in self.rows.iter() {
+            for (j, value) in row.iter() {
+                indices.push(*j as i32);
+                data.push(*value);
+            }
+
+            indptr.push(indices.len() as i32);
+        }
+
+        let arrays = [
+            (
+                "indices.npy",
+                npy::<i32>("<i4", &[nnz], &indices.iter().collect::<Vec<_>>())?,
+            ),
+            (
+                "indptr.npy",
+                npy::<i32>(
+                    "<i4",
+                    &[indptr.len() as u64],
+                    &indptr.iter().collect::<Vec<_>>(),
+                )?,
+            ),
+            ("format.npy", npy::<[u8]>("|S3", &[], &[b"csr"])?),
+            (
+                "shape.npy",
+                npy::<i64>(
+                    "<i8",
+                    &[2],
+                    &[&(self.rows.len() as i64), &(self.cols as i64)],
+                )?,
+            ),
+            (
+                "data.npy",
+                npy::<f64>("<f8", &[nnz], &data.iter().collect::<Vec<_>>())?,
+            ),
+        ];
+
+        let mut zip_writer = zip::ZipWriter::new(BufWriter::new(File::create(path)?));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+
+        for (name, bytes) in arrays {
+            zip_writer.start_file(name, options)?;
+            zip_writer.write_all(&bytes)?;
+        }
+
+        zip_writer.finish()?.flush()?;
+
+        Ok(())
+    }
+}
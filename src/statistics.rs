@@ -0,0 +1,510 @@
+// NOTE: this module gathers statistical distributions and helpers used to
+// compute test statistics & p-values. Special functions are adapted from
+// Numerical Recipes (3rd edition, chapter 6).
+use std::cmp::Ordering;
+
+const EPSILON: f64 = 1e-15;
+const TINY: f64 = 1e-300;
+const MAX_ITERATIONS: usize = 500;
+
+// Lanczos approximation
+pub fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.001_208_650_973_866_179,
+        -0.5395239384953e-5,
+    ];
+
+    let mut y = x;
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000000000190015;
+
+    for c in COEFFICIENTS {
+        y += 1.0;
+        series += c / y;
+    }
+
+    -tmp + (2.5066282746310005 * series / x).ln()
+}
+
+fn incomplete_beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    let qab = a + b;
+    let qap = a + 1.0;
+    let qam = a - 1.0;
+
+    let mut c = 1.0;
+    let mut d = 1.0 - qab * x / qap;
+
+    if d.abs() < TINY {
+        d = TINY;
+    }
+
+    d = 1.0 / d;
+    let mut h = d;
+
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+
+        let delta = d * c;
+        h *= delta;
+
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+
+    h
+}
+
+/// Regularized incomplete beta function I_x(a, b).
+pub fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * incomplete_beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * incomplete_beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Regularized upper incomplete gamma function Q(a, x).
+pub fn upper_incomplete_gamma(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+
+    let gln = ln_gamma(a);
+
+    if x < a + 1.0 {
+        // Series representation
+        let mut ap = a;
+        let mut del = 1.0 / a;
+        let mut sum = del;
+
+        for _ in 0..MAX_ITERATIONS {
+            ap += 1.0;
+            del *= x / ap;
+            sum += del;
+
+            if del.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+
+        1.0 - sum * (-x + a * x.ln() - gln).exp()
+    } else {
+        // Continued fraction representation
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut h = d;
+
+        for i in 1..=MAX_ITERATIONS {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+
+            let delta = d * c;
+            h *= delta;
+
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+
+        (-x + a * x.ln() - gln).exp() * h
+    }
+}
+
+pub fn erfc(x: f64) -> f64 {
+    if x >= 0.0 {
+        upper_incomplete_gamma(0.5, x * x)
+    } else {
+        2.0 - upper_incomplete_gamma(0.5, x * x)
+    }
+}
+
+pub fn normal_two_sided_pvalue(z: f64) -> f64 {
+    erfc(z.abs() / std::f64::consts::SQRT_2).min(1.0)
+}
+
+pub fn student_t_two_sided_pvalue(t: f64, df: f64) -> f64 {
+    if t.is_infinite() {
+        return 0.0;
+    }
+
+    incomplete_beta(df / 2.0, 0.5, df / (df + t * t)).min(1.0)
+}
+
+/// Survival function of the chi-square distribution.
+pub fn chi2_sf(x: f64, df: f64) -> f64 {
+    upper_incomplete_gamma(df / 2.0, x / 2.0)
+}
+
+/// Survival function of the Kolmogorov distribution.
+pub fn kolmogorov_sf(lambda: f64) -> f64 {
+    if lambda < 0.2 {
+        return 1.0;
+    }
+
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    let mut previous = 0.0;
+
+    for k in 1..=100 {
+        let k = k as f64;
+        let term = sign * 2.0 * (-2.0 * k * k * lambda * lambda).exp();
+
+        sum += term;
+
+        if term.abs() <= 1e-3 * previous || term.abs() <= 1e-8 * sum {
+            return sum.clamp(0.0, 1.0);
+        }
+
+        sign = -sign;
+        previous = term.abs();
+    }
+
+    1.0
+}
+
+fn float_cmp(a: &f64, b: &f64) -> Ordering {
+    a.total_cmp(b)
+}
+
+/// Returns 1-based ranks of given values, averaging ties, along with the
+/// tie correction term, i.e. the sum of t^3 - t for each group of t ties.
+pub fn average_ranks(values: &[f64]) -> (Vec<f64>, f64) {
+    let mut indices = (0..values.len()).collect::<Vec<_>>();
+    indices.sort_by(|i, j| float_cmp(&values[*i], &values[*j]));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut ties_correction = 0.0;
+    let mut i = 0;
+
+    while i < indices.len() {
+        let mut j = i + 1;
+
+        while j < indices.len() && values[indices[j]] == values[indices[i]] {
+            j += 1;
+        }
+
+        // NOTE: ranks are 1-based
+        let rank = (i + j + 1) as f64 / 2.0;
+
+        for index in indices[i..j].iter() {
+            ranks[*index] = rank;
+        }
+
+        let t = (j - i) as f64;
+        ties_correction += t * t * t - t;
+
+        i = j;
+    }
+
+    (ranks, ties_correction)
+}
+
+pub fn pearson_correlation(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let n = xs.len();
+
+    if n == 0 {
+        return None;
+    }
+
+    let mean_x = xs.iter().sum::<f64>() / n as f64;
+    let mean_y = ys.iter().sum::<f64>() / n as f64;
+
+    let mut covariance = 0.0;
+    let mut variance_x = 0.0;
+    let mut variance_y = 0.0;
+
+    for (x, y) in xs.iter().zip(ys.iter()) {
+        let dx = x - mean_x;
+        let dy = y - mean_y;
+
+        covariance += dx * dy;
+        variance_x += dx * dx;
+        variance_y += dy * dy;
+    }
+
+    let r = covariance / (variance_x * variance_y).sqrt();
+
+    if r.is_finite() {
+        Some(r.clamp(-1.0, 1.0))
+    } else {
+        None
+    }
+}
+
+fn has_nan(xs: &[f64], ys: &[f64]) -> bool {
+    xs.iter().chain(ys.iter()).any(|v| v.is_nan())
+}
+
+pub fn spearman_correlation(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if has_nan(xs, ys) {
+        return None;
+    }
+
+    pearson_correlation(&average_ranks(xs).0, &average_ranks(ys).0)
+}
+
+fn count_tied_pairs<T: PartialEq>(sorted: impl Iterator<Item = T>) -> u64 {
+    let mut total = 0;
+    let mut current: Option<(T, u64)> = None;
+
+    for item in sorted {
+        match current.as_mut() {
+            Some((value, count)) if *value == item => {
+                *count += 1;
+            }
+            _ => {
+                if let Some((_, count)) = current {
+                    total += count * (count - 1) / 2;
+                }
+
+                current = Some((item, 1));
+            }
+        }
+    }
+
+    if let Some((_, count)) = current {
+        total += count * (count - 1) / 2;
+    }
+
+    total
+}
+
+// NOTE: counting swaps performed by a stable merge sort
+fn merge_sort_swaps(values: &mut [f64], buffer: &mut Vec<f64>) -> u64 {
+    let n = values.len();
+
+    if n < 2 {
+        return 0;
+    }
+
+    let middle = n / 2;
+    let mut swaps = merge_sort_swaps(&mut values[..middle], buffer);
+    swaps += merge_sort_swaps(&mut values[middle..], buffer);
+
+    buffer.clear();
+
+    let (mut i, mut j) = (0, middle);
+
+    while i < middle && j < n {
+        if values[j] < values[i] {
+            buffer.push(values[j]);
+            swaps += (middle - i) as u64;
+            j += 1;
+        } else {
+            buffer.push(values[i]);
+            i += 1;
+        }
+    }
+
+    buffer.extend_from_slice(&values[i..middle]);
+    buffer.extend_from_slice(&values[j..n]);
+    values.copy_from_slice(buffer);
+
+    swaps
+}
+
+/// Kendall's tau-b rank correlation, computed in O(n log n) time using
+/// Knight's algorithm.
+pub fn kendall_correlation(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let n = xs.len() as u64;
+
+    if n < 2 || has_nan(xs, ys) {
+        return None;
+    }
+
+    let mut pairs = xs
+        .iter()
+        .copied()
+        .zip(ys.iter().copied())
+        .collect::<Vec<_>>();
+
+    pairs.sort_by(|a, b| float_cmp(&a.0, &b.0).then_with(|| float_cmp(&a.1, &b.1)));
+
+    let total = n * (n - 1) / 2;
+    let x_ties = count_tied_pairs(pairs.iter().map(|p| p.0));
+    let joint_ties = count_tied_pairs(pairs.iter().copied());
+
+    let mut sorted_ys = pairs.into_iter().map(|p| p.1).collect::<Vec<_>>();
+    let swaps = merge_sort_swaps(&mut sorted_ys, &mut Vec::with_capacity(n as usize));
+    let y_ties = count_tied_pairs(sorted_ys.into_iter());
+
+    let numerator =
+        total as f64 - x_ties as f64 - y_ties as f64 + joint_ties as f64 - 2.0 * swaps as f64;
+    let denominator = (((total - x_ties) as f64) * ((total - y_ties) as f64)).sqrt();
+
+    let tau = numerator / denominator;
+
+    if tau.is_finite() {
+        Some(tau.clamp(-1.0, 1.0))
+    } else {
+        None
+    }
+}
+
+/// Invert a square matrix, given as a flat row-major slice, using Gauss-Jordan
+/// elimination with partial pivoting. Returns `None` if the matrix is singular.
+pub fn invert_matrix(matrix: &[f64], n: usize) -> Option<Vec<f64>> {
+    let mut a = matrix.to_vec();
+    let mut inverse = vec![0.0; n * n];
+
+    // NOTE: singularity is tested relative to the scale of the matrix
+    let tolerance = matrix.iter().fold(0.0, |max: f64, v| max.max(v.abs())) * 1e-12;
+
+    for i in 0..n {
+        inverse[i * n + i] = 1.0;
+    }
+
+    for col in 0..n {
+        let pivot_row = (col..n)
+            .max_by(|i, j| float_cmp(&a[i * n + col].abs(), &a[j * n + col].abs()))
+            .unwrap();
+
+        let pivot = a[pivot_row * n + col];
+
+        if pivot.abs() <= tolerance {
+            return None;
+        }
+
+        if pivot_row != col {
+            for k in 0..n {
+                a.swap(pivot_row * n + k, col * n + k);
+                inverse.swap(pivot_row * n + k, col * n + k);
+            }
+        }
+
+        for k in 0..n {
+            a[col * n + k] /= pivot;
+            inverse[col * n + k] /= pivot;
+        }
+
+        for row in 0..n {
+            if row == col {
+                continue;
+            }
+
+            let factor = a[row * n + col];
+
+            if factor == 0.0 {
+                continue;
+            }
+
+            for k in 0..n {
+                a[row * n + k] -= factor * a[col * n + k];
+                inverse[row * n + k] -= factor * inverse[col * n + k];
+            }
+        }
+    }
+
+    Some(inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_distributions() {
+        assert_close(normal_two_sided_pvalue(1.96), 0.04999579029644087);
+        assert_close(student_t_two_sided_pvalue(2.0, 10.0), 0.07338803477074705);
+        assert_close(chi2_sf(3.84, 1.0), 0.05004352124870519);
+        assert_close(chi2_sf(10.0, 4.0), 0.04042768199451279);
+        assert_close(kolmogorov_sf(1.0), 0.26999967167735456);
+    }
+
+    #[test]
+    fn test_average_ranks() {
+        let (ranks, ties) = average_ranks(&[3.0, 1.0, 4.0, 1.0, 5.0]);
+
+        assert_eq!(ranks, vec![3.0, 1.5, 4.0, 1.5, 5.0]);
+        assert_eq!(ties, 6.0);
+    }
+
+    #[test]
+    fn test_correlations() {
+        let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let ys = [2.0, 1.0, 4.0, 3.0, 5.0];
+
+        assert_close(pearson_correlation(&xs, &ys).unwrap(), 0.8);
+        assert_close(spearman_correlation(&xs, &ys).unwrap(), 0.8);
+        assert_close(kendall_correlation(&xs, &ys).unwrap(), 0.6);
+
+        let ys = [1.0, 1.0, 2.0, 2.0, 3.0];
+
+        assert_close(kendall_correlation(&xs, &ys).unwrap(), 0.8944271909999159);
+        assert_eq!(kendall_correlation(&xs, &[1.0; 5]), None);
+
+        let ys = [2.0, f64::NAN, 4.0, 3.0, 5.0];
+
+        assert_eq!(spearman_correlation(&xs, &ys), None);
+        assert_eq!(kendall_correlation(&xs, &ys), None);
+    }
+
+    #[test]
+    fn test_invert_matrix() {
+        let inverse = invert_matrix(&[4.0, 7.0, 2.0, 6.0], 2).unwrap();
+
+        for (a, b) in inverse.into_iter().zip([0.6, -0.7, -0.2, 0.4]) {
+            assert_close(a, b);
+        }
+
+        assert_eq!(invert_matrix(&[1.0, 2.0, 2.0, 4.0], 2), None);
+    }
+}
@@ -0,0 +1,160 @@
+// Builtin stopword lists, mostly derived from the ones distributed alongside
+// the Snowball stemmers: https://snowballstem.org/
+use std::io::Read;
+
+use lazy_static::lazy_static;
+
+use crate::CliResult;
+use crate::collections::{HashMap, HashSet};
+use crate::config::Config;
+
+pub static LANGUAGES: [(&str, &str, &str); 8] = [
+    ("nl", "dutch", include_str!("nl.txt")),
+    ("en", "english", include_str!("en.txt")),
+    ("fr", "french", include_str!("fr.txt")),
+    ("de", "german", include_str!("de.txt")),
+    ("it", "italian", include_str!("it.txt")),
+    ("pt", "portuguese", include_str!("pt.txt")),
+    ("es", "spanish", include_str!("es.txt")),
+    ("sv", "swedish", include_str!("sv.txt")),
+];
+
+lazy_static! {
+    static ref BUILTIN_STOPWORDS: HashMap<&'static str, HashSet<&'static str>> = LANGUAGES
+        .iter()
+        .map(|(code, _, list)| (*code, list.lines().collect()))
+        .collect();
+}
+
+fn resolve_language(lang: &str) -> Result<&'static str, String> {
+    let lang = lang.trim().to_lowercase();
+
+    LANGUAGES
+        .iter()
+        .find(|(code, name, _)| *code == lang || *name == lang)
+        .map(|(code, _, _)| *code)
+        .ok_or_else(|| format!("unknown builtin stoplist language \"{}\"", lang))
+}
+
+pub fn builtin_stopwords(lang: &str) -> Result<&'static HashSet<&'static str>, String> {
+    resolve_language(lang).map(|code| &BUILTIN_STOPWORDS[code])
+}
+
+// NOTE: `langs` can contain multiple languages separated by commas
+pub fn is_stopword(token: &str, langs: &str) -> Result<bool, String> {
+    let lowercase = token.to_lowercase();
+
+    for lang in langs.split(',') {
+        if builtin_stopwords(lang)?.contains(lowercase.as_str()) {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+// NOTE: since file paths may contain "+", we only split on the ones that are
+// adjacent to a builtin source, which cannot contain any.
+fn split_spec(spec: &str) -> Vec<&str> {
+    let mut sources = Vec::new();
+    let mut start = 0;
+
+    for (i, _) in spec.match_indices('+') {
+        if spec[start..i].starts_with("builtin:") || spec[i + 1..].starts_with("builtin:") {
+            sources.push(&spec[start..i]);
+            start = i + 1;
+        }
+    }
+
+    sources.push(&spec[start..]);
+
+    sources
+}
+
+#[derive(Default, Debug)]
+pub struct Stoplist {
+    words: HashSet<String>,
+}
+
+impl Stoplist {
+    // NOTE: a stoplist is given as one or more sources separated by "+", each
+    // source being either a path to a .txt file containing one word per line,
+    // or "builtin:" followed by comma-separated language codes, e.g.
+    // "builtin:fr,en+custom.txt".
+    pub fn from_spec(spec: &str) -> CliResult<Self> {
+        let mut stoplist = Self::default();
+
+        for source in split_spec(spec) {
+            if let Some(langs) = source.strip_prefix("builtin:") {
+                for lang in langs.split(',') {
+                    for word in builtin_stopwords(lang)? {
+                        stoplist.words.insert(word.to_string());
+                    }
+                }
+            } else {
+                let mut contents = String::new();
+
+                Config::new(&Some(source.to_string()))
+                    .io_reader()?
+                    .read_to_string(&mut contents)?;
+
+                for word in contents.lines() {
+                    stoplist.words.insert(word.to_lowercase());
+                }
+            }
+        }
+
+        Ok(stoplist)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(|word| word.as_str())
+    }
+
+    pub fn contains(&self, token: &[u8]) -> bool {
+        match std::str::from_utf8(token) {
+            Ok(token) => self.words.contains(&token.to_lowercase()),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_stopword() {
+        assert_eq!(is_stopword("The", "en"), Ok(true));
+        assert_eq!(is_stopword("cat", "en"), Ok(false));
+        assert_eq!(is_stopword("les", "en"), Ok(false));
+        assert_eq!(is_stopword("les", "en,fr"), Ok(true));
+        assert_eq!(is_stopword("les", "french"), Ok(true));
+        assert!(is_stopword("les", "klingon").is_err());
+    }
+
+    #[test]
+    fn test_split_spec() {
+        assert_eq!(split_spec("builtin:fr,en"), vec!["builtin:fr,en"]);
+        assert_eq!(
+            split_spec("builtin:fr+custom.txt"),
+            vec!["builtin:fr", "custom.txt"]
+        );
+        assert_eq!(
+            split_spec("c++/words.txt+builtin:en+builtin:fr"),
+            vec!["c++/words.txt", "builtin:en", "builtin:fr"]
+        );
+        assert_eq!(split_spec("a+b.txt"), vec!["a+b.txt"]);
+    }
+
+    #[test]
+    fn test_stoplist() {
+        let stoplist = Stoplist::from_spec("builtin:fr,en").unwrap();
+
+        assert!(stoplist.contains(b"the"));
+        assert!(stoplist.contains(b"Les"));
+        assert!(!stoplist.contains(b"chat"));
+
+        assert!(Stoplist::from_spec("builtin:fr,xx").is_err());
+    }
+}
@@ -533,6 +533,57 @@ fn agg_correlation() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn agg_rank_correlation() {
+    let wrk = Workdir::new("agg_rank_correlation");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["x", "y"],
+            svec!["1", "0"],
+            svec!["4", "6"],
+            svec!["5", "7"],
+            svec!["7", "9"],
+            svec!["", ""],
+            svec!["9", "3"],
+            svec!["10", "3"],
+        ],
+    );
+
+    let mut cmd = wrk.command("agg");
+    cmd.arg("spearman_correlation(x, y) as s, kendall_correlation(x, y) as k")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["s", "k"],
+        svec!["0.11595420713048969", "0.13801311186847084"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn agg_rank_correlation_nan() {
+    let wrk = Workdir::new("agg_rank_correlation_nan");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["x", "y"],
+            svec!["1", "0"],
+            svec!["NaN", "6"],
+            svec!["5", "7"],
+        ],
+    );
+
+    let mut cmd = wrk.command("agg");
+    cmd.arg("spearman_correlation(x, y) as s, kendall_correlation(x, y) as k")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["s", "k"], svec!["", ""]];
+    assert_eq!(got, expected);
+}
+
 #[test]
 fn agg_along_rows() {
     let wrk = Workdir::new("agg_along_rows");
@@ -628,3 +679,54 @@ fn agg_along_matrix() {
     let expected = vec![svec!["total"], svec!["15"]];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn agg_sketches() {
+    let wrk = Workdir::new("agg_sketches");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["day", "name", "n"],
+            svec!["1", "john", "3"],
+            svec!["1", "mary", "4"],
+            svec!["1", "john", "5"],
+            svec!["2", "lucy", "1"],
+            svec!["2", "john", "10"],
+            svec!["2", "john", "2"],
+        ],
+    );
+
+    let mut cmd = wrk.command("groupby");
+    cmd.arg("day")
+        .arg("hll_sketch(name) as hll, tdigest_sketch(n) as td, count_min_sketch(name) as cms, heavy_hitters_sketch(1, name) as hh")
+        .arg("data.csv");
+
+    let sketches: String = wrk.stdout(&mut cmd);
+    wrk.write("sketches.csv", &sketches);
+
+    let mut cmd = wrk.command("agg");
+    cmd.arg("merge_sketches(hll) as hll, merge_sketches(td) as td, merge_sketches(cms) as cms, merge_sketches(hh) as hh")
+        .arg("sketches.csv");
+
+    let merged: String = wrk.stdout(&mut cmd);
+    wrk.write("merged.csv", &merged);
+
+    let mut cmd = wrk.command("select");
+    cmd.arg("-e")
+        .arg("sketch_cardinality(hll) as card, sketch_quantile(td, 0.5) as median, sketch_count(cms, 'john') as john, sketch_top(hh) as top")
+        .arg("merged.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["card", "median", "john", "top"],
+        svec!["3", "3.5", "4", "[\"john\"]"],
+    ];
+    assert_eq!(got, expected);
+
+    // Merging heterogeneous sketches
+    let mut cmd = wrk.command("agg");
+    cmd.arg("merge_sketches(if(day == 1, hll, td))")
+        .arg("sketches.csv");
+
+    wrk.assert_err(&mut cmd);
+}
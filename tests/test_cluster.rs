@@ -0,0 +1,334 @@
+use crate::workdir::Workdir;
+
+fn data() -> Vec<Vec<String>> {
+    vec![
+        svec!["name"],
+        svec!["John Smith"],
+        svec!["john smith"],
+        svec!["Smith, John"],
+        svec!["Jon Smith"],
+        svec!["John Smiht"],
+        svec!["Mary Poppins"],
+        svec!["Mary Popins"],
+        svec!["John Smith"],
+    ]
+}
+
+fn clustered_values(toml: &str) -> Vec<Vec<String>> {
+    let mut clusters: Vec<Vec<String>> = Vec::new();
+
+    for line in toml.lines() {
+        if line == "[[cluster]]" {
+            clusters.push(vec![]);
+        } else if let Some(rest) = line.trim().strip_prefix("{ value = \"") {
+            let value = rest.split('"').next().unwrap();
+            clusters.last_mut().unwrap().push(value.to_string());
+        }
+    }
+
+    clusters
+}
+
+#[test]
+fn cluster_fingerprint() {
+    let wrk = Workdir::new("cluster_fingerprint");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args(["-m", "fingerprint", "name", "data.csv"]);
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(
+        clustered_values(&got),
+        vec![svec!["John Smith", "john smith", "Smith, John"]]
+    );
+    assert!(got.contains("key = \"john smith\""));
+}
+
+#[test]
+fn cluster_ngram_fingerprint() {
+    let wrk = Workdir::new("cluster_ngram_fingerprint");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args(["-m", "ngram-fingerprint", "name", "data.csv"]);
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(
+        clustered_values(&got),
+        vec![svec!["John Smith", "john smith"]]
+    );
+}
+
+#[test]
+fn cluster_levenshtein() {
+    let wrk = Workdir::new("cluster_levenshtein");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args(["-m", "levenshtein", "name", "data.csv"]);
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(
+        clustered_values(&got),
+        vec![
+            svec!["John Smith", "Jon Smith"],
+            svec!["Mary Poppins", "Mary Popins"]
+        ]
+    );
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args([
+        "-m",
+        "levenshtein",
+        "-r",
+        "2",
+        "-b",
+        "0",
+        "name",
+        "data.csv",
+    ]);
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(
+        clustered_values(&got),
+        vec![
+            svec!["John Smith", "john smith", "Jon Smith", "John Smiht"],
+            svec!["Mary Poppins", "Mary Popins"]
+        ]
+    );
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args(["-m", "levenshtein", "-r", "1.5", "name", "data.csv"]);
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn cluster_jaro_winkler() {
+    let wrk = Workdir::new("cluster_jaro_winkler");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args(["-m", "jaro-winkler", "name", "data.csv"]);
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(
+        clustered_values(&got),
+        vec![
+            svec!["John Smith", "Jon Smith", "John Smiht"],
+            svec!["Mary Poppins", "Mary Popins"]
+        ]
+    );
+}
+
+#[test]
+fn cluster_short_values() {
+    let wrk = Workdir::new("cluster_short_values");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["city"],
+            svec!["Paris"],
+            svec!["Pariss"],
+            svec!["London"],
+            svec!["Londn"],
+            svec!["Berlin"],
+            svec!["Berlim"],
+            svec!["Rome"],
+        ],
+    );
+
+    for method in ["levenshtein", "jaro-winkler"] {
+        let mut cmd = wrk.command("cluster");
+        cmd.args(["-m", method, "city", "data.csv"]);
+
+        let got: String = wrk.stdout(&mut cmd);
+        assert_eq!(
+            clustered_values(&got),
+            vec![
+                svec!["Berlin", "Berlim"],
+                svec!["London", "Londn"],
+                svec!["Pariss", "Paris"]
+            ]
+        );
+    }
+}
+
+#[test]
+fn cluster_radius_with_key_method() {
+    let wrk = Workdir::new("cluster_radius_with_key_method");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args(["-r", "2", "name", "data.csv"]);
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn cluster_levenshtein_radius_too_large() {
+    let wrk = Workdir::new("cluster_levenshtein_radius_too_large");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args(["-m", "levenshtein", "-r", "4", "name", "data.csv"]);
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn cluster_apply() {
+    let wrk = Workdir::new("cluster_apply");
+    wrk.create("data.csv", data());
+    wrk.write(
+        "clusters.toml",
+        r#"[[cluster]]
+id = 0
+key = "john smith"
+replace_with = "John Smith"
+values = [
+  { value = "John Smith", count = 2 },
+  { value = "john smith", count = 1 },
+  { value = "Smith, John", count = 1 },
+]
+harmonize = true
+
+[[cluster]]
+id = 1
+key = "mary poppins"
+replace_with = "Mary Poppins"
+values = [
+  { value = "Mary Poppins", count = 1 },
+  { value = "Mary Popins", count = 1 },
+]
+harmonize = false
+"#,
+    );
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args(["name", "--apply", "clusters.toml", "data.csv"]);
+
+    let output = wrk.output(&mut cmd);
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "2 cells changed\n");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name"],
+        svec!["John Smith"],
+        svec!["John Smith"],
+        svec!["John Smith"],
+        svec!["Jon Smith"],
+        svec!["John Smiht"],
+        svec!["Mary Poppins"],
+        svec!["Mary Popins"],
+        svec!["John Smith"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args([
+        "name",
+        "--apply",
+        "clusters.toml",
+        "--into",
+        "harmonized",
+        "data.csv",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "harmonized"],
+        svec!["John Smith", "John Smith"],
+        svec!["john smith", "John Smith"],
+        svec!["Smith, John", "John Smith"],
+        svec!["Jon Smith", "Jon Smith"],
+        svec!["John Smiht", "John Smiht"],
+        svec!["Mary Poppins", "Mary Poppins"],
+        svec!["Mary Popins", "Mary Popins"],
+        svec!["John Smith", "John Smith"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args(["name", "--into", "harmonized", "data.csv"]);
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn cluster_apply_round_trip() {
+    let wrk = Workdir::new("cluster_apply_round_trip");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["name"],
+            svec!["a\u{7f}b"],
+            svec!["A\u{7f}b"],
+            svec!["a\u{7f}b"],
+            svec!["q\"x\\"],
+            svec!["Q\"x\\"],
+        ],
+    );
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args(["-m", "fingerprint", "name", "data.csv"]);
+
+    let clusters = wrk
+        .stdout::<String>(&mut cmd)
+        .replace("harmonize = false", "harmonize = true");
+    wrk.write("clusters.toml", &clusters);
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args(["name", "--apply", "clusters.toml", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name"],
+        svec!["a\u{7f}b"],
+        svec!["a\u{7f}b"],
+        svec!["a\u{7f}b"],
+        svec!["q\"x\\"],
+        svec!["q\"x\\"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn cluster_apply_csv() {
+    let wrk = Workdir::new("cluster_apply_csv");
+    wrk.create("data.csv", data());
+    wrk.create(
+        "clusters.csv",
+        vec![
+            svec!["harmonize", "replace_with", "values"],
+            svec![
+                "false",
+                "John Smith",
+                r#"[{"value":"John Smith"},{"value":"john smith"}]"#
+            ],
+            svec![
+                "true",
+                "Mary Poppins",
+                r#"[{"value":"Mary Poppins"},{"value":"Mary Popins"}]"#
+            ],
+        ],
+    );
+
+    let mut cmd = wrk.command("cluster");
+    cmd.args(["name", "-A", "clusters.csv", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name"],
+        svec!["John Smith"],
+        svec!["john smith"],
+        svec!["Smith, John"],
+        svec!["Jon Smith"],
+        svec!["John Smiht"],
+        svec!["Mary Poppins"],
+        svec!["Mary Poppins"],
+        svec!["John Smith"],
+    ];
+    assert_eq!(got, expected);
+}
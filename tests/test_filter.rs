@@ -15,6 +15,25 @@ fn filter() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn filter_is_stopword() {
+    let wrk = Workdir::new("filter_is_stopword");
+    wrk.create(
+        "data.csv",
+        vec![svec!["word"], svec!["The"], svec!["cat"], svec!["les"]],
+    );
+    let mut cmd = wrk.command("filter");
+    cmd.arg("!is_stopword(word, 'en,fr')").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["word"], svec!["cat"]];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("filter");
+    cmd.arg("is_stopword(word, 'klingon')").arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
+
 #[test]
 fn filter_invert_match() {
     let wrk = Workdir::new("filter_invert_match");
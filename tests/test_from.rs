@@ -89,3 +89,170 @@ fn from_json_root() {
     ];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn from_gexf() {
+    let wrk = Workdir::new("from_gexf");
+    wrk.write(
+        "graph.gexf",
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<gexf xmlns="http://gexf.net/1.3" xmlns:viz="http://gexf.net/1.3/viz" version="1.3">
+  <graph defaultedgetype="directed">
+    <attributes class="node">
+      <attribute id="0" title="community" type="integer"/>
+    </attributes>
+    <attributes class="edge">
+      <attribute id="0" title="kind" type="string">
+        <default>mention</default>
+      </attribute>
+    </attributes>
+    <nodes>
+      <node id="a" label="Alpha">
+        <attvalues>
+          <attvalue for="0" value="1"/>
+        </attvalues>
+        <viz:position x="10.5" y="-3.0" z="0.0"/>
+        <viz:size value="4"/>
+        <viz:color r="255" g="0" b="12"/>
+      </node>
+      <node id="b" label="Beta"/>
+    </nodes>
+    <edges>
+      <edge id="0" source="a" target="b" weight="2.0">
+        <attvalues>
+          <attvalue for="0" value="retweet"/>
+        </attvalues>
+      </edge>
+      <edge id="1" source="b" target="a"/>
+    </edges>
+  </graph>
+</gexf>"#,
+    );
+
+    let mut cmd = wrk.command("from");
+    cmd.arg("--nodes").arg("graph.gexf");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["node", "label", "x", "y", "z", "size", "color", "community"],
+        svec![
+            "a",
+            "Alpha",
+            "10.5",
+            "-3.0",
+            "0.0",
+            "4",
+            "rgb(255,0,12)",
+            "1"
+        ],
+        svec!["b", "Beta", "", "", "", "", "", ""],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("from");
+    cmd.arg("--edges").arg("graph.gexf");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["source", "target", "weight", "kind"],
+        svec!["a", "b", "2.0", "retweet"],
+        svec!["b", "a", "", "mention"],
+    ];
+    assert_eq!(got, expected);
+
+    // Either --nodes or --edges is required
+    let mut cmd = wrk.command("from");
+    cmd.arg("graph.gexf");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn from_graphml() {
+    let wrk = Workdir::new("from_graphml");
+    wrk.write(
+        "graph.graphml",
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="d0" for="node" attr.name="color" attr.type="string">
+    <default>yellow</default>
+  </key>
+  <key id="d1" for="edge" attr.name="weight" attr.type="double"/>
+  <graph id="G" edgedefault="undirected">
+    <node id="n0">
+      <data key="d0">green &amp; blue</data>
+    </node>
+    <node id="n1"/>
+    <edge source="n0" target="n1">
+      <data key="d1">1.5</data>
+    </edge>
+  </graph>
+</graphml>"#,
+    );
+
+    let mut cmd = wrk.command("from");
+    cmd.arg("--nodes").arg("graph.graphml");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["node", "color"],
+        svec!["n0", "green & blue"],
+        svec!["n1", "yellow"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("from");
+    cmd.arg("--edges").arg("graph.graphml");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["source", "target", "weight"],
+        svec!["n0", "n1", "1.5"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn from_graphology() {
+    let wrk = Workdir::new("from_graphology");
+    wrk.write(
+        "graph.json",
+        r#"{
+  "options": {"type": "directed"},
+  "nodes": [
+    {"key": "a", "attributes": {"label": "Alpha", "size": 3}},
+    {"key": "b", "attributes": {"community": 2}}
+  ],
+  "edges": [
+    {"source": "a", "target": "b", "attributes": {"weight": 1}},
+    {"source": "b", "target": "a"}
+  ]
+}"#,
+    );
+
+    let mut cmd = wrk.command("from");
+    cmd.args(["-f", "graphology"])
+        .arg("--nodes")
+        .arg("graph.json");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["node", "label", "size", "community"],
+        svec!["a", "Alpha", "3", ""],
+        svec!["b", "", "", "2"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("from");
+    cmd.args(["-f", "graphology"])
+        .arg("--edges")
+        .arg("graph.json");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["source", "target", "weight"],
+        svec!["a", "b", "1"],
+        svec!["b", "a", ""],
+    ];
+    assert_eq!(got, expected);
+}
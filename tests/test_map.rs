@@ -19,6 +19,31 @@ fn map() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn map_detect_lang() {
+    let wrk = Workdir::new("map_detect_lang");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["text"],
+            svec!["Der Hund schläft unter dem Tisch"],
+            svec!["El perro duerme debajo de la mesa"],
+            svec!["..."],
+        ],
+    );
+    let mut cmd = wrk.command("map");
+    cmd.arg("detect_lang(text) as lang").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["text", "lang"],
+        svec!["Der Hund schläft unter dem Tisch", "de"],
+        svec!["El perro duerme debajo de la mesa", "es"],
+        svec!["...", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
 #[test]
 fn map_multi() {
     let wrk = Workdir::new("map_multi");
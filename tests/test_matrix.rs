@@ -165,3 +165,283 @@ fn matrix_adj_undirected() {
     ];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn matrix_corr() {
+    let wrk = Workdir::new("matrix_corr");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["a", "b", "c"],
+            svec!["1", "2", "3"],
+            svec!["2", "1", ""],
+            svec!["3", "4", "1"],
+            svec!["4", "3", "2"],
+            svec!["5", "5", "5"],
+        ],
+    );
+
+    let mut cmd = wrk.command("matrix");
+    cmd.arg("corr").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["", "a", "b", "c"],
+        svec!["a", "", "0.7999999999999998", "0.37142857142857144"],
+        svec!["b", "0.7999999999999998", "", "0.3779644730092272"],
+        svec!["c", "0.37142857142857144", "0.3779644730092272", ""],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("matrix");
+    cmd.arg("corr")
+        .args(["-m", "spearman"])
+        .arg("-D")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["", "a", "b", "c"],
+        svec!["a", "1.0", "0.8", "0.4"],
+        svec!["b", "0.8", "1.0", "0.2"],
+        svec!["c", "0.4", "0.2", "1.0"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn matrix_corr_long() {
+    let wrk = Workdir::new("matrix_corr_long");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["a", "b", "c"],
+            svec!["1", "2", "3"],
+            svec!["2", "1", ""],
+            svec!["3", "4", "1"],
+            svec!["4", "3", "2"],
+            svec!["5", "5", "5"],
+        ],
+    );
+
+    let mut cmd = wrk.command("matrix");
+    cmd.arg("corr")
+        .args(["-m", "kendall"])
+        .arg("-L")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["x", "y", "coefficient", "n"],
+        svec!["a", "b", "0.6", "5"],
+        svec!["a", "c", "0.3333333333333333", "4"],
+        svec!["b", "c", "0", "4"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn matrix_corr_nan() {
+    let wrk = Workdir::new("matrix_corr_nan");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["a", "b"],
+            svec!["1", "2"],
+            svec!["NaN", "1"],
+            svec!["3", "4"],
+        ],
+    );
+
+    for method in ["spearman", "kendall"] {
+        let mut cmd = wrk.command("matrix");
+        cmd.arg("corr")
+            .args(["-m", method])
+            .arg("-L")
+            .arg("data.csv");
+
+        let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+        let expected = vec![
+            svec!["x", "y", "coefficient", "n"],
+            svec!["a", "b", "", "3"],
+        ];
+        assert_eq!(got, expected);
+    }
+}
+
+#[test]
+fn matrix_adj_sparse() {
+    let wrk = Workdir::new("matrix_adj_sparse");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["a", "b", "weight"],
+            svec!["one", "deux", "1"],
+            svec!["one", "trois", "5"],
+            svec!["two", "un", "2"],
+            svec!["one", "deux", "7"],
+            svec!["two", "one", "5"],
+        ],
+    );
+
+    let mut cmd = wrk.command("matrix");
+    cmd.arg("adj")
+        .arg("a")
+        .arg("b")
+        .args(["-w", "weight"])
+        .arg("--sparse")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["x", "y", "value"],
+        svec!["two", "one", "5"],
+        svec!["one", "deux", "8"],
+        svec!["one", "trois", "5"],
+        svec!["two", "un", "2"],
+    ];
+    assert_eq!(got, expected);
+
+    // Row-stochastic normalization
+    let mut cmd = wrk.command("matrix");
+    cmd.arg("adj")
+        .arg("a")
+        .arg("b")
+        .args(["-w", "weight"])
+        .arg("-U")
+        .arg("--sparse")
+        .args(["-N", "row"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["x", "y", "value"],
+        svec!["one", "deux", "1"],
+        svec!["deux", "one", "0.4444444444444444"],
+        svec!["trois", "one", "0.2777777777777778"],
+        svec!["two", "one", "0.2777777777777778"],
+        svec!["one", "trois", "1"],
+        svec!["one", "two", "0.7142857142857143"],
+        svec!["un", "two", "0.2857142857142857"],
+        svec!["two", "un", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn matrix_count_normalize() {
+    let wrk = Workdir::new("matrix_count_normalize");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["x", "y"],
+            svec!["a", "c"],
+            svec!["a", "c"],
+            svec!["a", "d"],
+            svec!["b", "d"],
+        ],
+    );
+
+    let mut cmd = wrk.command("matrix");
+    cmd.arg("count")
+        .arg("x")
+        .arg("y")
+        .args(["-N", "pmi"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["", "a", "b"],
+        svec!["c", "0.41503749927884376", ""],
+        svec!["d", "-0.5849625007211563", "1"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("matrix");
+    cmd.arg("count")
+        .arg("x")
+        .arg("y")
+        .args(["-N", "cosine"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["", "a", "b"],
+        svec!["c", "0.8164965809277261", ""],
+        svec!["d", "0.4082482904638631", "0.7071067811865475"],
+    ];
+    assert_eq!(got, expected);
+
+    // Not relevant with corr
+    let mut cmd = wrk.command("matrix");
+    cmd.arg("corr").args(["-N", "pmi"]).arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn matrix_mtx() {
+    let wrk = Workdir::new("matrix_mtx");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["x", "y"],
+            svec!["a", "c"],
+            svec!["a", "c"],
+            svec!["a", "d"],
+            svec!["b", "d"],
+        ],
+    );
+
+    let mut cmd = wrk.command("matrix");
+    cmd.arg("count")
+        .arg("x")
+        .arg("y")
+        .args(["--format", "mtx"])
+        .args(["-o", "m.mtx"])
+        .arg("data.csv");
+
+    wrk.assert_success(&mut cmd);
+
+    assert_eq!(
+        wrk.from_str::<String>(&wrk.path("m.mtx")),
+        "%%MatrixMarket matrix coordinate real general\n2 2 3\n1 1 2\n2 1 1\n2 2 1\n"
+    );
+    assert_eq!(wrk.from_str::<String>(&wrk.path("m.rows.csv")), "y\nc\nd\n");
+    assert_eq!(wrk.from_str::<String>(&wrk.path("m.cols.csv")), "x\na\nb\n");
+
+    // Correlation
+    wrk.create(
+        "numbers.csv",
+        vec![
+            svec!["a", "b", "c"],
+            svec!["1", "2", "3"],
+            svec!["2", "1", "2"],
+            svec!["3", "4", "1"],
+        ],
+    );
+
+    let mut cmd = wrk.command("matrix");
+    cmd.arg("corr")
+        .arg("-D")
+        .args(["--format", "mtx"])
+        .args(["-o", "corr.mtx"])
+        .arg("numbers.csv");
+
+    wrk.assert_success(&mut cmd);
+
+    assert_eq!(
+        wrk.from_str::<String>(&wrk.path("corr.mtx")),
+        "%%MatrixMarket matrix coordinate real general\n3 3 9\n1 1 1\n1 2 0.6546536707079772\n1 3 -1\n2 1 0.6546536707079772\n2 2 1\n2 3 -0.6546536707079772\n3 1 -1\n3 2 -0.6546536707079772\n3 3 1\n"
+    );
+    assert_eq!(
+        wrk.from_str::<String>(&wrk.path("corr.cols.csv")),
+        "column\na\nb\nc\n"
+    );
+
+    // Sparse formats require a path
+    let mut cmd = wrk.command("matrix");
+    cmd.arg("corr").args(["--format", "mtx"]).arg("numbers.csv");
+
+    wrk.assert_err(&mut cmd);
+}
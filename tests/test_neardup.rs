@@ -0,0 +1,143 @@
+use crate::workdir::Workdir;
+
+fn data() -> Vec<Vec<String>> {
+    vec![
+        svec!["id", "text"],
+        svec![
+            "1",
+            "The quick brown fox jumps over the lazy dog near the river bank today"
+        ],
+        svec![
+            "2",
+            "The quick brown fox jumps over the lazy dog near the river bank today!"
+        ],
+        svec![
+            "3",
+            "Completely unrelated sentence about financial markets and stocks going up"
+        ],
+        svec![
+            "4",
+            "the quick brown fox jumps over the lazy dog near the river bank"
+        ],
+        svec![
+            "5",
+            "Completely unrelated sentence about financial markets and stocks going down"
+        ],
+        svec!["6", "Something else entirely"],
+    ]
+}
+
+#[test]
+fn neardup() {
+    let wrk = Workdir::new("neardup");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("neardup");
+    cmd.args(["text", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let got = got
+        .into_iter()
+        .map(|row| row[0].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(got, svec!["id", "1", "3", "4", "5", "6"]);
+
+    let mut cmd = wrk.command("neardup");
+    cmd.args(["-L", "text", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let got = got
+        .into_iter()
+        .map(|row| row[0].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(got, svec!["id", "1", "3", "5", "6"]);
+}
+
+#[test]
+fn neardup_cluster_flag() {
+    let wrk = Workdir::new("neardup_cluster_flag");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("neardup");
+    cmd.args(["-L", "-C", "cluster", "-f", "dup", "text", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let got = got
+        .into_iter()
+        .map(|row| vec![row[0].clone(), row[2].clone(), row[3].clone()])
+        .collect::<Vec<_>>();
+    let expected = vec![
+        svec!["id", "cluster", "dup"],
+        svec!["1", "0", "false"],
+        svec!["2", "0", "true"],
+        svec!["3", "1", "false"],
+        svec!["4", "0", "true"],
+        svec!["5", "2", "false"],
+        svec!["6", "3", "false"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("neardup");
+    cmd.args(["-p", "-T", "0.5", "-C", "cluster", "text", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let got = got
+        .into_iter()
+        .map(|row| row[2].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(got, svec!["cluster", "0", "0", "1", "0", "1", "2"]);
+}
+
+#[test]
+fn neardup_empty_documents() {
+    let wrk = Workdir::new("neardup_empty_documents");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "text"],
+            svec!["1", ""],
+            svec!["2", "the"],
+            svec!["3", "Something else entirely"],
+            svec!["4", "!"],
+        ],
+    );
+
+    let mut cmd = wrk.command("neardup");
+    cmd.args([
+        "--stoplist",
+        "builtin:en",
+        "-D",
+        "punct",
+        "-C",
+        "cluster",
+        "text",
+        "data.csv",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "text", "cluster"],
+        svec!["1", "", ""],
+        svec!["2", "the", ""],
+        svec!["3", "Something else entirely", "0"],
+        svec!["4", "!", ""],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("neardup");
+    cmd.args(["text", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got.len(), 5);
+}
+
+#[test]
+fn neardup_invalid_bands() {
+    let wrk = Workdir::new("neardup_invalid_bands");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("neardup");
+    cmd.args(["-P", "100", "-b", "32", "text", "data.csv"]);
+
+    wrk.assert_err(&mut cmd);
+}
@@ -95,6 +95,723 @@ fn network_union_find() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn network_centralities() {
+    let wrk = Workdir::new("network_centralities");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["source", "target", "weight"],
+            svec!["A", "B", "1"],
+            svec!["B", "C", "3"],
+        ],
+    );
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .arg("-U")
+        .args(["source", "target"])
+        .args(["-f", "nodelist"])
+        .arg("--betweenness")
+        .arg("--closeness")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["node", "betweenness", "closeness"],
+        svec!["A", "0", "0.6666666666666666"],
+        svec!["B", "1", "1"],
+        svec!["C", "0", "0.6666666666666666"],
+    ];
+    assert_eq!(got, expected);
+
+    wrk.create(
+        "star.csv",
+        vec![
+            svec!["source", "target", "weight"],
+            svec!["A", "B", "1"],
+            svec!["A", "C", "3"],
+        ],
+    );
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "nodelist"])
+        .arg("--pagerank")
+        .arg("--hits")
+        .arg("star.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["node", "pagerank", "hub", "authority"],
+        svec!["A", "0.2597405051058464", "1", "0"],
+        svec!["B", "0.3701297474470768", "0", "0.5"],
+        svec!["C", "0.3701297474470768", "0", "0.5"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "nodelist"])
+        .args(["--weight", "weight"])
+        .arg("--pagerank")
+        .arg("star.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["node", "pagerank"],
+        svec!["A", "0.2597405051058464"],
+        svec!["B", "0.3149351262764616"],
+        svec!["C", "0.42532436861769196"],
+    ];
+    assert_eq!(got, expected);
+
+    // Eigenvector centrality is null for graphs without cycles
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "nodelist"])
+        .arg("--pagerank")
+        .arg("--eigenvector")
+        .arg("star.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["node", "pagerank", "eigenvector"],
+        svec!["A", "0.2597405051058464", "0"],
+        svec!["B", "0.3701297474470768", "0"],
+        svec!["C", "0.3701297474470768", "0"],
+    ];
+    assert_eq!(got, expected);
+
+    wrk.create(
+        "zero.csv",
+        vec![
+            svec!["source", "target", "weight"],
+            svec!["A", "B", "0"],
+            svec!["A", "C", "0"],
+        ],
+    );
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "nodelist"])
+        .args(["--weight", "weight"])
+        .arg("--pagerank")
+        .arg("zero.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["node", "pagerank"],
+        svec!["A", "0.3333333333333333"],
+        svec!["B", "0.3333333333333333"],
+        svec!["C", "0.3333333333333333"],
+    ];
+    assert_eq!(got, expected);
+
+    wrk.create(
+        "negative.csv",
+        vec![
+            svec!["source", "target", "weight"],
+            svec!["A", "B", "-1"],
+            svec!["A", "C", "3"],
+        ],
+    );
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "nodelist"])
+        .args(["--weight", "weight"])
+        .arg("--pagerank")
+        .arg("negative.csv");
+
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "nodelist"])
+        .arg("--betweenness")
+        .args(["--betweenness-samples", "0"])
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+
+    // Centralities require -f nodelist
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .arg("--pagerank")
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn network_louvain() {
+    let wrk = Workdir::new("network_louvain");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["source", "target"],
+            svec!["a", "b"],
+            svec!["b", "c"],
+            svec!["a", "c"],
+            svec!["d", "e"],
+            svec!["e", "f"],
+            svec!["d", "f"],
+            svec!["c", "d"],
+        ],
+    );
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "nodelist"])
+        .arg("--louvain")
+        .args(["--seed", "1"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["node", "community"],
+        svec!["a", "0"],
+        svec!["b", "0"],
+        svec!["c", "0"],
+        svec!["d", "1"],
+        svec!["e", "1"],
+        svec!["f", "1"],
+    ];
+    assert_eq!(got, expected);
+
+    // Existing community attribute is overwritten
+    wrk.create(
+        "nodes.csv",
+        vec![
+            svec!["id", "community", "name"],
+            svec!["a", "x", "A"],
+            svec!["b", "y", "B"],
+        ],
+    );
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("nodelist")
+        .arg("id")
+        .args(["-f", "nodelist"])
+        .arg("--louvain")
+        .arg("nodes.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["node", "community", "name"],
+        svec!["a", "0", "A"],
+        svec!["b", "1", "B"],
+    ];
+    assert_eq!(got, expected);
+
+    // Communities are not relevant with -f stats
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "stats"])
+        .arg("--louvain")
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn network_layout() {
+    let wrk = Workdir::new("network_layout");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["source", "target"],
+            svec!["a", "b"],
+            svec!["b", "c"],
+            svec!["c", "a"],
+            svec!["c", "d"],
+        ],
+    );
+
+    for algorithm in ["forceatlas2", "fruchterman-reingold"] {
+        for barnes_hut in [false, true] {
+            let mut cmd = wrk.command("network");
+            cmd.arg("edgelist")
+                .args(["source", "target"])
+                .args(["-f", "nodelist"])
+                .args(["--layout", algorithm])
+                .args(["--seed", "1"]);
+
+            if barnes_hut {
+                cmd.arg("--barnes-hut");
+            }
+
+            cmd.arg("data.csv");
+
+            let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+
+            assert_eq!(got[0], svec!["node", "x", "y"]);
+            assert_eq!(got.len(), 5);
+
+            for row in got[1..].iter() {
+                for cell in row[1..].iter() {
+                    assert!(cell.parse::<f64>().unwrap().is_finite());
+                }
+            }
+        }
+    }
+
+    // Positions are written as viz elements in gexf
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "gexf"])
+        .args(["--layout", "forceatlas2"])
+        .arg("data.csv");
+
+    let got = wrk.stdout::<String>(&mut cmd);
+    assert_eq!(got.matches("<viz:position ").count(), 4);
+    assert!(!got.contains("title=\"x\""));
+
+    // Existing x & y attributes are overwritten
+    wrk.create(
+        "nodes.csv",
+        vec![
+            svec!["id", "x", "y", "name"],
+            svec!["a", "10", "20", "A"],
+            svec!["b", "30", "40", "B"],
+        ],
+    );
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("nodelist")
+        .arg("id")
+        .args(["-f", "nodelist"])
+        .args(["--layout", "forceatlas2"])
+        .arg("nodes.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got[0], svec!["node", "x", "y", "name"]);
+    assert_eq!(got.len(), 3);
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("nodelist")
+        .arg("id")
+        .args(["-f", "gexf"])
+        .args(["--layout", "forceatlas2"])
+        .arg("nodes.csv");
+
+    let got = wrk.stdout::<String>(&mut cmd);
+    assert_eq!(got.matches("<viz:position ").count(), 2);
+    assert_eq!(got.matches("<attvalue ").count(), 2);
+    assert!(!got.contains("x=\"10\""));
+
+    // Layouts are not relevant with -f stats
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "stats"])
+        .args(["--layout", "forceatlas2"])
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn network_view() {
+    let wrk = Workdir::new("network_view");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["source", "target"],
+            svec!["a", "b"],
+            svec!["b", "c"],
+            svec!["c", "a"],
+            svec!["x", "y"],
+        ],
+    );
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "view"])
+        .args(["--cols", "40"])
+        .args(["--rows", "12"])
+        .args(["--seed", "1"])
+        .arg("data.csv");
+
+    let got = wrk.stdout::<String>(&mut cmd);
+    let lines = got.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 12);
+    assert_eq!(lines[10].trim_end(), "■ component of a (3)");
+    assert_eq!(lines[11].trim_end(), "■ component of x (2)");
+
+    // Coloring by an unknown attribute
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "view"])
+        .args(["--color-by", "community"])
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+
+    // Empty graphs
+    wrk.create("empty.csv", vec![svec!["source", "target"]]);
+
+    for largest_component in [false, true] {
+        let mut cmd = wrk.command("network");
+        cmd.arg("edgelist")
+            .args(["source", "target"])
+            .args(["-f", "view"])
+            .arg("empty.csv");
+
+        if largest_component {
+            cmd.arg("-L");
+        }
+
+        let got = wrk.stdout::<String>(&mut cmd);
+        assert_eq!(got, "");
+    }
+}
+
+#[test]
+fn network_knn() {
+    let wrk = Workdir::new("network_knn");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "x", "y", "vector"],
+            svec!["a", "1", "0", "1|0"],
+            svec!["b", "2", "0", "2|0"],
+            svec!["c", "0", "1", "0|1"],
+            svec!["d", "0", "3", "0|3"],
+        ],
+    );
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("knn")
+        .arg("id")
+        .args(["-s", "x,y"])
+        .args(["-k", "1"])
+        .args(["-f", "edgelist"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["source", "target", "weight"],
+        svec!["a", "b", "1.0"],
+        svec!["b", "a", "1.0"],
+        svec!["c", "d", "1.0"],
+        svec!["d", "c", "1.0"],
+    ];
+    assert_eq!(got, expected);
+
+    // Undirected, from vectors, using euclidean distance
+    let mut cmd = wrk.command("network");
+    cmd.arg("knn")
+        .arg("id")
+        .args(["--from-vectors", "vector"])
+        .args(["--metric", "euclidean"])
+        .args(["-k", "1"])
+        .arg("-U")
+        .args(["-f", "edgelist"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["source", "target", "weight"],
+        svec!["a", "b", "0.5"],
+        svec!["a", "c", "0.4142135623730951"],
+        svec!["c", "d", "0.3333333333333333"],
+    ];
+    assert_eq!(got, expected);
+
+    // Thresholding
+    let mut cmd = wrk.command("network");
+    cmd.arg("knn")
+        .arg("id")
+        .args(["-s", "x,y"])
+        .args(["--min-similarity", "0.5"])
+        .args(["-f", "edgelist"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got.len(), 5);
+
+    // Either -s/--select or --from-vectors must be given
+    let mut cmd = wrk.command("network");
+    cmd.arg("knn").arg("id").arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn network_largest_component() {
+    let wrk = Workdir::new("network_largest_component");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["source", "target"],
+            svec!["x", "y"],
+            svec!["a", "b"],
+            svec!["b", "c"],
+        ],
+    );
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "nodelist"])
+        .arg("-L")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["node"], svec!["a"], svec!["b"], svec!["c"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn network_bipartite_project() {
+    let wrk = Workdir::new("network_bipartite_project");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["user", "hashtag"],
+            svec!["u1", "a"],
+            svec!["u1", "b"],
+            svec!["u2", "a"],
+            svec!["u2", "b"],
+            svec!["u2", "b"],
+            svec!["u3", "b"],
+            svec!["u3", "c"],
+        ],
+    );
+    let mut cmd = wrk.command("network");
+    cmd.arg("bipartite")
+        .args(["user", "hashtag"])
+        .args(["--project", "user"])
+        .args(["-f", "edgelist"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["source", "target", "weight"],
+        svec!["u1", "u2", "2"],
+        svec!["u1", "u3", "1"],
+        svec!["u2", "u3", "1"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("bipartite")
+        .args(["user", "hashtag"])
+        .args(["--project", "hashtag"])
+        .args(["--weighting", "jaccard"])
+        .args(["--min-weight", "0.5"])
+        .args(["-f", "edgelist"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["source", "target", "weight"],
+        svec!["a", "b", "0.6666666666666666"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("bipartite")
+        .args(["user", "hashtag"])
+        .args(["--project", "user"])
+        .args(["--top-k", "1"])
+        .args(["-f", "edgelist"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["source", "target", "weight"],
+        svec!["u1", "u2", "2"],
+        svec!["u1", "u3", "1"],
+    ];
+    assert_eq!(got, expected);
+
+    // --top-k requires --project
+    let mut cmd = wrk.command("network");
+    cmd.arg("bipartite")
+        .args(["user", "hashtag"])
+        .args(["--top-k", "1"])
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn network_paths() {
+    let wrk = Workdir::new("network_paths");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["source", "target", "weight"],
+            svec!["a", "b", "1"],
+            svec!["b", "c", "1"],
+            svec!["a", "c", "5"],
+            svec!["c", "d", "1"],
+            svec!["x", "y", "1"],
+        ],
+    );
+    let mut cmd = wrk.command("network");
+    cmd.arg("paths")
+        .args(["source", "target"])
+        .args(["--from", "a,d"])
+        .args(["--to", "d,y"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["source", "target", "hops", "path"],
+        svec!["a", "d", "2", "a|c|d"],
+        svec!["a", "y", "", ""],
+        svec!["d", "d", "0", "d"],
+        svec!["d", "y", "", ""],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("paths")
+        .args(["source", "target"])
+        .args(["--from", "a"])
+        .args(["--to", "d"])
+        .args(["--weight", "weight"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["source", "target", "hops", "distance", "path"],
+        svec!["a", "d", "3", "3", "a|b|c|d"],
+    ];
+    assert_eq!(got, expected);
+
+    // Direction is followed unless -U is given
+    let mut cmd = wrk.command("network");
+    cmd.arg("paths")
+        .arg("-U")
+        .args(["source", "target"])
+        .args(["--from", "d"])
+        .args(["--to", "a"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["source", "target", "hops", "path"],
+        svec!["d", "a", "2", "d|c|a"],
+    ];
+    assert_eq!(got, expected);
+
+    // Unknown nodes
+    let mut cmd = wrk.command("network");
+    cmd.arg("paths")
+        .args(["source", "target"])
+        .args(["--from", "z"])
+        .args(["--to", "a"])
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+
+    // Negative weights are rejected before writing anything
+    wrk.create(
+        "negative.csv",
+        vec![svec!["source", "target", "weight"], svec!["a", "b", "-1"]],
+    );
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("paths")
+        .args(["source", "target"])
+        .args(["--from", "a"])
+        .args(["--to", "b"])
+        .args(["--weight", "weight"])
+        .arg("negative.csv");
+
+    let output = cmd.output().unwrap();
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+
+    // Output format is not relevant
+    let mut cmd = wrk.command("network");
+    cmd.arg("paths")
+        .args(["source", "target"])
+        .args(["--from", "a"])
+        .args(["--to", "d"])
+        .args(["-f", "json"])
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn network_ego() {
+    let wrk = Workdir::new("network_ego");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["source", "target", "weight"],
+            svec!["a", "b", "1"],
+            svec!["b", "c", "2"],
+            svec!["c", "d", "3"],
+            svec!["x", "y", "4"],
+        ],
+    );
+    let mut cmd = wrk.command("network");
+    cmd.arg("ego")
+        .args(["source", "target"])
+        .args(["--seeds", "d,x"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["source", "target", "weight"],
+        svec!["c", "d", "3"],
+        svec!["x", "y", "4"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("ego")
+        .args(["source", "target"])
+        .args(["--seeds", "d"])
+        .args(["--hops", "2"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["source", "target", "weight"],
+        svec!["b", "c", "2"],
+        svec!["c", "d", "3"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("network");
+    cmd.arg("ego")
+        .args(["source", "target"])
+        .args(["--seeds", "d"])
+        .args(["-f", "edgelist"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got.len(), 2);
+
+    // Only edgelist output is supported
+    let mut cmd = wrk.command("network");
+    cmd.arg("ego")
+        .args(["source", "target"])
+        .args(["--seeds", "d"])
+        .args(["-f", "json"])
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
 #[test]
 fn network_stats() {
     let wrk = Workdir::new("network_stats");
@@ -183,6 +900,109 @@ fn network_gexf() {
 </gexf>");
 }
 
+#[test]
+fn network_graphml() {
+    let wrk = Workdir::new("network_graphml");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["source", "target", "weight"],
+            svec!["A", "B", "1"],
+            svec!["B", "C", "3"],
+        ],
+    );
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "graphml"])
+        .arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(
+        got.trim(),
+        "\
+<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">
+  <key id=\"e0\" for=\"edge\" attr.name=\"weight\" attr.type=\"long\"/>
+  <graph edgedefault=\"directed\">
+    <node id=\"A\"/>
+    <node id=\"B\"/>
+    <node id=\"C\"/>
+    <edge source=\"A\" target=\"B\">
+      <data key=\"e0\">1</data>
+    </edge>
+    <edge source=\"B\" target=\"C\">
+      <data key=\"e0\">3</data>
+    </edge>
+  </graph>
+</graphml>"
+    );
+}
+
+#[test]
+fn network_dot() {
+    let wrk = Workdir::new("network_dot");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["source", "target", "weight"],
+            svec!["A", "B", "1"],
+            svec!["B", "C \"D\"", "3"],
+        ],
+    );
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .arg("-U")
+        .args(["source", "target"])
+        .args(["-f", "dot"])
+        .arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(
+        got.trim(),
+        "\
+graph {
+  \"A\";
+  \"B\";
+  \"C \\\"D\\\"\";
+  \"A\" -- \"B\" [\"weight\"=\"1\"];
+  \"B\" -- \"C \\\"D\\\"\" [\"weight\"=\"3\"];
+}"
+    );
+}
+
+#[test]
+fn network_pajek() {
+    let wrk = Workdir::new("network_pajek");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["source", "target", "weight"],
+            svec!["A", "B", "1"],
+            svec!["B", "C", "3"],
+        ],
+    );
+    let mut cmd = wrk.command("network");
+    cmd.arg("edgelist")
+        .args(["source", "target"])
+        .args(["-f", "pajek"])
+        .args(["--weight", "weight"])
+        .arg("data.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(
+        got.trim(),
+        "\
+*Vertices 3
+1 \"A\"
+2 \"B\"
+3 \"C\"
+*Arcs
+1 2 1
+2 3 3"
+    );
+}
+
 #[test]
 fn network_json() {
     let wrk = Workdir::new("network_json");
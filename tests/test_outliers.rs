@@ -0,0 +1,125 @@
+use crate::workdir::Workdir;
+
+fn data() -> Vec<Vec<String>> {
+    vec![
+        svec!["name", "value", "group"],
+        svec!["a", "10", "x"],
+        svec!["b", "12", "x"],
+        svec!["c", "11", "x"],
+        svec!["d", "13", "x"],
+        svec!["e", "100", "x"],
+        svec!["f", "", "y"],
+        svec!["g", "9", "y"],
+        svec!["h", "10", "y"],
+        svec!["i", "11", "y"],
+        svec!["j", "-50", "y"],
+    ]
+}
+
+#[test]
+fn outliers() {
+    let wrk = Workdir::new("outliers");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("outliers");
+    cmd.args(["-s", "value", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "value", "group"],
+        svec!["e", "100", "x"],
+        svec!["j", "-50", "y"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("outliers");
+    cmd.args(["-s", "value", "-v", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got.len(), 9);
+    assert!(!got.iter().any(|row| row[0] == "e" || row[0] == "j"));
+}
+
+#[test]
+fn outliers_flag_score() {
+    let wrk = Workdir::new("outliers_flag_score");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("outliers");
+    cmd.args([
+        "-s", "value", "-f", "outlier", "--score", "score", "data.csv",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "value", "group", "outlier", "score"],
+        svec!["a", "10", "x", "false", "0"],
+        svec!["b", "12", "x", "false", "0"],
+        svec!["c", "11", "x", "false", "0"],
+        svec!["d", "13", "x", "false", "0.5"],
+        svec!["e", "100", "x", "true", "44"],
+        svec!["f", "", "y", "false", ""],
+        svec!["g", "9", "y", "false", "0.5"],
+        svec!["h", "10", "y", "false", "0"],
+        svec!["i", "11", "y", "false", "0"],
+        svec!["j", "-50", "y", "true", "30"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn outliers_groupby() {
+    let wrk = Workdir::new("outliers_groupby");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("outliers");
+    cmd.args(["-s", "value", "-g", "group", "-m", "mad", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "value", "group"],
+        svec!["e", "100", "x"],
+        svec!["j", "-50", "y"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn outliers_zscore() {
+    let wrk = Workdir::new("outliers_zscore");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("outliers");
+    cmd.args(["-s", "value", "-m", "zscore", "-T", "1.5", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "value", "group"],
+        svec!["e", "100", "x"],
+        svec!["j", "-50", "y"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn outliers_approx() {
+    let wrk = Workdir::new("outliers_approx");
+
+    let mut rows = vec![svec!["value"]];
+
+    for i in 0..20 {
+        rows.push(vec![(10 + i % 3).to_string()]);
+    }
+
+    rows.push(svec!["50"]);
+    rows.push(svec!["11"]);
+
+    wrk.create("data.csv", rows);
+
+    let mut cmd = wrk.command("outliers");
+    cmd.args(["-s", "value", "-a", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["value"], svec!["50"]];
+    assert_eq!(got, expected);
+}
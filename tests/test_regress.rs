@@ -0,0 +1,219 @@
+use crate::workdir::Workdir;
+
+fn data() -> Vec<Vec<String>> {
+    vec![
+        svec!["y", "x", "group", "label"],
+        svec!["1", "1", "a", "0"],
+        svec!["3", "2", "a", "0"],
+        svec!["4", "3", "a", "1"],
+        svec!["7", "4", "b", "0"],
+        svec!["8", "5", "b", "1"],
+        svec!["", "6", "b", "1"],
+        svec!["11", "7", "b", "1"],
+        svec!["9", "8", "a", "0"],
+    ]
+}
+
+#[test]
+fn regress() {
+    let wrk = Workdir::new("regress");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("regress");
+    cmd.args(["y", "x", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "term",
+            "coefficient",
+            "stderr",
+            "statistic",
+            "pvalue",
+            "n",
+            "r2",
+            "adj_r2"
+        ],
+        svec![
+            "intercept",
+            "0.5217391304347843",
+            "1.0484182583112676",
+            "0.4976440712461188",
+            "0.6398450160668259",
+            "7",
+            "0.8825224934001398",
+            "0.8590269920801676"
+        ],
+        svec![
+            "x",
+            "1.3115942028985506",
+            "0.21400748082333784",
+            "6.128730630595411",
+            "0.0016784823246296272",
+            "7",
+            "0.8825224934001398",
+            "0.8590269920801676"
+        ],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn regress_groupby() {
+    let wrk = Workdir::new("regress_groupby");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("regress");
+    cmd.args(["-g", "group", "y", "x", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let got = got
+        .into_iter()
+        .map(|row| row[..3].to_vec())
+        .collect::<Vec<_>>();
+    let expected = vec![
+        svec!["group", "term", "coefficient"],
+        svec!["b", "intercept", "1.4285714285714293"],
+        svec!["b", "x", "1.357142857142857"],
+        svec!["a", "intercept", "0.4482758620689653"],
+        svec!["a", "x", "1.0862068965517242"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn regress_residuals() {
+    let wrk = Workdir::new("regress_residuals");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("regress");
+    cmd.args(["-R", "y", "x", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let got = [&got[0], &got[1], &got[2], &got[6]]
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>();
+    let expected = vec![
+        svec!["y", "x", "group", "label", "prediction", "residual"],
+        svec![
+            "1",
+            "1",
+            "a",
+            "0",
+            "1.8333333333333348",
+            "-0.8333333333333348"
+        ],
+        svec![
+            "3",
+            "2",
+            "a",
+            "0",
+            "3.1449275362318856",
+            "-0.1449275362318856"
+        ],
+        svec!["", "6", "b", "1", "", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn regress_large_offsets() {
+    let wrk = Workdir::new("regress_large_offsets");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["x", "y"],
+            svec!["1600000000", "4800000008"],
+            svec!["1600000060", "4800000186"],
+            svec!["1600000120", "4800000366"],
+            svec!["1600000180", "4800000548"],
+            svec!["1600000240", "4800000728"],
+            svec!["1600000300", "4800000906"],
+        ],
+    );
+
+    let mut cmd = wrk.command("regress");
+    cmd.args(["y", "x", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "term",
+            "coefficient",
+            "stderr",
+            "statistic",
+            "pvalue",
+            "n",
+            "r2",
+            "adj_r2"
+        ],
+        svec![
+            "intercept",
+            "1523816.666666576",
+            "7769935.225354236",
+            "0.19611703604608935",
+            "0.854079043670233",
+            "6",
+            "0.9999895122225465",
+            "0.9999868902781832"
+        ],
+        svec![
+            "x",
+            "2.999047619047619",
+            "0.004856209060576788",
+            "617.569709548586",
+            "0.000000000041247697666638674",
+            "6",
+            "0.9999895122225465",
+            "0.9999868902781832"
+        ],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn regress_logistic() {
+    let wrk = Workdir::new("regress_logistic");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("regress");
+    cmd.args(["-l", "label", "x", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "term",
+            "coefficient",
+            "stderr",
+            "statistic",
+            "pvalue",
+            "n",
+            "pseudo_r2"
+        ],
+        svec![
+            "intercept",
+            "-1.3758396211110904",
+            "1.7104105990866412",
+            "-0.8043914261556777",
+            "0.42117095124415593",
+            "8",
+            "0.07989788661342445"
+        ],
+        svec![
+            "x",
+            "0.30574213802468675",
+            "0.3417785398587746",
+            "0.8945621283039644",
+            "0.37102120427798235",
+            "8",
+            "0.07989788661342445"
+        ],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("regress");
+    cmd.args(["-l", "y", "x", "data.csv"]);
+
+    wrk.assert_err(&mut cmd);
+}
@@ -0,0 +1,290 @@
+use crate::workdir::Workdir;
+
+fn data() -> Vec<Vec<String>> {
+    vec![
+        svec!["x", "y", "group"],
+        svec!["1", "4", "a"],
+        svec!["2", "5", "a"],
+        svec!["3", "6", "b"],
+        svec!["4", "8", "b"],
+    ]
+}
+
+#[test]
+fn test_t() {
+    let wrk = Workdir::new("test_t");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("test");
+    cmd.args(["t", "x", "y", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "x",
+            "y",
+            "n_x",
+            "n_y",
+            "mean_x",
+            "mean_y",
+            "statistic",
+            "df",
+            "pvalue"
+        ],
+        svec![
+            "x",
+            "y",
+            "4",
+            "4",
+            "2.5",
+            "5.75",
+            "-3.0361458822299396",
+            "6",
+            "0.022916100238622297"
+        ],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn test_t_paired() {
+    let wrk = Workdir::new("test_t_paired");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("test");
+    cmd.args(["t", "-P", "x", "y", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "x",
+            "y",
+            "n_x",
+            "n_y",
+            "mean_x",
+            "mean_y",
+            "statistic",
+            "df",
+            "pvalue"
+        ],
+        svec![
+            "x",
+            "y",
+            "4",
+            "4",
+            "2.5",
+            "5.75",
+            "-13",
+            "3",
+            "0.000982801897719405"
+        ],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn test_t_by_category() {
+    let wrk = Workdir::new("test_t_by_category");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("test");
+    cmd.args(["t", "-c", "x", "group", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "x",
+            "y",
+            "n_x",
+            "n_y",
+            "mean_x",
+            "mean_y",
+            "statistic",
+            "df",
+            "pvalue"
+        ],
+        svec![
+            "a",
+            "b",
+            "2",
+            "2",
+            "1.5",
+            "3.5",
+            "-2.82842712474619",
+            "2",
+            "0.1055728090000791"
+        ],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("test");
+    cmd.args(["t", "-c", "x", "y", "data.csv"]);
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn test_t_groupby() {
+    let wrk = Workdir::new("test_t_groupby");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("test");
+    cmd.args(["t", "-g", "group", "x", "y", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "group",
+            "x",
+            "y",
+            "n_x",
+            "n_y",
+            "mean_x",
+            "mean_y",
+            "statistic",
+            "df",
+            "pvalue"
+        ],
+        svec![
+            "a",
+            "x",
+            "y",
+            "2",
+            "2",
+            "1.5",
+            "4.5",
+            "-4.242640687119285",
+            "2",
+            "0.051316701949483776"
+        ],
+        svec![
+            "b",
+            "x",
+            "y",
+            "2",
+            "2",
+            "3.5",
+            "7",
+            "-3.1304951684997055",
+            "2",
+            "0.08867762313422867"
+        ],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn test_mw_ks() {
+    let wrk = Workdir::new("test_mw_ks");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("test");
+    cmd.args(["mw", "x", "y", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["x", "y", "n_x", "n_y", "statistic", "pvalue"],
+        svec!["x", "y", "4", "4", "0.5", "0.04206641220677903"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("test");
+    cmd.args(["ks", "x", "y", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["x", "y", "n_x", "n_y", "statistic", "pvalue"],
+        svec!["x", "y", "4", "4", "0.75", "0.10749046501347267"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn test_nan() {
+    let wrk = Workdir::new("test_nan");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["x", "y"],
+            svec!["1", "4"],
+            svec!["NaN", "5"],
+            svec!["3", "6"],
+        ],
+    );
+
+    for args in [vec!["mw"], vec!["ks"], vec!["corr", "--spearman"]] {
+        let mut cmd = wrk.command("test");
+        cmd.args(args).args(["x", "y", "data.csv"]);
+
+        wrk.assert_err(&mut cmd);
+    }
+}
+
+#[test]
+fn test_chi2() {
+    let wrk = Workdir::new("test_chi2");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["color", "size"],
+            svec!["red", "big"],
+            svec!["red", "big"],
+            svec!["blue", "small"],
+            svec!["blue", "small"],
+            svec!["red", "small"],
+            svec!["blue", "big"],
+        ],
+    );
+
+    let mut cmd = wrk.command("test");
+    cmd.args(["chi2", "color", "size", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["x", "y", "n", "statistic", "df", "pvalue", "cramers_v"],
+        svec![
+            "color",
+            "size",
+            "6",
+            "0.6666666666666666",
+            "1",
+            "0.4142161782425504",
+            "0.3333333333333333"
+        ],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn test_corr() {
+    let wrk = Workdir::new("test_corr");
+    wrk.create("data.csv", data());
+
+    let mut cmd = wrk.command("test");
+    cmd.args(["corr", "x", "y", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["x", "y", "n", "coefficient", "statistic", "df", "pvalue"],
+        svec![
+            "x",
+            "y",
+            "4",
+            "0.9827076298239906",
+            "7.5055534994651",
+            "2",
+            "0.01729237017600856"
+        ],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("test");
+    cmd.args(["corr", "-S", "x", "y", "data.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["x", "y", "n", "coefficient", "statistic", "df", "pvalue"],
+        svec!["x", "y", "4", "1", "inf", "2", "0"],
+    ];
+    assert_eq!(got, expected);
+}
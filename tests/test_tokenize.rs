@@ -40,6 +40,54 @@ fn tokenize_simple() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn tokenize_snowball_stemmer() {
+    let wrk = Workdir::new("tokenize_snowball_stemmer");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["n", "text"],
+            svec!["1", "generations of abilities"],
+            svec!["2", "les chevaux mangeaient"],
+        ],
+    );
+    let mut cmd = wrk.command("tokenize");
+    cmd.arg("words")
+        .arg("text")
+        .args(["--stemmer", "snowball:en"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["n", "tokens"],
+        svec!["1", "generat of abil"],
+        svec!["2", "les chevaux mangeaient"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("tokenize");
+    cmd.arg("words")
+        .arg("text")
+        .args(["--stemmer", "snowball:fr"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["n", "tokens"],
+        svec!["1", "gener of abilit"],
+        svec!["2", "le cheval mang"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("tokenize");
+    cmd.arg("words")
+        .arg("text")
+        .args(["--stemmer", "snowball:klingon"])
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
 #[test]
 fn tokenize_sep() {
     let wrk = Workdir::new("tokenize_sep");
@@ -271,6 +319,154 @@ fn tokenize_stoplist() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn tokenize_phrases() {
+    let wrk = Workdir::new("tokenize_phrases");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["n", "text"],
+            svec!["1", "I love New York and the New York Times"],
+            svec!["2", "Social security is new"],
+        ],
+    );
+    wrk.create(
+        "phrases.csv",
+        vec![
+            svec!["collocation"],
+            svec!["new york"],
+            svec!["new york times"],
+            svec!["social security"],
+        ],
+    );
+
+    let mut cmd = wrk.command("tokenize");
+    cmd.arg("words")
+        .arg("text")
+        .arg("-L")
+        .args(["--phrases", "phrases.csv"])
+        .args(["--ngrams-sep", "_"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["n", "tokens"],
+        svec!["1", "i love new_york and the new_york_times"],
+        svec!["2", "social_security is new"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn tokenize_stoplist_builtin() {
+    let wrk = Workdir::new("tokenize_stoplist_builtin");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["n", "text"],
+            svec!["1", "The cat and le chat"],
+            svec!["2", "A dog, a mouse"],
+        ],
+    );
+    wrk.write("stoplist.txt", "dog\nmouse\n");
+
+    let mut cmd = wrk.command("tokenize");
+    cmd.arg("words")
+        .arg("text")
+        .args(["--stoplist", "builtin:en,fr"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["n", "tokens"],
+        svec!["1", "cat chat"],
+        svec!["2", "dog , mouse"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("tokenize");
+    cmd.arg("words")
+        .arg("text")
+        .args(["--stoplist", "builtin:en+stoplist.txt"])
+        .args(["-D", "punct"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["n", "tokens"],
+        svec!["1", "cat le chat"],
+        svec!["2", ""],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("tokenize");
+    cmd.arg("words")
+        .arg("text")
+        .args(["--stoplist", "builtin:xx"])
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn tokenize_lang() {
+    let wrk = Workdir::new("tokenize_lang");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["n", "text"],
+            svec!["1", "The cat is sleeping on the roof of the house"],
+            svec!["2", "Le chat dort sur le toit de la maison"],
+            svec!["3", "42"],
+            svec!["4", "Собака спит под столом"],
+        ],
+    );
+
+    let mut cmd = wrk.command("tokenize");
+    cmd.arg("lang").arg("text").arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+
+    assert_eq!(got[0], svec!["n", "text", "lang", "lang_confidence"]);
+    assert_eq!(
+        got.iter()
+            .skip(1)
+            .map(|row| row[2].as_str())
+            .collect::<Vec<_>>(),
+        vec!["en", "fr", "", "ru"]
+    );
+    assert_eq!(got[1][3], "1");
+    assert_eq!(got[3][3], "");
+
+    let mut cmd = wrk.command("tokenize");
+    cmd.arg("lang")
+        .arg("text")
+        .args(["--langs", "en,fr"])
+        .args(["-c", "language"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+
+    assert_eq!(
+        got[0],
+        svec!["n", "text", "language", "language_confidence"]
+    );
+    assert_eq!(
+        got.iter()
+            .skip(1)
+            .map(|row| row[2].as_str())
+            .collect::<Vec<_>>(),
+        vec!["en", "fr", "", ""]
+    );
+
+    let mut cmd = wrk.command("tokenize");
+    cmd.arg("lang")
+        .arg("text")
+        .args(["--langs", "en,tlh"])
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
 #[test]
 fn tokenize_ngrams() {
     let wrk = Workdir::new("tokenize_ngrams");
@@ -148,6 +148,35 @@ fn vocab_token() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn vocab_token_stoplist() {
+    let wrk = Workdir::new("vocab_token_stoplist");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["tokens"],
+            svec!["the cat and the dog"],
+            svec!["le chat"],
+        ],
+    );
+    let mut cmd = wrk.command("vocab");
+    cmd.arg("corpus")
+        .args(["--stoplist", "builtin:en,fr"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "doc_count",
+            "token_count",
+            "distinct_token_count",
+            "average_doc_len"
+        ],
+        svec!["2", "3", "3", "1.5"],
+    ];
+    assert_eq!(got, expected);
+}
+
 #[test]
 fn vocab_token_sep() {
     let wrk = Workdir::new("vocab_token_sep");
@@ -219,6 +248,79 @@ fn vocab_doc_token() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn vocab_doc_token_mtx() {
+    let wrk = Workdir::new("vocab_doc_token_mtx");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["doc", "token"],
+            svec!["1", "cat"],
+            svec!["1", "dog"],
+            svec!["1", "cat"],
+            svec!["2", "cat"],
+            svec!["2", "rabbit"],
+        ],
+    );
+    let mut cmd = wrk.command("vocab");
+    cmd.arg("doc-token")
+        .arg("--implode")
+        .args(["--doc", "doc"])
+        .args(["--format", "mtx"])
+        .args(["-o", "dtm.mtx"])
+        .arg("data.csv");
+
+    wrk.assert_success(&mut cmd);
+
+    assert_eq!(
+        wrk.from_str::<String>(&wrk.path("dtm.mtx")),
+        "%%MatrixMarket matrix coordinate real general\n2 3 4\n1 1 2\n1 2 1\n2 1 1\n2 3 1\n"
+    );
+    assert_eq!(
+        wrk.from_str::<String>(&wrk.path("dtm.rows.csv")),
+        "doc\n1\n2\n"
+    );
+    assert_eq!(
+        wrk.from_str::<String>(&wrk.path("dtm.cols.csv")),
+        "token\ncat\ndog\nrabbit\n"
+    );
+
+    // Values
+    let mut cmd = wrk.command("vocab");
+    cmd.arg("doc-token")
+        .arg("--implode")
+        .args(["--doc", "doc"])
+        .args(["--format", "mtx"])
+        .args(["--value", "chi2"])
+        .args(["-o", "chi2.mtx"])
+        .arg("data.csv");
+
+    wrk.assert_success(&mut cmd);
+
+    assert_eq!(
+        wrk.from_str::<String>(&wrk.path("chi2.mtx")),
+        "%%MatrixMarket matrix coordinate real general\n2 3 3\n1 1 0.13888888888888884\n1 2 0.8333333333333334\n2 3 1.8750000000000002\n"
+    );
+
+    // Errors
+    let mut cmd = wrk.command("vocab");
+    cmd.arg("doc-token")
+        .arg("--implode")
+        .args(["--doc", "doc"])
+        .args(["--format", "npz"])
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("vocab");
+    cmd.arg("token")
+        .args(["--format", "mtx"])
+        .args(["-o", "dtm.mtx"])
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
 #[test]
 fn vocab_cooc_sep_no_doc() {
     let wrk = Workdir::new("vocab_cooc_sep_no_doc");
@@ -356,3 +458,191 @@ fn vocab_cooc_no_sep_window() {
     ];
     assert_eq!(got, expected);
 }
+
+#[test]
+fn vocab_keyphrases() {
+    let wrk = Workdir::new("vocab_keyphrases");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["doc", "tokens"],
+            svec!["a", "the red fox , the red fox jumps"],
+            svec!["a", "quick brown fox"],
+            svec!["b", "a lazy dog sleeps"],
+        ],
+    );
+    let mut cmd = wrk.command("vocab");
+    cmd.arg("keyphrases")
+        .args(["-D", "doc"])
+        .args(["--stoplist", "builtin:en"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["doc", "keyphrase", "count", "score"],
+        svec!["a", "quick brown fox", "1", "8.666666666666666"],
+        svec!["a", "red fox jumps", "1", "8.166666666666666"],
+        svec!["a", "red fox", "1", "5.166666666666666"],
+        svec!["b", "lazy dog sleeps", "1", "9"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("vocab");
+    cmd.arg("keyphrases")
+        .args(["-D", "doc"])
+        .args(["--stoplist", "builtin:en"])
+        .args(["-m", "yake"])
+        .args(["-t", "2"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+
+    assert_eq!(
+        got.iter()
+            .map(|row| (row[0].as_str(), row[1].as_str(), row[2].as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("doc", "keyphrase", "count"),
+            ("a", "red fox", "2"),
+            ("a", "red fox jumps", "1"),
+            ("b", "lazy dog sleeps", "1"),
+            ("b", "dog sleeps", "1"),
+        ]
+    );
+}
+
+#[test]
+fn vocab_keyphrases_rows() {
+    let wrk = Workdir::new("vocab_keyphrases_rows");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["tokens"],
+            svec!["the red fox and the quick brown fox"],
+            svec!["a lazy dog"],
+        ],
+    );
+    let mut cmd = wrk.command("vocab");
+    cmd.arg("keyphrases")
+        .args(["--stoplist", "builtin:en"])
+        .args(["--max-words", "2"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["doc", "keyphrase", "count", "score"],
+        svec!["0", "red fox", "1", "4"],
+        svec!["1", "lazy dog", "1", "4"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn vocab_topics() {
+    let wrk = Workdir::new("vocab_topics");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "tokens"],
+            svec!["1", "cat dog cat mouse cat dog"],
+            svec!["2", "dog cat mouse dog"],
+            svec!["3", "python rust code rust"],
+            svec!["4", "code python rust compiler code"],
+            svec!["5", "cat mouse dog"],
+            svec!["6", "rust code compiler"],
+        ],
+    );
+    let mut cmd = wrk.command("vocab");
+    cmd.arg("topics")
+        .args(["-D", "id"])
+        .args(["-k", "2"])
+        .args(["--top-words", "3"])
+        .args(["--seed", "1"])
+        .args(["--doc-topics", "doc-topics.csv"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["topic", "token", "probability"],
+        svec!["0", "cat", "0.3833205814843152"],
+        svec!["0", "dog", "0.3833205814843152"],
+        svec!["0", "mouse", "0.2302983932670237"],
+        svec!["1", "rust", "0.33222866611433305"],
+        svec!["1", "code", "0.33222866611433305"],
+        svec!["1", "python", "0.16652858326429162"],
+    ];
+    assert_eq!(got, expected);
+
+    assert_eq!(
+        wrk.from_str::<String>(&wrk.path("doc-topics.csv")),
+        "id,topic_0,topic_1\n1,0.9838709677419354,0.016129032258064516\n2,0.976190476190476,0.023809523809523808\n3,0.023809523809523808,0.976190476190476\n4,0.019230769230769232,0.9807692307692306\n5,0.96875,0.03125\n6,0.03125,0.96875\n"
+    );
+}
+
+#[test]
+fn vocab_collocations() {
+    let wrk = Workdir::new("vocab_collocations");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["tokens"],
+            svec!["new york is big"],
+            svec!["the new york times"],
+            svec!["i love new york"],
+            svec!["big apple"],
+        ],
+    );
+    let mut cmd = wrk.command("vocab");
+    cmd.arg("collocations")
+        .args(["--max-words", "2"])
+        .args(["--min-count", "2"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "collocation",
+            "n",
+            "count",
+            "expected_count",
+            "G2",
+            "t_score",
+            "pmi",
+            "npmi"
+        ],
+        svec![
+            "new york",
+            "2",
+            "3",
+            "0.6428571428571429",
+            "14.548235495654431",
+            "1.3608970630898323",
+            "2.222392421336448",
+            "1"
+        ],
+    ];
+    assert_eq!(got, expected);
+
+    // Stopwords should break collocations
+    let mut cmd = wrk.command("vocab");
+    cmd.arg("collocations")
+        .args(["--stoplist", "builtin:en"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+
+    assert_eq!(
+        got.iter()
+            .skip(1)
+            .map(|row| row[0].as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "new york",
+            "big apple",
+            "love new",
+            "love new york",
+            "new york times",
+            "york times"
+        ]
+    );
+}
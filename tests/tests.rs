@@ -19,6 +19,7 @@ mod test_agg;
 mod test_behead;
 mod test_bisect;
 mod test_cat;
+mod test_cluster;
 mod test_complete;
 mod test_count;
 mod test_dedup;
@@ -36,11 +37,14 @@ mod test_join;
 mod test_map;
 mod test_matrix;
 mod test_merge;
+mod test_neardup;
 mod test_network;
+mod test_outliers;
 mod test_parallel;
 mod test_partition;
 mod test_pivot;
 mod test_range;
+mod test_regress;
 mod test_rename;
 mod test_reverse;
 mod test_sample;
@@ -53,6 +57,7 @@ mod test_slice;
 mod test_sort;
 mod test_split;
 mod test_stats;
+mod test_test;
 mod test_to;
 mod test_tokenize;
 mod test_top;